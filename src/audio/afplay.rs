@@ -23,9 +23,20 @@ pub fn run_afplay(file_path: &Path, volume: u32) -> Result<()> {
     // afplay -v takes a float: 0.0 = silent, 1.0 = full volume. Clamp to 100 so
     // a mis-configured volume can't amplify past 1.0 and over-drive the output.
     let afplay_volume = volume.min(100) as f32 / 100.0;
+    // Overlapping playbacks wait for a free slot (tts.max_concurrent_playbacks)
+    let _permit = super::playback_limit::PlaybackPermit::acquire();
     // Tell the menu bar avatar which file is playing so it can flap its mouth
     // from the real amplitude. Single choke point: every provider plays here.
     crate::notify_log::set_now_playing(file_path);
+
+    // A selected output device needs SoX; fall back to the default output
+    if let Some(device) = super::device::current_output_device() {
+        match super::device::play_on_device(file_path, volume, &device) {
+            Ok(()) => return Ok(()),
+            Err(e) => tracing::warn!("{}, playing on the default output", e),
+        }
+    }
+
     let status = Command::new("afplay")
         .arg("-v")
         .arg(format!("{:.2}", afplay_volume))
@@ -46,7 +57,8 @@ pub fn run_afplay(file_path: &Path, volume: u32) -> Result<()> {
 /// Play audio data using macOS afplay command
 ///
 /// # Arguments
-/// * `audio_data` - Audio data bytes (must be WAV format)
+/// * `audio_data` - Audio data bytes; the format (WAV, MP3, ...) is detected
+///   from its magic bytes, and headerless data is played as 24kHz 16-bit mono PCM
 /// * `volume` - Volume level 0-100
 /// * `temp_file_prefix` - Prefix for temporary file (e.g., "sumvox_google")
 ///
@@ -60,7 +72,7 @@ pub fn run_afplay(file_path: &Path, volume: u32) -> Result<()> {
 /// - afplay exited with non-zero status
 ///
 /// # Implementation
-/// 1. Writes audio_data to `/tmp/{temp_file_prefix}.wav`
+/// 1. Writes audio_data to `/tmp/{temp_file_prefix}.{ext}` (`ext` from the detected format)
 /// 2. Spawns `afplay -v {volume/100.0:.2} {path}`
 /// 3. Cleans up temp file after playback (best effort, ignores cleanup errors)
 pub fn play_with_afplay(audio_data: &[u8], volume: u32, temp_file_prefix: &str) -> Result<()> {
@@ -71,10 +83,24 @@ pub fn play_with_afplay(audio_data: &[u8], volume: u32, temp_file_prefix: &str)
         temp_file_prefix
     );
 
+    let format = super::format::AudioFormat::detect(audio_data);
+    let wrapped;
+    let audio_data = if format == super::format::AudioFormat::Pcm {
+        // Headerless samples: the 24kHz 16-bit mono PCM cloud providers emit
+        tracing::debug!("No audio header found, playing as 24kHz 16-bit mono PCM");
+        wrapped = super::wav_header::create_wav_file(audio_data, 24000, 1, 16);
+        &wrapped
+    } else {
+        audio_data
+    };
+    let audio_data = super::padding::apply_current_padding(audio_data);
+    let audio_data = super::pan::apply_current_pan(&audio_data);
+
     // Write to temp file
-    let tmp_path = std::env::temp_dir().join(format!("{}.wav", temp_file_prefix));
+    let tmp_path =
+        std::env::temp_dir().join(format!("{}.{}", temp_file_prefix, format.extension()));
     std::fs::File::create(&tmp_path)
-        .and_then(|mut f| f.write_all(audio_data))
+        .and_then(|mut f| f.write_all(&audio_data))
         .map_err(|e| VoiceError::Voice(format!("Failed to write temp WAV: {}", e)))?;
 
     // Capture the result before cleanup so the temp file is removed on every
@@ -0,0 +1,128 @@
+// Output device selection for playback
+// afplay always plays on the system default output, so a named device routes
+// playback through SoX (`sox ... -t coreaudio <device>`) instead. The device
+// is chosen per utterance: a hook event's own device (notification_device,
+// stop_device) overrides tts.output_device.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use crate::error::{Result, VoiceError};
+
+/// Device used by playback in this process, set for one utterance at a time
+static OUTPUT_DEVICE: Mutex<Option<String>> = Mutex::new(None);
+
+/// The device for one event: its own setting, else the global default.
+/// Blank names count as unset (system default output).
+pub fn resolve_output_device<'a>(
+    event_device: Option<&'a str>,
+    default_device: Option<&'a str>,
+) -> Option<&'a str> {
+    let named = |d: Option<&'a str>| d.map(str::trim).filter(|d| !d.is_empty());
+    named(event_device).or_else(|| named(default_device))
+}
+
+/// Device playback should use right now, if any
+pub fn current_output_device() -> Option<String> {
+    OUTPUT_DEVICE.lock().ok().and_then(|d| d.clone())
+}
+
+/// Routes playback to a device until dropped, then restores the previous one
+pub struct OutputDeviceGuard {
+    previous: Option<String>,
+}
+
+impl OutputDeviceGuard {
+    pub fn select(device: Option<&str>) -> Self {
+        let previous = match OUTPUT_DEVICE.lock() {
+            Ok(mut current) => std::mem::replace(&mut *current, device.map(str::to_string)),
+            Err(_) => None,
+        };
+        if let Some(device) = device {
+            tracing::debug!("Playing on output device: {}", device);
+        }
+        Self { previous }
+    }
+}
+
+impl Drop for OutputDeviceGuard {
+    fn drop(&mut self) {
+        if let Ok(mut current) = OUTPUT_DEVICE.lock() {
+            *current = self.previous.take();
+        }
+    }
+}
+
+/// `sox -q -v {volume} {file} -t coreaudio {device}`
+pub fn device_play_command(file_path: &Path, volume: u32, device: &str) -> Command {
+    let mut cmd = Command::new("sox");
+    cmd.arg("-q")
+        .arg("-v")
+        .arg(format!("{:.2}", volume.min(100) as f32 / 100.0))
+        .arg(file_path)
+        .arg("-t")
+        .arg("coreaudio")
+        .arg(device)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    cmd
+}
+
+/// Play `file_path` on `device` to completion (blocking)
+pub fn play_on_device(file_path: &Path, volume: u32, device: &str) -> Result<()> {
+    let status = device_play_command(file_path, volume, device)
+        .status()
+        .map_err(|e| VoiceError::Voice(format!("Failed to run sox: {}", e)))?;
+
+    if !status.success() {
+        return Err(VoiceError::Voice(format!(
+            "sox could not play on output device \"{}\"",
+            device
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_device_overrides_default() {
+        assert_eq!(
+            resolve_output_device(Some("AirPods Pro"), Some("MacBook Pro Speakers")),
+            Some("AirPods Pro")
+        );
+        assert_eq!(
+            resolve_output_device(None, Some("MacBook Pro Speakers")),
+            Some("MacBook Pro Speakers")
+        );
+        assert_eq!(
+            resolve_output_device(Some("  "), Some("MacBook Pro Speakers")),
+            Some("MacBook Pro Speakers")
+        );
+        assert_eq!(resolve_output_device(None, Some("")), None);
+        assert_eq!(resolve_output_device(None, None), None);
+    }
+
+    #[test]
+    fn test_device_play_command_args() {
+        let cmd = device_play_command(Path::new("/tmp/a.wav"), 80, "AirPods Pro");
+        assert_eq!(cmd.get_program(), "sox");
+        let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy()).collect();
+        assert_eq!(
+            args,
+            [
+                "-q",
+                "-v",
+                "0.80",
+                "/tmp/a.wav",
+                "-t",
+                "coreaudio",
+                "AirPods Pro"
+            ]
+        );
+    }
+}
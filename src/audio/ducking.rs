@@ -0,0 +1,219 @@
+// Audio ducking: lower other apps' audio (music) while a voice report plays
+// macOS: scriptable players (Music, Spotify) are turned down through osascript
+// and restored to their previous volume afterwards. Elsewhere this is a no-op.
+
+use std::process::{Command, Stdio};
+
+use crate::error::{Result, VoiceError};
+
+/// Lowers other audio before playback and restores it afterwards
+pub trait AudioDucker: Send + Sync {
+    /// Duck other audio. Returns the state needed to restore it, or None when
+    /// nothing was changed.
+    fn duck(&self) -> Result<Option<String>>;
+
+    /// Undo a previous `duck`
+    fn restore(&self, state: &str) -> Result<()>;
+}
+
+/// Default ducker: leaves other audio alone
+pub struct NoopDucker;
+
+impl AudioDucker for NoopDucker {
+    fn duck(&self) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn restore(&self, _state: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// macOS ducker driving scriptable players through `osascript`
+pub struct OsascriptDucker {
+    apps: Vec<String>,
+    /// Volume while ducked, as a percentage of the app's current volume
+    level_percent: u32,
+}
+
+impl Default for OsascriptDucker {
+    fn default() -> Self {
+        Self {
+            apps: vec!["Music".to_string(), "Spotify".to_string()],
+            level_percent: 30,
+        }
+    }
+}
+
+impl OsascriptDucker {
+    /// Script that lowers every running app and prints `App=volume,` per app
+    pub fn duck_command(&self) -> Command {
+        let mut script = String::from("set previous to \"\"\n");
+        for app in &self.apps {
+            script.push_str(&format!(
+                "if application \"{app}\" is running then\n\
+                 tell application \"{app}\"\n\
+                 set previous to previous & \"{app}=\" & (sound volume as text) & \",\"\n\
+                 set sound volume to (sound volume * {level} div 100)\n\
+                 end tell\n\
+                 end if\n",
+                app = app,
+                level = self.level_percent
+            ));
+        }
+        script.push_str("return previous");
+        osascript(&script)
+    }
+
+    /// Script that sets each app in `state` (`App=volume,...`) back to its volume
+    pub fn restore_command(&self, state: &str) -> Command {
+        let mut script = String::new();
+        for (app, volume) in parse_state(state) {
+            // Only apps we ducked, so the state can't inject arbitrary script
+            if self.apps.iter().any(|a| a == app) {
+                script.push_str(&format!(
+                    "if application \"{app}\" is running then tell application \"{app}\" to set sound volume to {volume}\n"
+                ));
+            }
+        }
+        osascript(&script)
+    }
+}
+
+impl AudioDucker for OsascriptDucker {
+    fn duck(&self) -> Result<Option<String>> {
+        let output = self
+            .duck_command()
+            .output()
+            .map_err(|e| VoiceError::Voice(format!("Failed to run osascript: {}", e)))?;
+        if !output.status.success() {
+            return Err(VoiceError::Voice("osascript ducking failed".to_string()));
+        }
+        let state = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(if state.is_empty() { None } else { Some(state) })
+    }
+
+    fn restore(&self, state: &str) -> Result<()> {
+        let status = self
+            .restore_command(state)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|e| VoiceError::Voice(format!("Failed to run osascript: {}", e)))?;
+        if !status.success() {
+            return Err(VoiceError::Voice("osascript restore failed".to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn osascript(script: &str) -> Command {
+    let mut cmd = Command::new("osascript");
+    cmd.arg("-e").arg(script).stdin(Stdio::null());
+    cmd
+}
+
+/// Parse `App=volume,App=volume,` as printed by the duck script
+fn parse_state(state: &str) -> impl Iterator<Item = (&str, u32)> {
+    state.split(',').filter_map(|entry| {
+        let (app, volume) = entry.split_once('=')?;
+        Some((app.trim(), volume.trim().parse().ok()?))
+    })
+}
+
+/// The ducker for this platform, or a no-op when disabled or unsupported
+pub fn ducker(enabled: bool) -> Box<dyn AudioDucker> {
+    if !enabled {
+        return Box::new(NoopDucker);
+    }
+    if cfg!(target_os = "macos") {
+        Box::new(OsascriptDucker::default())
+    } else {
+        tracing::warn!("tts.duck_other_audio is only supported on macOS, ignoring");
+        Box::new(NoopDucker)
+    }
+}
+
+/// Keeps other audio ducked until dropped
+pub struct DuckGuard {
+    ducker: Box<dyn AudioDucker>,
+    state: Option<String>,
+}
+
+impl DuckGuard {
+    /// Duck other audio if enabled. Failures are logged, never fatal to playback.
+    pub fn new(enabled: bool) -> Self {
+        let ducker = ducker(enabled);
+        let state = ducker.duck().unwrap_or_else(|e| {
+            tracing::warn!("Failed to duck other audio: {}", e);
+            None
+        });
+        Self { ducker, state }
+    }
+}
+
+impl Drop for DuckGuard {
+    fn drop(&mut self) {
+        if let Some(state) = &self.state {
+            if let Err(e) = self.ducker.restore(state) {
+                tracing::warn!("Failed to restore other audio: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script_of(cmd: &Command) -> String {
+        let args: Vec<_> = cmd
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(cmd.get_program(), "osascript");
+        assert_eq!(args[0], "-e");
+        args[1].clone()
+    }
+
+    #[test]
+    fn test_duck_command_lowers_each_app() {
+        let ducker = OsascriptDucker::default();
+        let script = script_of(&ducker.duck_command());
+
+        for app in ["Music", "Spotify"] {
+            assert!(script.contains(&format!("if application \"{}\" is running", app)));
+        }
+        assert!(script.contains("set sound volume to (sound volume * 30 div 100)"));
+        assert!(script.ends_with("return previous"));
+    }
+
+    #[test]
+    fn test_restore_command_uses_recorded_volumes() {
+        let ducker = OsascriptDucker::default();
+        let script = script_of(&ducker.restore_command("Music=80,Spotify=55,"));
+
+        assert!(script.contains("tell application \"Music\" to set sound volume to 80"));
+        assert!(script.contains("tell application \"Spotify\" to set sound volume to 55"));
+
+        // Unknown apps and malformed entries are ignored
+        let script = script_of(&ducker.restore_command("Finder=1,Music=abc"));
+        assert!(script.is_empty());
+    }
+
+    #[test]
+    fn test_disabled_ducker_is_noop() {
+        let ducker = ducker(false);
+        assert_eq!(ducker.duck().unwrap(), None);
+        assert!(ducker.restore("Music=80").is_ok());
+
+        let guard = DuckGuard::new(false);
+        assert!(guard.state.is_none());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_enabled_ducker_is_noop_off_macos() {
+        assert_eq!(ducker(true).duck().unwrap(), None);
+    }
+}
@@ -6,7 +6,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::error::{Result, VoiceError};
-use crate::tts::TtsProvider;
+use crate::tts::{TtsCapabilities, TtsProvider};
 
 /// Mode for audio file selection
 #[derive(Debug, Clone)]
@@ -106,6 +106,15 @@ fn play_audio_blocking(file_path: &Path, volume: u32) -> Result<()> {
     crate::audio::afplay::run_afplay(file_path, volume)
 }
 
+/// What SumVox can do with this provider
+pub const CAPABILITIES: TtsCapabilities = TtsCapabilities {
+    ssml: false,
+    rate: false,
+    volume: true,
+    voices: false,
+    streaming: false,
+};
+
 #[async_trait]
 impl TtsProvider for AudioFileProvider {
     fn name(&self) -> &str {
@@ -134,6 +143,10 @@ impl TtsProvider for AudioFileProvider {
         Ok(true)
     }
 
+    fn capabilities(&self) -> TtsCapabilities {
+        CAPABILITIES
+    }
+
     fn estimate_cost(&self, _char_count: usize) -> f64 {
         // Audio file playback is free
         0.0
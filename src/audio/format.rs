@@ -0,0 +1,81 @@
+// Audio format detection from magic bytes
+// Providers return WAV, MP3 or headerless PCM, and the mime type they report
+// is not always there or right. The leading bytes decide how to play it.
+
+/// Container/codec of a block of audio bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    Mp3,
+    Ogg,
+    Flac,
+    /// No known header: raw PCM samples
+    Pcm,
+}
+
+impl AudioFormat {
+    /// Detect the format from the first bytes of `data`
+    pub fn detect(data: &[u8]) -> Self {
+        match data {
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => Self::Wav,
+            [b'I', b'D', b'3', ..] => Self::Mp3,
+            // MPEG audio frame sync; a layer of 0 is AAC (ADTS), not MP3
+            [0xFF, b, ..] if b & 0xE0 == 0xE0 && b & 0x06 != 0 => Self::Mp3,
+            [b'O', b'g', b'g', b'S', ..] => Self::Ogg,
+            [b'f', b'L', b'a', b'C', ..] => Self::Flac,
+            _ => Self::Pcm,
+        }
+    }
+
+    /// File extension players expect for this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Wav | Self::Pcm => "wav",
+            Self::Mp3 => "mp3",
+            Self::Ogg => "ogg",
+            Self::Flac => "flac",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_from_magic_bytes() {
+        let wav = crate::audio::wav_header::create_wav_file(&[0, 0], 24000, 1, 16);
+        assert_eq!(AudioFormat::detect(&wav), AudioFormat::Wav);
+        assert_eq!(AudioFormat::detect(b"ID3\x04\x00\x00"), AudioFormat::Mp3);
+        assert_eq!(
+            AudioFormat::detect(&[0xFF, 0xFB, 0x90, 0x64]),
+            AudioFormat::Mp3
+        );
+        assert_eq!(
+            AudioFormat::detect(&[0xFF, 0xF3, 0x64, 0xC4]),
+            AudioFormat::Mp3
+        );
+        assert_eq!(AudioFormat::detect(b"OggS\x00\x02"), AudioFormat::Ogg);
+        assert_eq!(AudioFormat::detect(b"fLaC\x00\x00"), AudioFormat::Flac);
+    }
+
+    #[test]
+    fn test_unknown_bytes_are_pcm() {
+        // 16-bit PCM samples, an AAC (ADTS) header and a RIFF that isn't WAVE
+        assert_eq!(
+            AudioFormat::detect(&[0x12, 0x00, 0xF0, 0xFF]),
+            AudioFormat::Pcm
+        );
+        assert_eq!(
+            AudioFormat::detect(&[0xFF, 0xF1, 0x50, 0x80]),
+            AudioFormat::Pcm
+        );
+        assert_eq!(
+            AudioFormat::detect(b"RIFF\x00\x00\x00\x00AVI "),
+            AudioFormat::Pcm
+        );
+        assert_eq!(AudioFormat::detect(&[]), AudioFormat::Pcm);
+        assert_eq!(AudioFormat::Pcm.extension(), "wav");
+        assert_eq!(AudioFormat::Mp3.extension(), "mp3");
+    }
+}
@@ -1,8 +1,17 @@
 // Audio module - audio file playback support
 
 pub mod afplay;
+pub mod device;
+pub mod ducking;
 pub mod file;
+pub mod format;
 pub mod normalize;
+pub mod padding;
+pub mod pan;
+pub mod pcm_stream;
+pub mod playback_limit;
+pub mod status_file;
+pub mod tone;
 pub mod wav_header;
 
 pub use file::AudioFileProvider;
@@ -0,0 +1,185 @@
+// Silence before/after cloud TTS audio (`tts.preroll_ms`, `tts.postroll_ms`)
+// Bluetooth outputs wake up when playback starts and clip the first syllable.
+// Padding the WAV with silence gives the device time to engage. Only PCM WAV
+// is padded; other formats (MP3 from OpenAI) play unchanged.
+
+use std::borrow::Cow;
+use std::sync::Mutex;
+
+/// Padding used by playback in this process, set for one utterance at a time
+static PADDING: Mutex<(u32, u32)> = Mutex::new((0, 0));
+
+/// Pads playback with silence until dropped, then restores the previous padding
+pub struct SilencePaddingGuard {
+    previous: (u32, u32),
+}
+
+impl SilencePaddingGuard {
+    pub fn set(preroll_ms: u32, postroll_ms: u32) -> Self {
+        let previous = match PADDING.lock() {
+            Ok(mut current) => std::mem::replace(&mut *current, (preroll_ms, postroll_ms)),
+            Err(_) => (0, 0),
+        };
+        Self { previous }
+    }
+}
+
+impl Drop for SilencePaddingGuard {
+    fn drop(&mut self) {
+        if let Ok(mut current) = PADDING.lock() {
+            *current = self.previous;
+        }
+    }
+}
+
+/// `audio_data` with the current padding applied, or unchanged when there is
+/// none or it isn't PCM WAV
+pub fn apply_current_padding(audio_data: &[u8]) -> Cow<'_, [u8]> {
+    let (preroll_ms, postroll_ms) = PADDING.lock().map(|p| *p).unwrap_or((0, 0));
+    if preroll_ms == 0 && postroll_ms == 0 {
+        return Cow::Borrowed(audio_data);
+    }
+    match pad_wav(audio_data, preroll_ms, postroll_ms) {
+        Some(padded) => Cow::Owned(padded),
+        None => {
+            tracing::debug!("Audio is not PCM WAV, playing without pre/post-roll");
+            Cow::Borrowed(audio_data)
+        }
+    }
+}
+
+/// Format details needed to write silence
+struct PcmFormat {
+    sample_rate: u32,
+    block_align: u16,
+    bits_per_sample: u16,
+}
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn parse_fmt(chunk: &[u8]) -> Option<PcmFormat> {
+    // 1 = integer PCM, 3 = IEEE float, 0xFFFE = extensible; silence is
+    // all-zero for each except 8-bit unsigned PCM
+    let audio_format = read_u16(chunk, 0)?;
+    if !matches!(audio_format, 1 | 3 | 0xFFFE) {
+        return None;
+    }
+    let format = PcmFormat {
+        sample_rate: read_u32(chunk, 4)?,
+        block_align: read_u16(chunk, 12)?,
+        bits_per_sample: read_u16(chunk, 14)?,
+    };
+    (format.sample_rate > 0 && format.block_align > 0).then_some(format)
+}
+
+/// Silence for `ms` milliseconds in `format`
+fn silence(format: &PcmFormat, ms: u32) -> Vec<u8> {
+    let frames = u64::from(format.sample_rate) * u64::from(ms) / 1000;
+    let fill = if format.bits_per_sample == 8 { 0x80 } else { 0 };
+    vec![fill; frames as usize * usize::from(format.block_align)]
+}
+
+/// Insert `preroll_ms` of silence before and `postroll_ms` after the samples
+/// of a PCM WAV file. None when `wav` isn't a WAV this can pad.
+pub fn pad_wav(wav: &[u8], preroll_ms: u32, postroll_ms: u32) -> Option<Vec<u8>> {
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= wav.len() {
+        let id = &wav[pos..pos + 4];
+        let size = read_u32(wav, pos + 4)? as usize;
+        let body = pos + 8;
+        if id == b"data" {
+            let format = format?;
+            // Streamed WAVs may leave the size unset; take the rest of the file
+            let end = body.saturating_add(size).min(wav.len());
+            let (pre, post) = (silence(&format, preroll_ms), silence(&format, postroll_ms));
+            let data_len = end - body + pre.len() + post.len();
+
+            let mut out = Vec::with_capacity(wav.len() + pre.len() + post.len());
+            out.extend_from_slice(&wav[..pos + 4]);
+            out.extend_from_slice(&u32::try_from(data_len).ok()?.to_le_bytes());
+            out.extend_from_slice(&pre);
+            out.extend_from_slice(&wav[body..end]);
+            out.extend_from_slice(&post);
+            out.extend_from_slice(&wav[end..]);
+            let riff_size = u32::try_from(out.len() - 8).ok()?;
+            out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+            return Some(out);
+        }
+        if id == b"fmt " {
+            format = Some(parse_fmt(wav.get(body..body + size)?)?);
+        }
+        // Chunks are padded to an even size
+        pos = body.checked_add(size + (size & 1))?;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::wav_header::create_wav_file;
+
+    fn data_size(wav: &[u8]) -> usize {
+        read_u32(wav, 40).unwrap() as usize
+    }
+
+    #[test]
+    fn test_padding_adds_expected_samples() {
+        // 100 samples of 16-bit mono at 24kHz
+        let samples: Vec<u8> = (0..100u16).flat_map(|s| (s + 1).to_le_bytes()).collect();
+        let wav = create_wav_file(&samples, 24000, 1, 16);
+
+        let padded = pad_wav(&wav, 250, 100).unwrap();
+        // 250ms = 6000 samples before, 100ms = 2400 after, 2 bytes each
+        assert_eq!(data_size(&padded), (6000 + 100 + 2400) * 2);
+        assert_eq!(padded.len(), 44 + data_size(&padded));
+        assert_eq!(read_u32(&padded, 4).unwrap() as usize, padded.len() - 8);
+
+        let data = &padded[44..];
+        assert!(data[..6000 * 2].iter().all(|&b| b == 0));
+        assert_eq!(&data[6000 * 2..6100 * 2], &samples[..]);
+        assert!(data[6100 * 2..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_padding_counts_frames_per_channel() {
+        let wav = create_wav_file(&[1u8; 8], 48000, 2, 16);
+        let padded = pad_wav(&wav, 10, 0).unwrap();
+        // 10ms = 480 frames of 2 channels x 2 bytes
+        assert_eq!(data_size(&padded), 8 + 480 * 4);
+
+        let eight_bit = pad_wav(&create_wav_file(&[0x90; 4], 8000, 1, 8), 0, 1).unwrap();
+        assert_eq!(
+            &eight_bit[44..],
+            &[0x90, 0x90, 0x90, 0x90, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80]
+        );
+    }
+
+    #[test]
+    fn test_non_wav_is_not_padded() {
+        assert!(pad_wav(b"ID3\x04\x00mp3 frames", 100, 100).is_none());
+        assert!(pad_wav(b"RIFF\x00\x00\x00\x00WAVE", 100, 100).is_none());
+    }
+
+    #[test]
+    fn test_current_padding_follows_guard() {
+        let wav = create_wav_file(&[0, 0], 1000, 1, 16);
+        assert_eq!(apply_current_padding(&wav).len(), wav.len());
+        {
+            let _padding = SilencePaddingGuard::set(5, 5);
+            assert_eq!(apply_current_padding(&wav).len(), wav.len() + 20);
+        }
+        assert_eq!(apply_current_padding(&wav).len(), wav.len());
+    }
+}
@@ -0,0 +1,175 @@
+// Stereo panning (`hooks.claude_code.pan`)
+// With several sessions talking, putting each one at its own place between
+// the left and right speaker tells them apart. Mono 16-bit PCM WAV is split
+// into two channels with per-channel gains; other audio plays unchanged.
+
+use std::borrow::Cow;
+use std::sync::Mutex;
+
+use super::wav_header::create_wav_file;
+
+/// Pan used by playback in this process, set for one utterance at a time
+static PAN: Mutex<Option<f32>> = Mutex::new(None);
+
+/// Pans playback until dropped, then restores the previous pan
+pub struct StereoPanGuard {
+    previous: Option<f32>,
+}
+
+impl StereoPanGuard {
+    pub fn set(pan: Option<f32>) -> Self {
+        let previous = match PAN.lock() {
+            Ok(mut current) => std::mem::replace(&mut *current, pan),
+            Err(_) => None,
+        };
+        Self { previous }
+    }
+}
+
+impl Drop for StereoPanGuard {
+    fn drop(&mut self) {
+        if let Ok(mut current) = PAN.lock() {
+            *current = self.previous;
+        }
+    }
+}
+
+/// `audio_data` panned to the current position, or unchanged when there is
+/// none or it isn't mono 16-bit PCM WAV
+pub fn apply_current_pan(audio_data: &[u8]) -> Cow<'_, [u8]> {
+    let Some(pan) = PAN.lock().ok().and_then(|p| *p) else {
+        return Cow::Borrowed(audio_data);
+    };
+    match pan_wav(audio_data, pan) {
+        Some(panned) => Cow::Owned(panned),
+        None => {
+            tracing::debug!("Audio is not mono 16-bit PCM WAV, playing without panning");
+            Cow::Borrowed(audio_data)
+        }
+    }
+}
+
+/// (left, right) gains for `pan` from -1.0 (left) to 1.0 (right). The
+/// centre plays at full level on both sides, like the unpanned mono
+pub fn channel_gains(pan: f32) -> (f32, f32) {
+    let pan = if pan.is_nan() {
+        0.0
+    } else {
+        pan.clamp(-1.0, 1.0)
+    };
+    ((1.0 - pan).min(1.0), (1.0 + pan).min(1.0))
+}
+
+/// Interleaved stereo samples of `mono` at `pan`
+pub fn pan_samples(mono: &[i16], pan: f32) -> Vec<i16> {
+    let (left, right) = channel_gains(pan);
+    mono.iter()
+        .flat_map(|&s| {
+            let s = f32::from(s);
+            [(s * left).round() as i16, (s * right).round() as i16]
+        })
+        .collect()
+}
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// A stereo WAV of the mono 16-bit PCM `wav` panned to `pan`. None when
+/// `wav` isn't mono 16-bit integer PCM.
+pub fn pan_wav(wav: &[u8], pan: f32) -> Option<Vec<u8>> {
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut sample_rate = None;
+    let mut pos = 12;
+    while pos + 8 <= wav.len() {
+        let id = &wav[pos..pos + 4];
+        let size = read_u32(wav, pos + 4)? as usize;
+        let body = pos + 8;
+        if id == b"fmt " {
+            let (format, channels) = (read_u16(wav, body)?, read_u16(wav, body + 2)?);
+            let bits = read_u16(wav, body + 14)?;
+            if format != 1 || channels != 1 || bits != 16 {
+                return None;
+            }
+            sample_rate = Some(read_u32(wav, body + 4)?);
+        }
+        if id == b"data" {
+            let sample_rate = sample_rate?;
+            // Streamed WAVs may leave the size unset; take the rest of the file
+            let end = body.saturating_add(size).min(wav.len());
+            let mono: Vec<i16> = wav[body..end]
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                .collect();
+            let stereo: Vec<u8> = pan_samples(&mono, pan)
+                .into_iter()
+                .flat_map(i16::to_le_bytes)
+                .collect();
+            return Some(create_wav_file(&stereo, sample_rate, 2, 16));
+        }
+        // Chunks are padded to an even size
+        pos = body.checked_add(size + (size & 1))?;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pan_sets_per_channel_amplitudes() {
+        let mono = [1000i16, -2000];
+        assert_eq!(pan_samples(&mono, -1.0), [1000, 0, -2000, 0]);
+        assert_eq!(pan_samples(&mono, 1.0), [0, 1000, 0, -2000]);
+        assert_eq!(pan_samples(&mono, 0.0), [1000, 1000, -2000, -2000]);
+        assert_eq!(pan_samples(&mono, 0.5), [500, 1000, -1000, -2000]);
+        assert_eq!(pan_samples(&mono, -0.25), [1000, 750, -2000, -1500]);
+        // Out of range positions stop at the edges
+        assert_eq!(pan_samples(&mono, -3.0), pan_samples(&mono, -1.0));
+    }
+
+    #[test]
+    fn test_pan_wav_makes_stereo_wav() {
+        let samples: Vec<u8> = [1000i16, -2000]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let wav = create_wav_file(&samples, 24000, 1, 16);
+
+        let panned = pan_wav(&wav, 0.5).unwrap();
+        assert_eq!(read_u16(&panned, 22), Some(2));
+        assert_eq!(read_u32(&panned, 24), Some(24000));
+        assert_eq!(read_u32(&panned, 40), Some(8));
+        let data: Vec<i16> = panned[44..]
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        assert_eq!(data, [500, 1000, -1000, -2000]);
+    }
+
+    #[test]
+    fn test_only_mono_16_bit_wav_is_panned() {
+        assert!(pan_wav(&create_wav_file(&[0; 8], 24000, 2, 16), 0.5).is_none());
+        assert!(pan_wav(&create_wav_file(&[0x80; 4], 8000, 1, 8), 0.5).is_none());
+        assert!(pan_wav(b"ID3\x04\x00mp3 frames", 0.5).is_none());
+    }
+
+    #[test]
+    fn test_current_pan_follows_guard() {
+        let wav = create_wav_file(&[0, 0], 1000, 1, 16);
+        assert_eq!(apply_current_pan(&wav).len(), wav.len());
+        {
+            let _pan = StereoPanGuard::set(Some(-1.0));
+            assert_eq!(apply_current_pan(&wav).len(), wav.len() + 2);
+        }
+        assert_eq!(apply_current_pan(&wav).len(), wav.len());
+    }
+}
@@ -0,0 +1,120 @@
+// Incremental PCM segmenter for streaming TTS
+// Network chunks arrive at arbitrary byte boundaries (even mid-sample); this
+// regroups them into sample-aligned segments that can each be played as a WAV.
+
+/// Splits a 16-bit PCM byte stream into playable segments.
+///
+/// The first segment is kept short so playback starts quickly; later segments
+/// are longer to keep the number of gaps between afplay runs low.
+pub struct PcmSegmenter {
+    pending: Vec<u8>,
+    first_segment_bytes: usize,
+    segment_bytes: usize,
+    emitted: usize,
+}
+
+impl PcmSegmenter {
+    /// Sizes are in bytes and rounded down to whole 16-bit samples (min 2 bytes)
+    pub fn new(first_segment_bytes: usize, segment_bytes: usize) -> Self {
+        Self {
+            pending: Vec::new(),
+            first_segment_bytes: (first_segment_bytes & !1).max(2),
+            segment_bytes: (segment_bytes & !1).max(2),
+            emitted: 0,
+        }
+    }
+
+    /// Segmenter sized by duration for mono 16-bit PCM at `sample_rate`
+    pub fn for_duration(sample_rate: u32, first_ms: u32, segment_ms: u32) -> Self {
+        let bytes_per_ms = sample_rate as usize * 2 / 1000;
+        Self::new(
+            bytes_per_ms * first_ms as usize,
+            bytes_per_ms * segment_ms as usize,
+        )
+    }
+
+    fn target(&self) -> usize {
+        if self.emitted == 0 {
+            self.first_segment_bytes
+        } else {
+            self.segment_bytes
+        }
+    }
+
+    /// Feed a network chunk; returns every segment that is now complete
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<Vec<u8>> {
+        self.pending.extend_from_slice(chunk);
+
+        let mut ready = Vec::new();
+        while self.pending.len() >= self.target() {
+            let rest = self.pending.split_off(self.target());
+            ready.push(std::mem::replace(&mut self.pending, rest));
+            self.emitted += 1;
+        }
+        ready
+    }
+
+    /// End of stream: return the remaining whole samples, if any.
+    /// A dangling odd byte (truncated sample) is dropped.
+    pub fn finish(mut self) -> Option<Vec<u8>> {
+        let whole = self.pending.len() & !1;
+        self.pending.truncate(whole);
+        if self.pending.is_empty() {
+            None
+        } else {
+            Some(self.pending)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments_across_odd_chunk_boundaries() {
+        let mut segmenter = PcmSegmenter::new(4, 6);
+        let stream: Vec<u8> = (0..17).collect();
+
+        // Chunks split mid-sample: 3 + 1 + 5 + 8 bytes
+        let mut segments = Vec::new();
+        segments.extend(segmenter.push(&stream[0..3]));
+        assert!(segments.is_empty());
+        segments.extend(segmenter.push(&stream[3..4]));
+        assert_eq!(segments, vec![vec![0, 1, 2, 3]]);
+        segments.extend(segmenter.push(&stream[4..9]));
+        segments.extend(segmenter.push(&stream[9..17]));
+
+        assert_eq!(
+            segments,
+            vec![
+                vec![0, 1, 2, 3],
+                vec![4, 5, 6, 7, 8, 9],
+                vec![10, 11, 12, 13, 14, 15],
+            ]
+        );
+        // Byte 16 is half a sample and is dropped
+        assert_eq!(segmenter.finish(), None);
+    }
+
+    #[test]
+    fn test_finish_flushes_tail_and_keeps_order() {
+        let mut segmenter = PcmSegmenter::new(4, 4);
+        let mut bytes = Vec::new();
+        for chunk in [[1u8, 2, 3].as_slice(), &[4, 5, 6, 7], &[8, 9, 10]] {
+            for segment in segmenter.push(chunk) {
+                assert_eq!(segment.len() % 2, 0);
+                bytes.extend(segment);
+            }
+        }
+        bytes.extend(segmenter.finish().unwrap());
+        assert_eq!(bytes, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_for_duration_sizes() {
+        let segmenter = PcmSegmenter::for_duration(24000, 500, 4000);
+        assert_eq!(segmenter.first_segment_bytes, 24000);
+        assert_eq!(segmenter.segment_bytes, 192000);
+    }
+}
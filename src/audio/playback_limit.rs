@@ -0,0 +1,98 @@
+// Cap on simultaneous playbacks in this process (`tts.max_concurrent_playbacks`)
+// Overlapping utterances garble each other and can saturate the output
+// device, so each playback holds a permit and extra ones wait their turn.
+// Separate hook processes are ordered by the queue lock instead.
+
+use std::sync::{Condvar, Mutex};
+
+struct Limit {
+    max: usize,
+    active: usize,
+}
+
+static LIMIT: Mutex<Limit> = Mutex::new(Limit { max: 1, active: 0 });
+static RELEASED: Condvar = Condvar::new();
+
+/// Set how many playbacks may run at once (at least 1)
+pub fn set_max_concurrent_playbacks(max: usize) {
+    if let Ok(mut limit) = LIMIT.lock() {
+        limit.max = max.max(1);
+    }
+    RELEASED.notify_all();
+}
+
+/// Permission to play; the next waiting playback starts when it is dropped
+pub struct PlaybackPermit {
+    held: bool,
+}
+
+impl PlaybackPermit {
+    /// Block until fewer than the maximum playbacks are running
+    pub fn acquire() -> Self {
+        let Ok(mut limit) = LIMIT.lock() else {
+            return Self { held: false };
+        };
+        if limit.active >= limit.max {
+            tracing::debug!("Waiting for {} playback(s) to finish", limit.active);
+        }
+        while limit.active >= limit.max {
+            limit = match RELEASED.wait(limit) {
+                Ok(limit) => limit,
+                Err(_) => return Self { held: false },
+            };
+        }
+        limit.active += 1;
+        Self { held: true }
+    }
+}
+
+impl Drop for PlaybackPermit {
+    fn drop(&mut self) {
+        if !self.held {
+            return;
+        }
+        if let Ok(mut limit) = LIMIT.lock() {
+            limit.active = limit.active.saturating_sub(1);
+        }
+        RELEASED.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    /// Acquire a permit on another thread, reporting when it gets one
+    fn acquire_in_background() -> (mpsc::Receiver<()>, std::thread::JoinHandle<()>) {
+        let (acquired, rx) = mpsc::channel();
+        let handle = std::thread::spawn(move || {
+            let _permit = PlaybackPermit::acquire();
+            acquired.send(()).unwrap();
+        });
+        (rx, handle)
+    }
+
+    // One test covers both limits: the counter is process-wide
+    #[test]
+    fn test_acquire_beyond_limit_waits_for_release() {
+        set_max_concurrent_playbacks(1);
+        let first = PlaybackPermit::acquire();
+        let (acquired, waiter) = acquire_in_background();
+        assert!(acquired.recv_timeout(Duration::from_millis(100)).is_err());
+
+        drop(first);
+        acquired.recv_timeout(Duration::from_secs(5)).unwrap();
+        waiter.join().unwrap();
+
+        // With room for two, a second playback starts right away
+        set_max_concurrent_playbacks(2);
+        let first = PlaybackPermit::acquire();
+        let (acquired, waiter) = acquire_in_background();
+        acquired.recv_timeout(Duration::from_secs(5)).unwrap();
+        waiter.join().unwrap();
+        drop(first);
+        set_max_concurrent_playbacks(1);
+    }
+}
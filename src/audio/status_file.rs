@@ -0,0 +1,117 @@
+// Speaking indicator for editor integrations (`tts.status_file`)
+// While an utterance plays, the file holds
+// `{"speaking": true, "text": ..., "started_at": ..., "pid": ...}`; it is
+// removed when playback ends, fails, times out or is dropped. `pid` lets a
+// poller spot a file left behind by a killed process.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+struct SpeakingStatus<'a> {
+    speaking: bool,
+    text: &'a str,
+    started_at: String,
+    pid: u32,
+}
+
+/// Writes the status file on creation and removes it when dropped
+pub struct StatusFileGuard {
+    path: Option<PathBuf>,
+}
+
+impl StatusFileGuard {
+    /// Mark `text` as speaking in `path` (`~` expanded). No-op without a path.
+    pub fn start(path: Option<&str>, text: &str) -> Self {
+        let Some(path) = path.filter(|p| !p.trim().is_empty()) else {
+            return Self { path: None };
+        };
+        let path = PathBuf::from(shellexpand::tilde(path).to_string());
+        if let Err(e) = write_status(&path, text) {
+            tracing::warn!("Failed to write status file {:?}: {}", path, e);
+        }
+        Self { path: Some(path) }
+    }
+}
+
+impl Drop for StatusFileGuard {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn write_status(path: &Path, text: &str) -> std::io::Result<()> {
+    let status = SpeakingStatus {
+        speaking: true,
+        text,
+        started_at: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        pid: std::process::id(),
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Write then rename so a poller never reads a half-written file
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, serde_json::to_vec(&status)?)?;
+    std::fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Result, VoiceError};
+
+    async fn mock_speak(status_path: &Path, fail: bool) -> Result<()> {
+        let content = std::fs::read_to_string(status_path).unwrap();
+        let status: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(status["speaking"], true);
+        assert_eq!(status["text"], "Build finished");
+        assert!(status["started_at"].is_string());
+        assert_eq!(status["pid"], std::process::id());
+        if fail {
+            return Err(VoiceError::Voice("device busy".into()));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_status_written_during_and_cleared_after_speak() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("speaking.json");
+
+        for fail in [false, true] {
+            let result = {
+                let _status = StatusFileGuard::start(path.to_str(), "Build finished");
+                mock_speak(&path, fail).await
+            };
+            assert_eq!(result.is_err(), fail);
+            assert!(!path.exists());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_cleared_on_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("speaking.json");
+
+        let slow = async {
+            let _status = StatusFileGuard::start(path.to_str(), "Build finished");
+            assert!(path.exists());
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        };
+        let timed_out = tokio::time::timeout(std::time::Duration::from_millis(20), slow).await;
+
+        assert!(timed_out.is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_no_path_writes_nothing() {
+        let guard = StatusFileGuard::start(None, "Build finished");
+        assert!(guard.path.is_none());
+        assert!(StatusFileGuard::start(Some(" "), "x").path.is_none());
+    }
+}
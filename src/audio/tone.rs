@@ -0,0 +1,98 @@
+// Diagnostic test tone
+// A plain sine wave played through afplay, bypassing every TTS provider, so
+// users can tell an audio stack problem apart from a provider/config problem.
+
+use super::wav_header::create_wav_file;
+
+/// Test tone frequency (concert A)
+pub const TEST_TONE_HZ: f32 = 440.0;
+/// Test tone length in milliseconds
+pub const TEST_TONE_MS: u32 = 1000;
+/// Test tone sample rate
+pub const TEST_TONE_SAMPLE_RATE: u32 = 24000;
+/// Peak amplitude as a fraction of i16::MAX (leaves headroom, avoids clipping)
+const TEST_TONE_AMPLITUDE: f32 = 0.5;
+
+/// Generate mono 16-bit sine samples
+pub fn sine_samples(freq_hz: f32, duration_ms: u32, sample_rate: u32, amplitude: f32) -> Vec<i16> {
+    let count = (u64::from(sample_rate) * u64::from(duration_ms) / 1000) as usize;
+    let peak = amplitude.clamp(0.0, 1.0) * f32::from(i16::MAX);
+
+    (0..count)
+        .map(|n| {
+            let t = n as f32 / sample_rate as f32;
+            (peak * (2.0 * std::f32::consts::PI * freq_hz * t).sin()).round() as i16
+        })
+        .collect()
+}
+
+/// Boundary cue frequency (an octave above the test tone)
+const BOUNDARY_TONE_HZ: f32 = 880.0;
+/// Boundary cue length in milliseconds
+const BOUNDARY_TONE_MS: u32 = 120;
+/// Boundary cue amplitude (kept quieter than the test tone)
+const BOUNDARY_TONE_AMPLITUDE: f32 = 0.3;
+
+/// Build the short 880Hz cue played between queued playbacks as a WAV file
+pub fn boundary_tone_wav() -> Vec<u8> {
+    let samples = sine_samples(
+        BOUNDARY_TONE_HZ,
+        BOUNDARY_TONE_MS,
+        TEST_TONE_SAMPLE_RATE,
+        BOUNDARY_TONE_AMPLITUDE,
+    );
+    let pcm: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+    create_wav_file(&pcm, TEST_TONE_SAMPLE_RATE, 1, 16)
+}
+
+/// Stop chime notes: a rising fifth, (frequency, milliseconds)
+const CHIME_NOTES: [(f32, u32); 2] = [(660.0, 90), (990.0, 140)];
+
+/// Build the two-note chime played before a Stop summary (`stop_chime: tone`)
+pub fn chime_wav() -> Vec<u8> {
+    let pcm: Vec<u8> = CHIME_NOTES
+        .iter()
+        .flat_map(|&(freq, ms)| {
+            sine_samples(freq, ms, TEST_TONE_SAMPLE_RATE, BOUNDARY_TONE_AMPLITUDE)
+        })
+        .flat_map(|s| s.to_le_bytes())
+        .collect();
+    create_wav_file(&pcm, TEST_TONE_SAMPLE_RATE, 1, 16)
+}
+
+/// Build the 440Hz / 1s test tone as a complete WAV file
+pub fn test_tone_wav() -> Vec<u8> {
+    let samples = sine_samples(
+        TEST_TONE_HZ,
+        TEST_TONE_MS,
+        TEST_TONE_SAMPLE_RATE,
+        TEST_TONE_AMPLITUDE,
+    );
+    let pcm: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+    create_wav_file(&pcm, TEST_TONE_SAMPLE_RATE, 1, 16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sine_samples_length_and_peak() {
+        let samples = sine_samples(440.0, 1000, 24000, 0.5);
+        assert_eq!(samples.len(), 24000);
+
+        let peak = samples.iter().map(|s| s.unsigned_abs()).max().unwrap();
+        let expected = (0.5 * f32::from(i16::MAX)) as u16;
+        // A 440Hz sine sampled at 24kHz gets within a couple of LSBs of its peak
+        assert!(peak <= expected + 1, "peak {} above {}", peak, expected);
+        assert!(peak >= expected - 2, "peak {} below {}", peak, expected);
+    }
+
+    #[test]
+    fn test_tone_wav_size() {
+        let wav = test_tone_wav();
+        assert_eq!(&wav[0..4], b"RIFF");
+        // 44-byte header + 1s of mono 16-bit PCM
+        assert_eq!(wav.len(), 44 + TEST_TONE_SAMPLE_RATE as usize * 2);
+    }
+}
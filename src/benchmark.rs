@@ -0,0 +1,301 @@
+// Provider benchmarks for `sumvox benchmark`
+// Runs the same request against each configured provider (in turn, or a few
+// at once with --max-concurrency) and reports latency, tokens and cost,
+// fastest first, to help order the fallback chains.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use futures_util::stream::{self, StreamExt};
+
+use crate::config::ModelPricing;
+use crate::llm::{self, GenerationRequest, LlmProvider};
+use crate::tts::TtsProvider;
+
+/// Timing of one provider run
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkResult {
+    pub kind: &'static str,
+    pub name: String,
+    pub latency: Duration,
+    /// Input and output tokens (LLM only)
+    pub tokens: Option<(u32, u32)>,
+    pub cost_usd: f64,
+    /// Why the run failed; its latency is then the time until the failure
+    pub error: Option<String>,
+}
+
+impl BenchmarkResult {
+    fn failed(kind: &'static str, name: &str, latency: Duration, error: String) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            latency,
+            tokens: None,
+            cost_usd: 0.0,
+            error: Some(error),
+        }
+    }
+}
+
+/// Time one `generate` call. Returns None for an unavailable provider.
+pub async fn bench_llm(
+    name: &str,
+    provider: &dyn LlmProvider,
+    model: &str,
+    request: &GenerationRequest,
+    pricing: &HashMap<String, ModelPricing>,
+    timeout: Duration,
+) -> Option<BenchmarkResult> {
+    if !provider.is_available() {
+        return None;
+    }
+
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(timeout, provider.generate(request)).await;
+    let latency = started.elapsed();
+
+    Some(match outcome {
+        Ok(Ok(response)) => BenchmarkResult {
+            kind: "llm",
+            name: name.to_string(),
+            latency,
+            tokens: Some((response.input_tokens, response.output_tokens)),
+            cost_usd: llm::price_for(
+                pricing,
+                provider,
+                model,
+                response.input_tokens,
+                response.output_tokens,
+            ),
+            error: None,
+        },
+        Ok(Err(e)) => BenchmarkResult::failed("llm", name, latency, e.to_string()),
+        Err(_) => BenchmarkResult::failed(
+            "llm",
+            name,
+            latency,
+            format!("timed out after {}s", timeout.as_secs()),
+        ),
+    })
+}
+
+/// Time speaking `text` (synthesis plus playback, since providers only
+/// expose `speak`). Returns None for an unavailable provider.
+pub async fn bench_tts(
+    name: &str,
+    provider: &dyn TtsProvider,
+    text: &str,
+    timeout: Duration,
+) -> Option<BenchmarkResult> {
+    if !provider.is_available() {
+        return None;
+    }
+
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(timeout, provider.speak(text)).await;
+    let latency = started.elapsed();
+
+    Some(match outcome {
+        Ok(Ok(true)) => BenchmarkResult {
+            kind: "tts",
+            name: name.to_string(),
+            latency,
+            tokens: None,
+            cost_usd: provider.estimate_cost(text.chars().count()),
+            error: None,
+        },
+        Ok(Ok(false)) => BenchmarkResult::failed("tts", name, latency, "not spoken".into()),
+        Ok(Err(e)) => BenchmarkResult::failed("tts", name, latency, e.to_string()),
+        Err(_) => BenchmarkResult::failed(
+            "tts",
+            name,
+            latency,
+            format!("timed out after {}s", timeout.as_secs()),
+        ),
+    })
+}
+
+/// Run `probes` with at most `max_concurrency` (at least 1) in flight,
+/// collecting their outputs in completion order. Each `bench_*` probe starts
+/// its own clock when polled, so latencies stay per provider.
+pub async fn run_concurrently<T, Fut>(
+    probes: impl IntoIterator<Item = Fut>,
+    max_concurrency: usize,
+) -> Vec<T>
+where
+    Fut: Future<Output = T>,
+{
+    stream::iter(probes)
+        .buffer_unordered(max_concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// Fastest successful run first; failures go last
+pub fn sort_results(results: &mut [BenchmarkResult]) {
+    results.sort_by_key(|r| (r.error.is_some(), r.latency));
+}
+
+/// One row per result: status, kind, provider, latency, tokens, cost
+pub fn format_table(results: &[BenchmarkResult]) -> String {
+    let name_width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    let mut table = String::new();
+    for r in results {
+        let tokens = match r.tokens {
+            Some((input, output)) => format!("{}/{}", input, output),
+            None => "-".to_string(),
+        };
+        let detail = match &r.error {
+            Some(e) => e.clone(),
+            None => format!("${:.6}", r.cost_usd),
+        };
+        table.push_str(&format!(
+            "{} {:<3}  {:<width$}  {:>7}  {:>9}  {}\n",
+            if r.error.is_none() { "✓" } else { "✗" },
+            r.kind,
+            r.name,
+            format!("{}ms", r.latency.as_millis()),
+            tokens,
+            detail,
+            width = name_width
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::mock::{RecordingProvider, MOCK_MODEL};
+
+    fn provider(delay_ms: u64) -> RecordingProvider {
+        RecordingProvider::new()
+            .usage(120, 12)
+            .priced(0.000001)
+            .slow(Duration::from_millis(delay_ms))
+    }
+
+    fn request() -> GenerationRequest {
+        GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 50,
+            temperature: 0.3,
+            disable_thinking: true,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        }
+    }
+
+    async fn run(name: &str, provider: &RecordingProvider) -> Option<BenchmarkResult> {
+        bench_llm(
+            name,
+            provider,
+            MOCK_MODEL,
+            &request(),
+            &HashMap::new(),
+            Duration::from_secs(5),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_results_sorted_by_latency_with_failures_last() {
+        let failing = provider(0).failing("503 Service Unavailable");
+        let mut results = vec![
+            run("slow", &provider(80)).await.unwrap(),
+            run("broken", &failing).await.unwrap(),
+            run("fast", &provider(5)).await.unwrap(),
+            run("medium", &provider(40)).await.unwrap(),
+        ];
+
+        sort_results(&mut results);
+
+        let order: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(order, ["fast", "medium", "slow", "broken"]);
+        assert!(results[0].latency >= Duration::from_millis(5));
+        assert_eq!(results[0].tokens, Some((120, 12)));
+        assert!((results[0].cost_usd - 0.000132).abs() < 1e-9);
+        assert!(results[3].error.as_deref().unwrap().contains("503"));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_runs_collect_every_provider() {
+        let providers = [("slow", 80), ("fast", 5), ("medium", 40), ("instant", 0)];
+        for max_concurrency in [0, 1, 2, 4, 10] {
+            let probes = providers.iter().map(|&(name, delay_ms)| async move {
+                run(name, &provider(delay_ms)).await.unwrap()
+            });
+            let mut results = run_concurrently(probes, max_concurrency).await;
+            assert_eq!(results.len(), providers.len());
+
+            sort_results(&mut results);
+            let order: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+            assert_eq!(order, ["instant", "fast", "medium", "slow"]);
+            for result in &results {
+                let (_, delay_ms) = providers.iter().find(|(n, _)| *n == result.name).unwrap();
+                assert!(result.latency >= Duration::from_millis(*delay_ms));
+            }
+        }
+
+        // Timed independently: running alongside the slow probe doesn't
+        // add its wait to the fast one
+        let probes = providers
+            .iter()
+            .map(|&(name, delay_ms)| async move { run(name, &provider(delay_ms)).await.unwrap() });
+        let results = run_concurrently(probes, 4).await;
+        let fast = results.iter().find(|r| r.name == "fast").unwrap();
+        assert!(fast.latency < Duration::from_millis(80));
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_provider_is_skipped() {
+        let missing = provider(0).unavailable();
+        assert!(run("missing", &missing).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_slow_provider_times_out() {
+        let result = bench_llm(
+            "ollama",
+            &provider(5_000),
+            MOCK_MODEL,
+            &request(),
+            &HashMap::new(),
+            Duration::from_millis(20),
+        )
+        .await
+        .unwrap();
+        assert!(result.error.unwrap().contains("timed out"));
+    }
+
+    #[test]
+    fn test_format_table() {
+        let results = vec![
+            BenchmarkResult {
+                kind: "llm",
+                name: "google".to_string(),
+                latency: Duration::from_millis(412),
+                tokens: Some((120, 12)),
+                cost_usd: 0.000132,
+                error: None,
+            },
+            BenchmarkResult::failed(
+                "llm",
+                "openai",
+                Duration::from_millis(90),
+                "401 Unauthorized".to_string(),
+            ),
+        ];
+        let table = format_table(&results);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "✓ llm  google    412ms     120/12  $0.000132");
+        assert_eq!(
+            lines[1],
+            "✗ llm  openai     90ms          -  401 Unauthorized"
+        );
+    }
+}
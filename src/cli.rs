@@ -1,7 +1,9 @@
 // CLI argument parsing for sumvox
 // Subcommand-based architecture for versatile voice notification
 
-use clap::{Parser, Subcommand};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "sumvox")]
@@ -11,6 +13,75 @@ pub struct Cli {
     /// Subcommand to execute (optional: auto-detect json mode from stdin if not specified)
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// With no subcommand and stdin a terminal: error, quick_start or wizard
+    /// (overrides hooks.terminal_fallback)
+    #[arg(long, value_parser = ["error", "quick_start", "wizard"])]
+    pub terminal_fallback: Option<String>,
+
+    /// Re-read the config file on every load instead of reusing the copy
+    /// parsed earlier in this process (daemon, watch)
+    #[arg(long, global = true)]
+    pub fresh_config: bool,
+}
+
+/// Stands in for `--provider` when the flag isn't given
+pub const LLM_ENV: &str = "SUMVOX_LLM";
+/// Stands in for `--tts` when the flag isn't given
+pub const TTS_ENV: &str = "SUMVOX_TTS";
+
+impl Cli {
+    /// Parse the process arguments, then fill `--provider`/`--tts` from
+    /// SUMVOX_LLM/SUMVOX_TTS where they weren't passed. Exits on bad input
+    /// like `Cli::parse`.
+    pub fn parse_with_env() -> Self {
+        Self::try_parse_with_env(std::env::args_os(), |name| std::env::var(name).ok())
+            .unwrap_or_else(|e| e.exit())
+    }
+
+    /// `parse_with_env` with the arguments and environment lookup supplied.
+    /// Precedence: flag, then environment, then config (an unset provider
+    /// or `--tts auto` defers to the configured fallback chain).
+    pub fn try_parse_with_env<I, T>(
+        args: I,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        let matches = Self::command().try_get_matches_from(args)?;
+        let mut cli = Self::from_arg_matches(&matches)?;
+        if let Some((_, sub_matches)) = matches.subcommand() {
+            cli.apply_env(sub_matches, |name| {
+                env(name).filter(|v| !v.trim().is_empty())
+            });
+        }
+        Ok(cli)
+    }
+
+    fn apply_env(&mut self, matches: &ArgMatches, env: impl Fn(&str) -> Option<String>) {
+        // Only asked of subcommands that have `--tts`: clap panics on unknown ids
+        let fill_tts = |tts: &mut String| {
+            if matches.value_source("tts") == Some(ValueSource::DefaultValue) {
+                if let Some(engine) = env(TTS_ENV) {
+                    *tts = engine;
+                }
+            }
+        };
+        match &mut self.command {
+            Some(Commands::Sum(args)) => {
+                if args.provider.is_none() {
+                    args.provider = env(LLM_ENV);
+                }
+                fill_tts(&mut args.tts);
+            }
+            Some(Commands::Say(args)) => fill_tts(&mut args.tts),
+            Some(Commands::Daemon(args)) => fill_tts(&mut args.tts),
+            Some(Commands::Watch(args)) => fill_tts(&mut args.tts),
+            _ => {}
+        }
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -26,13 +97,49 @@ pub enum Commands {
 
     /// Initialize config file at ~/.config/sumvox/config.yaml
     Init(InitArgs),
+
+    /// List models available from configured LLM providers (cached for 24h)
+    Models(ModelsArgs),
+
+    /// Inspect and verify provider credentials
+    Credentials(CredentialsArgs),
+
+    /// Print why the last voice report went silent (last recorded failure)
+    WhySilent,
+
+    /// Check config, credentials and recent failures; exits non-zero if a check fails
+    Doctor(DoctorArgs),
+
+    /// Inspect Notification hook payloads
+    Notifications(NotificationsArgs),
+
+    /// Describe what each LLM and TTS provider supports
+    Providers(ProvidersArgs),
+
+    /// Speak each line written to a named pipe until interrupted
+    Daemon(DaemonArgs),
+
+    /// Time each configured provider on the same input, fastest first
+    Benchmark(BenchmarkArgs),
+
+    /// Speak a summary of each new turn as a transcript grows, until interrupted
+    Watch(WatchArgs),
+
+    /// Show how a transcript is read: entry counts, turn starts and what the
+    /// last 1-3 turns extract to
+    TranscriptStats(TranscriptStatsArgs),
 }
 
 /// Arguments for 'say' subcommand
 #[derive(Parser, Debug, Clone)]
 pub struct SayArgs {
     /// Text to speak
-    pub text: String,
+    #[arg(required_unless_present_any = ["ssml_file", "test_tone"])]
+    pub text: Option<String>,
+
+    /// Speak an authored SSML file verbatim (SSML-capable engines only, e.g. cloud_tts)
+    #[arg(long, conflicts_with = "text")]
+    pub ssml_file: Option<PathBuf>,
 
     /// TTS engine: auto, macos, google
     #[arg(long, default_value = "auto")]
@@ -44,13 +151,31 @@ pub struct SayArgs {
     #[arg(long)]
     pub voice: Option<String>,
 
-    /// Speech rate for macOS say (90-300), ignored for Google TTS
-    #[arg(long, default_value = "200")]
-    pub rate: u32,
+    /// Speech rate for macOS say (90-300), ignored for Google TTS.
+    /// Default: the provider's `rate`, else `tts.wpm`, else 200
+    #[arg(long)]
+    pub rate: Option<u32>,
 
     /// Volume level (0-100)
     #[arg(long)]
     pub volume: Option<u32>,
+
+    /// Give up on a TTS provider after this many seconds (auto mode tries the next one)
+    #[arg(long, default_value = "60")]
+    pub timeout: u64,
+
+    /// Speak one sentence at a time with a pause between them
+    /// (length set by tts.pause_between_sentences_ms)
+    #[arg(long, conflicts_with = "ssml_file")]
+    pub split_sentences: bool,
+
+    /// Return immediately and speak from a background process
+    #[arg(long = "async")]
+    pub async_mode: bool,
+
+    /// Play a 1s 440Hz test tone, bypassing TTS (audio stack diagnostic)
+    #[arg(long, hide = true, conflicts_with_all = ["text", "ssml_file"])]
+    pub test_tone: bool,
 }
 
 /// Arguments for 'sum' subcommand
@@ -71,10 +196,33 @@ pub struct SumArgs {
     #[arg(long, default_value = "50")]
     pub max_length: usize,
 
+    /// What the summary should emphasize (overrides summarization.focus)
+    #[arg(long)]
+    pub focus: Option<String>,
+
+    /// Phrase the summary as this character, e.g. "a terse sysadmin"
+    /// (overrides summarization.persona)
+    #[arg(long)]
+    pub persona: Option<String>,
+
+    /// Reasoning effort for OpenAI reasoning models
+    /// (overrides llm.parameters.reasoning_effort)
+    #[arg(long, value_parser = ["low", "medium", "high", "xhigh"])]
+    pub reasoning_effort: Option<String>,
+
+    /// Daily LLM spend limit in USD for this run only
+    /// (replaces cost.daily_limit_usd)
+    #[arg(long, value_name = "USD")]
+    pub budget_override: Option<f64>,
+
     /// Only output summary, don't speak
     #[arg(long)]
     pub no_speak: bool,
 
+    /// Print a JSON object (summary, provider, tokens, cost) instead of plain text
+    #[arg(long)]
+    pub output_json: bool,
+
     /// Request timeout in seconds
     #[arg(long, default_value = "10")]
     pub timeout: u64,
@@ -87,9 +235,10 @@ pub struct SumArgs {
     #[arg(long)]
     pub voice: Option<String>,
 
-    /// Speech rate for macOS say (90-300)
-    #[arg(long, default_value = "200")]
-    pub rate: u32,
+    /// Speech rate for macOS say (90-300).
+    /// Default: the provider's `rate`, else `tts.wpm`, else 200
+    #[arg(long)]
+    pub rate: Option<u32>,
 
     /// Volume level (0-100)
     #[arg(long)]
@@ -99,13 +248,24 @@ pub struct SumArgs {
 /// Arguments for 'json' subcommand (hook mode)
 #[derive(Parser, Debug, Clone)]
 pub struct JsonArgs {
-    /// JSON format: auto, claude-code, gemini-cli, generic
+    /// JSON format: auto, claude-code, generic. An explicit format skips
+    /// detection and fails if the input lacks its fields
     #[arg(long, default_value = "auto")]
     pub format: String,
 
     /// Request timeout in seconds
     #[arg(long, default_value = "10")]
     pub timeout: u64,
+
+    /// Print the spoken text to stdout as well, so it isn't lost when audio
+    /// fails. Default: on unless stdout is a terminal
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub print_summary: Option<bool>,
+
+    /// Daily LLM spend limit in USD for this run only
+    /// (replaces cost.daily_limit_usd)
+    #[arg(long, value_name = "USD")]
+    pub budget_override: Option<f64>,
 }
 
 /// Arguments for 'init' subcommand
@@ -114,6 +274,214 @@ pub struct InitArgs {
     /// Force overwrite existing config
     #[arg(long)]
     pub force: bool,
+
+    /// Ask for provider, API key, TTS engine/voice and cost cap instead of writing defaults
+    #[arg(long, short = 'i')]
+    pub interactive: bool,
+}
+
+/// Arguments for 'models' subcommand
+#[derive(Parser, Debug, Clone)]
+pub struct ModelsArgs {
+    /// Only list models for this provider (default: all configured providers)
+    #[arg(long)]
+    pub provider: Option<String>,
+
+    /// Ignore the cached list and fetch from the provider
+    #[arg(long)]
+    pub refresh: bool,
+}
+
+/// Arguments for 'daemon' subcommand
+#[derive(Parser, Debug, Clone)]
+pub struct DaemonArgs {
+    /// Named pipe to read line-delimited text from (create with mkfifo)
+    #[arg(long)]
+    pub fifo: PathBuf,
+
+    /// TTS engine: auto, macos, google
+    #[arg(long, default_value = "auto")]
+    pub tts: String,
+
+    /// Voice name (engine-specific)
+    #[arg(long)]
+    pub voice: Option<String>,
+
+    /// Speech rate for macOS say (90-300), ignored for Google TTS.
+    /// Default: the provider's `rate`, else `tts.wpm`, else 200
+    #[arg(long)]
+    pub rate: Option<u32>,
+
+    /// Volume level (0-100)
+    #[arg(long)]
+    pub volume: Option<u32>,
+
+    /// Give up on a TTS provider after this many seconds (auto mode tries the next one)
+    #[arg(long, default_value = "60")]
+    pub timeout: u64,
+}
+
+/// Arguments for 'watch' subcommand
+#[derive(Parser, Debug, Clone)]
+pub struct WatchArgs {
+    /// Transcript (.jsonl) to watch
+    pub transcript: PathBuf,
+
+    /// Seconds between checks of the transcript
+    #[arg(long, default_value = "2")]
+    pub interval: u64,
+
+    /// Minimum seconds between spoken updates; turns in between are combined
+    #[arg(long, default_value = "60")]
+    pub min_interval: u64,
+
+    /// TTS engine: auto, macos, google
+    #[arg(long, default_value = "auto")]
+    pub tts: String,
+
+    /// Volume level (0-100)
+    #[arg(long)]
+    pub volume: Option<u32>,
+
+    /// LLM request timeout in seconds
+    #[arg(long, default_value = "10")]
+    pub timeout: u64,
+}
+
+/// Arguments for 'transcript-stats' subcommand
+#[derive(Parser, Debug, Clone)]
+pub struct TranscriptStatsArgs {
+    /// Transcript (.jsonl or .jsonl.gz) to inspect
+    pub transcript: PathBuf,
+}
+
+/// Arguments for 'benchmark' subcommand
+#[derive(Parser, Debug, Clone)]
+pub struct BenchmarkArgs {
+    /// Text to summarize (or, with --tts, to speak) with every provider
+    #[arg(long)]
+    pub text: String,
+
+    /// Benchmark TTS providers instead of LLM providers (plays the text aloud)
+    #[arg(long)]
+    pub tts: bool,
+
+    /// Give up on a provider after this many seconds
+    #[arg(long, default_value = "30")]
+    pub timeout: u64,
+
+    /// Probe up to this many providers at once. The default of 1 keeps
+    /// timings free of contention.
+    #[arg(long, default_value = "1")]
+    pub max_concurrency: usize,
+}
+
+/// Arguments for 'credentials' subcommand
+#[derive(Parser, Debug, Clone)]
+pub struct CredentialsArgs {
+    #[command(subcommand)]
+    pub action: CredentialsAction,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CredentialsAction {
+    /// List configured providers and whether their credentials are set
+    List(CredentialsListArgs),
+
+    /// Probe configured providers with a minimal live request
+    Test(CredentialsTestArgs),
+
+    /// Store an API key for a configured provider in the config file
+    /// (or the system keychain with --keychain)
+    Set(CredentialsSetArgs),
+
+    /// Delete a provider's API key from the system keychain
+    Remove(CredentialsRemoveArgs),
+}
+
+/// Arguments for 'credentials list'
+#[derive(Parser, Debug, Clone)]
+pub struct CredentialsListArgs {
+    /// Print a JSON array instead of the table
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Arguments for 'doctor' subcommand
+#[derive(Parser, Debug, Clone)]
+pub struct DoctorArgs {
+    /// Print a JSON array of { check, status, detail } instead of the list
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Arguments for 'credentials test'
+#[derive(Parser, Debug, Clone)]
+pub struct CredentialsTestArgs {
+    /// Provider name to test (LLM and TTS entries with this name)
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub provider: Option<String>,
+
+    /// Test every configured LLM and TTS provider
+    #[arg(long)]
+    pub all: bool,
+
+    /// Per-provider probe timeout in seconds
+    #[arg(long, default_value = "10")]
+    pub timeout: u64,
+}
+
+/// Arguments for 'credentials set'
+#[derive(Parser, Debug, Clone)]
+pub struct CredentialsSetArgs {
+    /// Provider name (LLM and TTS entries with this name get the key)
+    pub provider: String,
+
+    /// Read the key from the first line of stdin instead of prompting
+    #[arg(long, conflicts_with = "value")]
+    pub stdin: bool,
+
+    /// The key itself (ends up in shell history; prefer --stdin)
+    #[arg(long)]
+    pub value: Option<String>,
+
+    /// Store the key in the system keychain instead of the config file
+    #[arg(long)]
+    pub keychain: bool,
+}
+
+/// Arguments for 'credentials remove'
+#[derive(Parser, Debug, Clone)]
+pub struct CredentialsRemoveArgs {
+    /// Provider name whose keychain entry to delete
+    pub provider: String,
+}
+
+/// Arguments for 'notifications' subcommand
+#[derive(Parser, Debug, Clone)]
+pub struct NotificationsArgs {
+    #[command(subcommand)]
+    pub action: NotificationsAction,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum NotificationsAction {
+    /// Print the notification types recorded with
+    /// hooks.claude_code.observe_notification_types
+    Observed,
+}
+
+/// Arguments for 'providers' subcommand
+#[derive(Parser, Debug, Clone)]
+pub struct ProvidersArgs {
+    #[command(subcommand)]
+    pub action: ProvidersAction,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ProvidersAction {
+    /// Print a capability matrix for every provider
+    Info,
 }
 
 #[cfg(test)]
@@ -127,9 +495,11 @@ mod tests {
 
         match cli.command {
             Some(Commands::Say(args)) => {
-                assert_eq!(args.text, "Hello world");
+                assert_eq!(args.text, Some("Hello world".to_string()));
+                assert_eq!(args.ssml_file, None);
+                assert_eq!(args.timeout, 60);
                 assert_eq!(args.tts, "auto");
-                assert_eq!(args.rate, 200);
+                assert_eq!(args.rate, None);
                 assert_eq!(args.voice, None);
                 assert_eq!(args.volume, None);
             }
@@ -140,23 +510,89 @@ mod tests {
     #[test]
     fn test_parse_say_with_options() {
         let cli = Cli::try_parse_from([
-            "sumvox", "say", "Hello", "--tts", "macos", "--voice", "Tingting", "--rate", "180",
-            "--volume", "75",
+            "sumvox",
+            "say",
+            "Hello",
+            "--tts",
+            "macos",
+            "--voice",
+            "Tingting",
+            "--rate",
+            "180",
+            "--volume",
+            "75",
+            "--timeout",
+            "15",
         ])
         .unwrap();
 
         match cli.command {
             Some(Commands::Say(args)) => {
-                assert_eq!(args.text, "Hello");
+                assert_eq!(args.text, Some("Hello".to_string()));
                 assert_eq!(args.tts, "macos");
                 assert_eq!(args.voice, Some("Tingting".to_string()));
-                assert_eq!(args.rate, 180);
+                assert_eq!(args.rate, Some(180));
                 assert_eq!(args.volume, Some(75));
+                assert_eq!(args.timeout, 15);
+                assert!(!args.async_mode);
             }
             _ => panic!("Expected Say command"),
         }
     }
 
+    #[test]
+    fn test_parse_say_async() {
+        let cli = Cli::try_parse_from(["sumvox", "say", "Build done", "--async"]).unwrap();
+
+        match cli.command {
+            Some(Commands::Say(args)) => assert!(args.async_mode),
+            _ => panic!("Expected Say command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_say_ssml_file() {
+        let cli = Cli::try_parse_from([
+            "sumvox",
+            "say",
+            "--ssml-file",
+            "greeting.ssml",
+            "--tts",
+            "cloud_tts",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Commands::Say(args)) => {
+                assert_eq!(args.text, None);
+                assert_eq!(args.ssml_file, Some(PathBuf::from("greeting.ssml")));
+            }
+            _ => panic!("Expected Say command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_say_requires_text_or_ssml_file() {
+        assert!(Cli::try_parse_from(["sumvox", "say"]).is_err());
+        assert!(
+            Cli::try_parse_from(["sumvox", "say", "Hi", "--ssml-file", "greeting.ssml"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_say_test_tone() {
+        let cli = Cli::try_parse_from(["sumvox", "say", "--test-tone"]).unwrap();
+
+        match cli.command {
+            Some(Commands::Say(args)) => {
+                assert!(args.test_tone);
+                assert_eq!(args.text, None);
+            }
+            _ => panic!("Expected Say command"),
+        }
+        assert!(Cli::try_parse_from(["sumvox", "say", "Hi", "--test-tone"]).is_err());
+    }
+
     #[test]
     fn test_parse_sum_command() {
         let cli = Cli::try_parse_from(["sumvox", "sum", "Long text to summarize"]).unwrap();
@@ -168,6 +604,7 @@ mod tests {
                 assert_eq!(args.model, None);
                 assert_eq!(args.max_length, 50);
                 assert!(!args.no_speak);
+                assert!(!args.output_json);
             }
             _ => panic!("Expected Sum command"),
         }
@@ -186,6 +623,15 @@ mod tests {
             "--max-length",
             "100",
             "--no-speak",
+            "--output-json",
+            "--focus",
+            "failing tests",
+            "--persona",
+            "a terse sysadmin",
+            "--reasoning-effort",
+            "high",
+            "--budget-override",
+            "2.5",
         ])
         .unwrap();
 
@@ -196,9 +642,108 @@ mod tests {
                 assert_eq!(args.model, Some("gemini-2.5-flash".to_string()));
                 assert_eq!(args.max_length, 100);
                 assert!(args.no_speak);
+                assert!(args.output_json);
+                assert_eq!(args.focus.as_deref(), Some("failing tests"));
+                assert_eq!(args.persona.as_deref(), Some("a terse sysadmin"));
+                assert_eq!(args.reasoning_effort.as_deref(), Some("high"));
+                assert_eq!(args.budget_override, Some(2.5));
             }
             _ => panic!("Expected Sum command"),
         }
+
+        let bad = Cli::try_parse_from(["sumvox", "sum", "-", "--reasoning-effort", "max"]);
+        assert!(bad.is_err());
+    }
+
+    #[test]
+    fn test_env_fills_provider_and_tts_unless_flag_given() {
+        let env = |name: &str| match name {
+            LLM_ENV => Some("groq".to_string()),
+            TTS_ENV => Some("macos".to_string()),
+            _ => None,
+        };
+        let parse =
+            |args: &[&str], env: &dyn Fn(&str) -> Option<String>| match Cli::try_parse_with_env(
+                args.iter().copied(),
+                env,
+            )
+            .unwrap()
+            .command
+            {
+                Some(Commands::Sum(args)) => (args.provider, args.tts),
+                _ => panic!("Expected Sum command"),
+            };
+
+        // Config: nothing set leaves the fallback chain in charge
+        assert_eq!(
+            parse(&["sumvox", "sum", "x"], &|_| None),
+            (None, "auto".into())
+        );
+        // Blank values count as unset
+        assert_eq!(
+            parse(&["sumvox", "sum", "x"], &|_| Some(" ".into())),
+            (None, "auto".into())
+        );
+
+        // Environment beats config
+        assert_eq!(
+            parse(&["sumvox", "sum", "x"], &env),
+            (Some("groq".into()), "macos".into())
+        );
+
+        // Flags beat environment, including an explicit `--tts auto`
+        assert_eq!(
+            parse(
+                &[
+                    "sumvox",
+                    "sum",
+                    "x",
+                    "--provider",
+                    "google",
+                    "--tts",
+                    "auto"
+                ],
+                &env
+            ),
+            (Some("google".into()), "auto".into())
+        );
+
+        // Other commands with --tts pick it up too
+        let cli = Cli::try_parse_with_env(["sumvox", "say", "hi"], env).unwrap();
+        match cli.command {
+            Some(Commands::Say(args)) => assert_eq!(args.tts, "macos"),
+            _ => panic!("Expected Say command"),
+        }
+        // ...and commands without it are left alone
+        assert!(Cli::try_parse_with_env(["sumvox", "json"], env).is_ok());
+    }
+
+    #[test]
+    fn test_parse_terminal_fallback() {
+        let cli = Cli::try_parse_from(["sumvox", "--terminal-fallback", "quick_start"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.terminal_fallback.as_deref(), Some("quick_start"));
+
+        assert!(Cli::try_parse_from(["sumvox", "--terminal-fallback", "help"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_fresh_config_anywhere() {
+        assert!(
+            !Cli::try_parse_from(["sumvox", "json"])
+                .unwrap()
+                .fresh_config
+        );
+        assert!(
+            Cli::try_parse_from(["sumvox", "--fresh-config", "json"])
+                .unwrap()
+                .fresh_config
+        );
+        assert!(
+            Cli::try_parse_from(["sumvox", "json", "--fresh-config"])
+                .unwrap()
+                .fresh_config
+        );
     }
 
     #[test]
@@ -250,8 +795,170 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_models_command() {
+        let cli =
+            Cli::try_parse_from(["sumvox", "models", "--provider", "google", "--refresh"]).unwrap();
+
+        match cli.command {
+            Some(Commands::Models(args)) => {
+                assert_eq!(args.provider, Some("google".to_string()));
+                assert!(args.refresh);
+            }
+            _ => panic!("Expected Models command"),
+        }
+    }
+
     #[test]
     fn test_cli_verify() {
         Cli::command().debug_assert();
     }
+
+    #[test]
+    fn test_parse_credentials_test() {
+        let cli = Cli::try_parse_from(["sumvox", "credentials", "test", "--all"]).unwrap();
+        match cli.command {
+            Some(Commands::Credentials(CredentialsArgs {
+                action: CredentialsAction::Test(args),
+            })) => {
+                assert!(args.all);
+                assert_eq!(args.provider, None);
+                assert_eq!(args.timeout, 10);
+            }
+            _ => panic!("Expected Credentials command"),
+        }
+
+        let cli = Cli::try_parse_from(["sumvox", "credentials", "test", "google"]).unwrap();
+        match cli.command {
+            Some(Commands::Credentials(CredentialsArgs {
+                action: CredentialsAction::Test(args),
+            })) => assert_eq!(args.provider.as_deref(), Some("google")),
+            _ => panic!("Expected Credentials command"),
+        }
+
+        // Needs a provider or --all, not both
+        assert!(Cli::try_parse_from(["sumvox", "credentials", "test"]).is_err());
+        assert!(Cli::try_parse_from(["sumvox", "credentials", "test", "google", "--all"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_credentials_set() {
+        let cli =
+            Cli::try_parse_from(["sumvox", "credentials", "set", "google", "--stdin"]).unwrap();
+        match cli.command {
+            Some(Commands::Credentials(CredentialsArgs {
+                action: CredentialsAction::Set(args),
+            })) => {
+                assert_eq!(args.provider, "google");
+                assert!(args.stdin);
+                assert_eq!(args.value, None);
+                assert!(!args.keychain);
+            }
+            _ => panic!("Expected Credentials command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["sumvox", "credentials", "set", "openai", "--keychain"]).unwrap();
+        match cli.command {
+            Some(Commands::Credentials(CredentialsArgs {
+                action: CredentialsAction::Set(args),
+            })) => assert!(args.keychain),
+            _ => panic!("Expected Credentials command"),
+        }
+        let cli = Cli::try_parse_from(["sumvox", "credentials", "remove", "openai"]).unwrap();
+        match cli.command {
+            Some(Commands::Credentials(CredentialsArgs {
+                action: CredentialsAction::Remove(args),
+            })) => assert_eq!(args.provider, "openai"),
+            _ => panic!("Expected Credentials command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "sumvox",
+            "credentials",
+            "set",
+            "google",
+            "--stdin",
+            "--value",
+            "k"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_json_print_summary() {
+        let parse = |args: &[&str]| match Cli::try_parse_from(args).unwrap().command {
+            Some(Commands::Json(args)) => args.print_summary,
+            _ => panic!("Expected Json command"),
+        };
+        assert_eq!(parse(&["sumvox", "json"]), None);
+        assert_eq!(parse(&["sumvox", "json", "--print-summary"]), Some(true));
+        assert_eq!(
+            parse(&["sumvox", "json", "--print-summary=false"]),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        let cli = Cli::try_parse_from(["sumvox", "watch", "/tmp/s.jsonl", "--min-interval", "30"])
+            .unwrap();
+        match cli.command {
+            Some(Commands::Watch(args)) => {
+                assert_eq!(args.transcript, PathBuf::from("/tmp/s.jsonl"));
+                assert_eq!(args.interval, 2);
+                assert_eq!(args.min_interval, 30);
+            }
+            _ => panic!("Expected Watch command"),
+        }
+        assert!(Cli::try_parse_from(["sumvox", "watch"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_doctor() {
+        let cli = Cli::try_parse_from(["sumvox", "doctor", "--json"]).unwrap();
+        match cli.command {
+            Some(Commands::Doctor(args)) => assert!(args.json),
+            _ => panic!("Expected Doctor command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_why_silent() {
+        let cli = Cli::try_parse_from(["sumvox", "why-silent"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::WhySilent)));
+    }
+
+    #[test]
+    fn test_parse_notifications_observed() {
+        let cli = Cli::try_parse_from(["sumvox", "notifications", "observed"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Notifications(NotificationsArgs {
+                action: NotificationsAction::Observed
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_parse_providers_info() {
+        let cli = Cli::try_parse_from(["sumvox", "providers", "info"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Providers(ProvidersArgs {
+                action: ProvidersAction::Info
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_parse_credentials_list() {
+        let cli = Cli::try_parse_from(["sumvox", "credentials", "list", "--json"]).unwrap();
+        match cli.command {
+            Some(Commands::Credentials(CredentialsArgs {
+                action: CredentialsAction::List(args),
+            })) => assert!(args.json),
+            _ => panic!("Expected Credentials command"),
+        }
+    }
 }
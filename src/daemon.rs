@@ -0,0 +1,116 @@
+// FIFO daemon - speak each line written to a named pipe
+// `sumvox daemon --fifo <path>` keeps the pipe open across writers and speaks
+// every non-empty line as it arrives, until the process is interrupted.
+
+use std::future::Future;
+use std::path::Path;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+
+use crate::error::{Result, VoiceError};
+
+/// Read line-delimited text from `reader` and call `speak` once per non-empty
+/// line, in order. A failed speak is logged and the loop moves on.
+///
+/// Returns the number of lines handed to `speak` once the reader hits EOF.
+pub async fn speak_lines<R, F, Fut>(reader: R, mut speak: F) -> Result<usize>
+where
+    R: AsyncBufRead + Unpin,
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut lines = reader.lines();
+    let mut spoken = 0;
+
+    while let Some(line) = lines.next_line().await? {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        spoken += 1;
+        if let Err(e) = speak(line.to_string()).await {
+            tracing::warn!("Failed to speak line from FIFO: {}", e);
+        }
+    }
+
+    Ok(spoken)
+}
+
+/// Speak lines from the FIFO at `path` forever.
+///
+/// Opening a FIFO blocks until a writer shows up, and each writer's close is
+/// an EOF, so the pipe is reopened after every EOF to wait for the next one.
+pub async fn run_fifo<F, Fut>(path: &Path, mut speak: F) -> Result<()>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    use std::os::unix::fs::FileTypeExt;
+
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| VoiceError::Config(format!("Cannot open FIFO {:?}: {}", path, e)))?;
+    if !metadata.file_type().is_fifo() {
+        return Err(VoiceError::Config(format!(
+            "{:?} is not a named pipe (create one with mkfifo)",
+            path
+        )));
+    }
+
+    tracing::info!("Listening on FIFO {:?}", path);
+    loop {
+        let file = tokio::fs::File::open(path).await?;
+        let spoken = speak_lines(BufReader::new(file), &mut speak).await?;
+        tracing::debug!("FIFO writer closed after {} line(s), reopening", spoken);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[tokio::test]
+    async fn test_speak_lines_speaks_each_line_once() {
+        let input: &[u8] = b"Build finished\n\n  Tests passed  \nDeploy started";
+        let spoken = RefCell::new(Vec::new());
+
+        let count = speak_lines(input, |line| {
+            spoken.borrow_mut().push(line);
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(
+            spoken.into_inner(),
+            ["Build finished", "Tests passed", "Deploy started"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_speak_lines_continues_after_failure() {
+        let input: &[u8] = b"first\nsecond\n";
+        let calls = RefCell::new(0);
+
+        let count = speak_lines(input, |_| {
+            *calls.borrow_mut() += 1;
+            async { Err(VoiceError::Voice("device busy".into())) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(calls.into_inner(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_fifo_rejects_regular_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let err = run_fifo(file.path(), |_| async { Ok(()) })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not a named pipe"));
+    }
+}
@@ -0,0 +1,115 @@
+// Desktop notification when the TTS fallback chain fails (`tts.notify_on_failure`)
+// A silent failure otherwise goes unnoticed, so the summary is shown instead:
+// macOS posts it through osascript, Linux through notify-send. Best-effort;
+// a missing notifier is only logged.
+
+use std::process::{Command, Stdio};
+
+use crate::config::TtsConfig;
+
+const FAILURE_TITLE: &str = "Sumvox: summary generated but couldn't play audio";
+
+/// Longest summary shown in the notification body, in characters
+const BODY_MAX_CHARS: usize = 240;
+
+/// Title and body for a summary that couldn't be spoken
+pub fn failure_message(text: &str) -> (String, String) {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let body = if text.chars().count() > BODY_MAX_CHARS {
+        let cut: String = text.chars().take(BODY_MAX_CHARS - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        text
+    };
+    (FAILURE_TITLE.to_string(), body)
+}
+
+/// Handle `text` that no TTS provider played: record why for `why-silent`
+/// and post the failure notification when `tts.notify_on_failure` is set.
+/// Call once per utterance, after any retry.
+pub fn report_silent_playback(tts: &TtsConfig, text: &str, failures: &[String]) {
+    crate::notify_log::record_last_error(&crate::notify_log::failure_reason("TTS", failures));
+    if let Some(err) = failures.last() {
+        tracing::warn!(
+            "All TTS providers failed. Last error: {}. Notification will be silent.",
+            err
+        );
+    } else {
+        tracing::warn!("No TTS providers available. Notification will be silent.");
+    }
+    if tts.notify_on_failure {
+        notify_tts_failure(text);
+    }
+}
+
+/// Post the failure notification for `text`
+pub fn notify_tts_failure(text: &str) {
+    let (title, body) = failure_message(text);
+    let Some(mut command) = notify_command(&title, &body) else {
+        tracing::debug!("No desktop notifier on this platform");
+        return;
+    };
+    match command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+    {
+        Ok(status) if status.success() => tracing::debug!("Posted TTS failure notification"),
+        Ok(status) => tracing::warn!("Desktop notification failed: {}", status),
+        Err(e) => tracing::warn!("Failed to run desktop notifier: {}", e),
+    }
+}
+
+/// The platform's notifier command, or None when there is none
+fn notify_command(title: &str, body: &str) -> Option<Command> {
+    if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("osascript");
+        cmd.arg("-e").arg(applescript(title, body));
+        Some(cmd)
+    } else if cfg!(target_os = "linux") {
+        let mut cmd = Command::new("notify-send");
+        cmd.arg("--app-name=sumvox").arg(title).arg(body);
+        Some(cmd)
+    } else {
+        None
+    }
+}
+
+/// `display notification` script with both strings quoted for AppleScript
+fn applescript(title: &str, body: &str) -> String {
+    format!(
+        "display notification {} with title {}",
+        applescript_string(body),
+        applescript_string(title)
+    )
+}
+
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_message_includes_summary() {
+        let (title, body) = failure_message("Refactored the parser.\nAll 42 tests pass.");
+        assert_eq!(title, "Sumvox: summary generated but couldn't play audio");
+        assert_eq!(body, "Refactored the parser. All 42 tests pass.");
+
+        let (_, body) = failure_message(&"word ".repeat(100));
+        assert_eq!(body.chars().count(), BODY_MAX_CHARS);
+        assert!(body.ends_with("word…"));
+    }
+
+    #[test]
+    fn test_applescript_quotes_title_and_body() {
+        let (title, body) = failure_message(r#"Renamed "cfg" to C:\config"#);
+        assert_eq!(
+            applescript(&title, &body),
+            r#"display notification "Renamed \"cfg\" to C:\\config" with title "Sumvox: summary generated but couldn't play audio""#
+        );
+    }
+}
@@ -7,6 +7,10 @@ pub enum VoiceError {
     #[error("Configuration error: {0}")]
     Config(String),
 
+    /// Every problem found by config validation, not just the first
+    #[error("Configuration error: {}", join_config_errors(.0))]
+    InvalidConfig(Vec<ConfigError>),
+
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -33,6 +37,56 @@ pub enum LlmError {
 
     #[error("API request failed: {0}")]
     Request(String),
+
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    /// The requested model is overloaded; a sibling model may still work
+    #[error("Model unavailable: {0}")]
+    ModelUnavailable(String),
+
+    /// The provider doesn't have the requested model (e.g. not pulled into Ollama)
+    #[error("Model not found: {0}")]
+    ModelNotFound(String),
+
+    /// Today's spend has reached `cost.daily_limit_usd`
+    #[error("Daily budget exceeded: {0}")]
+    BudgetExceeded(String),
+}
+
+impl LlmError {
+    /// Worth retrying the same provider after a short wait
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::RateLimited(_) | Self::ModelUnavailable(_))
+    }
+}
+
+/// A config validation problem, located by its field path
+/// (e.g. `tts.providers[1].rate`)
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("{message}")]
+pub struct ConfigError {
+    pub path: String,
+    pub value: String,
+    pub message: String,
+}
+
+impl ConfigError {
+    pub fn new(path: impl Into<String>, value: impl ToString, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            value: value.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+fn join_config_errors(errors: &[ConfigError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 pub type Result<T> = std::result::Result<T, VoiceError>;
@@ -63,6 +117,34 @@ mod tests {
         assert!(matches!(voice_err, VoiceError::Json(_)));
     }
 
+    #[test]
+    fn test_invalid_config_display_joins_messages() {
+        let err = VoiceError::InvalidConfig(vec![
+            ConfigError::new(
+                "llm.parameters.max_tokens",
+                0,
+                "max_tokens must be greater than 0",
+            ),
+            ConfigError::new(
+                "tts.providers[0].volume",
+                150,
+                "TTS volume 150 out of range",
+            ),
+        ]);
+        assert_eq!(
+            err.to_string(),
+            "Configuration error: max_tokens must be greater than 0; TTS volume 150 out of range"
+        );
+    }
+
+    #[test]
+    fn test_llm_error_transient() {
+        assert!(LlmError::RateLimited("429".into()).is_transient());
+        assert!(LlmError::ModelUnavailable("overloaded".into()).is_transient());
+        assert!(!LlmError::Request("400".into()).is_transient());
+        assert!(!LlmError::ModelNotFound("gone".into()).is_transient());
+    }
+
     #[test]
     fn test_queue_error() {
         let err = VoiceError::Queue("lock timeout".to_string());
@@ -1,21 +1,26 @@
 // Claude Code hook handler
-// Processes JSON input from Claude Code Stop and Notification hooks
+// Processes JSON input from Claude Code Stop, Notification and PreCompact hooks
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::config::{effective_disable_thinking, SumvoxConfig};
+use crate::config::{
+    effective_disable_thinking, ClaudeCodeHookConfig, LlmProviderConfig, PlaybackConfig,
+    SummarizationConfig, SumvoxConfig, Turns,
+};
 use crate::error::Result;
-use crate::llm::GenerationRequest;
-use crate::provider_factory::ProviderFactory;
-use crate::queue::{NotificationQueue, QueueLock};
+use crate::hooks::HookFormat;
+use crate::llm::http::HttpContext;
+use crate::llm::{self, GenerationRequest};
+use crate::provider_factory::{prioritize_session_provider, ProviderFactory};
+use crate::queue::{NotificationQueue, NotificationSequence, QueueLock};
 use crate::transcript::TranscriptReader;
 use crate::tts::{create_tts_from_config, resolve_tts_provider, TtsEngine, TtsProvider};
 
 /// Claude Code hook input structure
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ClaudeCodeInput {
     pub session_id: String,
     pub transcript_path: String,
@@ -23,9 +28,15 @@ pub struct ClaudeCodeInput {
     pub permission_mode: Option<String>,
     pub hook_event_name: String,
     pub stop_hook_active: Option<bool>,
+    /// Working directory of the session
+    pub cwd: Option<String>,
     // Notification hook specific fields
     pub message: Option<String>,
+    /// Optional heading spoken before `message`
+    pub title: Option<String>,
     pub notification_type: Option<String>,
+    /// PreCompact hook: "auto" when the context filled up, "manual" for /compact
+    pub trigger: Option<String>,
     // Stop hook content source alternative
     pub last_assistant_message: Option<String>,
 }
@@ -36,6 +47,39 @@ impl ClaudeCodeInput {
         let parsed: Self = serde_json::from_str(input)?;
         Ok(parsed)
     }
+
+    /// Name of the session's repository: the git root above `cwd` (or `cwd`
+    /// itself outside git), else the project directory of the transcript
+    pub fn repo_name(&self) -> Option<String> {
+        if let Some(cwd) = self.cwd.as_deref().filter(|c| !c.trim().is_empty()) {
+            let cwd = Path::new(cwd);
+            let root = git_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+            if let Some(name) = root.file_name() {
+                return Some(name.to_string_lossy().into_owned());
+            }
+        }
+        repo_from_transcript_path(&self.transcript_path)
+    }
+}
+
+/// Nearest directory at or above `start` that contains `.git`
+fn git_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Repository name from a transcript under `~/.claude/projects/<encoded cwd>/`.
+/// The encoding turns `/` into `-`, so only the last segment is recoverable
+/// (a repo named `sum-vox` comes back as `vox`).
+fn repo_from_transcript_path(transcript_path: &str) -> Option<String> {
+    let project = Path::new(transcript_path).parent()?.file_name()?.to_str()?;
+    let encoded = project.strip_prefix('-')?;
+    encoded
+        .rsplit('-')
+        .find(|segment| !segment.is_empty())
+        .map(str::to_string)
 }
 
 /// TTS options for hook handlers
@@ -43,8 +87,13 @@ impl ClaudeCodeInput {
 pub struct TtsOptions {
     pub engine: String,
     pub voice: Option<String>,
-    pub rate: u32,
+    /// macOS `say` rate; the provider's `rate` or `tts.wpm` when None
+    pub rate: Option<u32>,
     pub volume: Option<u32>,
+    /// Output device for this event; `tts.output_device` when None
+    pub device: Option<String>,
+    /// Print spoken summaries and notifications to stdout (`--print-summary`)
+    pub print_text: bool,
 }
 
 impl Default for TtsOptions {
@@ -52,8 +101,10 @@ impl Default for TtsOptions {
         Self {
             engine: "auto".to_string(),
             voice: None,
-            rate: 200,
+            rate: None,
             volume: None,
+            device: None,
+            print_text: false,
         }
     }
 }
@@ -63,6 +114,8 @@ pub struct LlmOptions {
     pub provider: Option<String>,
     pub model: Option<String>,
     pub timeout: u64,
+    /// `--budget-override`, passed on to detached children
+    pub budget_override: Option<f64>,
 }
 
 impl Default for LlmOptions {
@@ -71,6 +124,26 @@ impl Default for LlmOptions {
             provider: None,
             model: None,
             timeout: 10,
+            budget_override: None,
+        }
+    }
+}
+
+/// Hook events with a handler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HookEvent {
+    Notification,
+    Stop,
+    PreCompact,
+}
+
+impl HookEvent {
+    fn from_name(hook_event_name: &str) -> Option<Self> {
+        match hook_event_name {
+            "Notification" => Some(Self::Notification),
+            "Stop" => Some(Self::Stop),
+            "PreCompact" => Some(Self::PreCompact),
+            _ => None,
         }
     }
 }
@@ -83,9 +156,10 @@ pub async fn process(
     llm_opts: &LlmOptions,
 ) -> Result<()> {
     tracing::info!(
-        "Processing Claude Code hook: session_id={}, event={}",
+        "Processing Claude Code hook: session_id={}, event={}, repo={}",
         input.session_id,
-        input.hook_event_name
+        input.hook_event_name,
+        input.repo_name().as_deref().unwrap_or("unknown")
     );
 
     // Prevent infinite loop - if stop_hook is active, exit immediately
@@ -95,14 +169,17 @@ pub async fn process(
     }
 
     // Dispatch based on hook event type
-    match input.hook_event_name.as_str() {
-        "Notification" => {
-            handle_notification(input, config, tts_opts).await?;
+    match HookEvent::from_name(&input.hook_event_name) {
+        Some(HookEvent::Notification) => {
+            handle_notification(input, config, tts_opts, llm_opts).await?;
         }
-        "Stop" => {
+        Some(HookEvent::Stop) => {
             handle_stop(input, config, tts_opts, llm_opts).await?;
         }
-        _ => {
+        Some(HookEvent::PreCompact) => {
+            handle_pre_compact(input, config, tts_opts).await?;
+        }
+        None => {
             tracing::warn!("Unknown hook event: {}", input.hook_event_name);
         }
     }
@@ -121,7 +198,11 @@ async fn acquire_queue_lock(config: &SumvoxConfig) -> Result<Option<QueueLock>>
     let timeout = Duration::from_secs(timeout_secs);
     let queue = NotificationQueue::new(Some(timeout))?;
     match QueueLock::acquire(&queue).await {
-        Ok(lock) => Ok(Some(lock)),
+        Ok(lock) => {
+            lock.keep_gap(Duration::from_millis(config.hooks.playback.gap_ms))
+                .await;
+            Ok(Some(lock))
+        }
         Err(e) => {
             tracing::warn!(
                 "Failed to acquire queue lock, proceeding without lock: {}",
@@ -132,11 +213,126 @@ async fn acquire_queue_lock(config: &SumvoxConfig) -> Result<Option<QueueLock>>
     }
 }
 
+/// Cue played between two lock-serialized playbacks
+#[derive(Debug, Clone, PartialEq)]
+enum BoundaryCue {
+    Tone,
+    Spoken(String),
+}
+
+/// The cue to play before this playback: only when boundaries are announced
+/// and it was queued behind another one (`waited` on the lock)
+fn boundary_cue(playback: &PlaybackConfig, waited: bool) -> Option<BoundaryCue> {
+    if !playback.announce_boundaries || !waited {
+        return None;
+    }
+    match playback.boundary_cue.as_deref().map(str::trim) {
+        Some(text) if !text.is_empty() => Some(BoundaryCue::Spoken(text.to_string())),
+        _ => Some(BoundaryCue::Tone),
+    }
+}
+
+/// Play the boundary cue, if any, while holding the queue lock. Never fails
+/// the hook: a cue that can't play is only logged.
+async fn announce_boundary(config: &SumvoxConfig, tts_opts: &TtsOptions, lock: &Option<QueueLock>) {
+    let waited = lock.as_ref().is_some_and(QueueLock::waited);
+    let Some(cue) = boundary_cue(&config.hooks.playback, waited) else {
+        return;
+    };
+    let result = play_boundary_cue(cue, tts_opts, |text| async move {
+        speak_text(config, tts_opts, &text).await
+    })
+    .await;
+    if let Err(e) = result {
+        tracing::debug!("Boundary cue failed: {}", e);
+    }
+}
+
+/// Play `cue`: the tone, or its text through `speak`. The text is the same
+/// every time, so it never goes through `tts.global_dedup`.
+async fn play_boundary_cue<S, Fut>(cue: BoundaryCue, tts_opts: &TtsOptions, speak: S) -> Result<()>
+where
+    S: FnOnce(String) -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    match cue {
+        BoundaryCue::Tone => {
+            let wav = crate::audio::tone::boundary_tone_wav();
+            let volume = tts_opts.volume.unwrap_or(100);
+            crate::audio::afplay::play_with_afplay(&wav, volume, "sumvox_boundary")
+        }
+        BoundaryCue::Spoken(text) => speak(text).await,
+    }
+}
+
+/// The warning to speak for a PreCompact event: only when enabled, and not
+/// for a `/compact` the user typed themselves
+fn pre_compact_warning<'a>(input: &ClaudeCodeInput, config: &'a SumvoxConfig) -> Option<&'a str> {
+    let hook_config = &config.hooks.claude_code;
+    if !hook_config.pre_compact_warning || input.trigger.as_deref() == Some("manual") {
+        return None;
+    }
+    Some(hook_config.pre_compact_message.as_str()).filter(|m| !m.trim().is_empty())
+}
+
+/// Handle PreCompact hook - warn that the context is getting long
+async fn handle_pre_compact(
+    input: &ClaudeCodeInput,
+    config: &SumvoxConfig,
+    tts_opts: &TtsOptions,
+) -> Result<()> {
+    let Some(warning) = pre_compact_warning(input, config) else {
+        tracing::debug!(
+            "PreCompact warning disabled or manual compaction (trigger={:?}), skipping",
+            input.trigger
+        );
+        return Ok(());
+    };
+    tracing::info!("Speaking PreCompact warning: {}", warning);
+
+    let rotated = crate::tts::voice_rotation::with_next_voice(config);
+    let config = rotated.as_ref().unwrap_or(config);
+    let lock = acquire_queue_lock(config).await?;
+    let notification_tts_opts = notification_tts_options(config, tts_opts);
+    announce_boundary(config, &notification_tts_opts, &lock).await;
+    print_spoken(tts_opts, warning);
+    speak_text(config, &notification_tts_opts, warning).await?;
+
+    // Lock released on drop
+    Ok(())
+}
+
+/// Whether `notification_filter` lets this notification through
+fn should_speak_notification(input: &ClaudeCodeInput, hook_config: &ClaudeCodeHookConfig) -> bool {
+    let filter = &hook_config.notification_filter;
+    let notification_type = input
+        .notification_type
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    if filter.is_empty() {
+        // Empty filter = disabled
+        false
+    } else if filter.iter().any(|f| f == "*") {
+        // Wildcard = all notifications
+        true
+    } else {
+        match notification_type {
+            // Check if notification type is in filter
+            Some(notification_type) => filter.iter().any(|f| f == notification_type),
+            None => {
+                hook_config.speak_untyped_notifications || filter.iter().any(|f| f == "unknown")
+            }
+        }
+    }
+}
+
 /// Handle Notification hook - speak notification message directly
 async fn handle_notification(
     input: &ClaudeCodeInput,
     config: &SumvoxConfig,
     tts_opts: &TtsOptions,
+    llm_opts: &LlmOptions,
 ) -> Result<()> {
     tracing::info!("Processing Notification hook");
 
@@ -155,21 +351,11 @@ async fn handle_notification(
         notification_type,
         message
     );
+    if config.hooks.claude_code.observe_notification_types {
+        crate::notify_log::record_notification_type(notification_type);
+    }
 
-    // Check filter: should we speak this notification type?
-    let filter = &config.hooks.claude_code.notification_filter;
-    let should_speak = if filter.is_empty() {
-        // Empty filter = disabled
-        false
-    } else if filter.contains(&"*".to_string()) {
-        // Wildcard = all notifications
-        true
-    } else {
-        // Check if notification type is in filter
-        filter.contains(&notification_type.to_string())
-    };
-
-    if !should_speak {
+    if !should_speak_notification(input, &config.hooks.claude_code) {
         tracing::debug!(
             "Notification type '{}' not in filter, skipping",
             notification_type
@@ -177,32 +363,101 @@ async fn handle_notification(
         return Ok(());
     }
 
-    // Acquire queue lock for cross-process coordination
-    let _lock = acquire_queue_lock(config).await?;
+    // Hand playback to a background process and return to the agent right away
+    if config.hooks.claude_code.notification_detached && !super::detach::is_detached_child() {
+        if let Err(e) = spawn_detached_playback(input, llm_opts, None) {
+            tracing::warn!("{}, speaking in the foreground", e);
+        } else {
+            print_spoken(
+                tts_opts,
+                &super::with_title(input.title.as_deref(), message),
+            );
+            return Ok(());
+        }
+    }
 
-    // Speak the notification message directly (no LLM processing)
-    tracing::info!("Speaking notification: {}", message);
+    // Take a ticket before waiting, so a later arrival can supersede this one
+    let delay = Duration::from_millis(config.hooks.claude_code.notification_delay_ms);
+    let coalesce_ticket = if config.hooks.playback.coalesce || !delay.is_zero() {
+        match NotificationSequence::from_home().and_then(|seq| Ok((seq.register()?, seq))) {
+            Ok(ticket) => Some(ticket),
+            Err(e) => {
+                tracing::warn!("Failed to register notification for coalescing: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
 
-    // Use configured notification TTS provider if specified
-    let mut notification_tts_opts = tts_opts.clone();
-    if let Some(ref provider) = config.hooks.claude_code.notification_tts_provider {
-        tracing::info!("Using configured notification TTS provider: {}", provider);
-        notification_tts_opts.engine = provider.clone();
+    if !delay.is_zero() {
+        let ticket = coalesce_ticket.as_ref().map(|(t, seq)| (*t, seq));
+        if !wait_notification_delay(delay, ticket).await {
+            tracing::info!("Notification superseded during notification_delay_ms, skipping");
+            return Ok(());
+        }
     }
 
-    // Set notification-specific volume (priority: CLI > hook config > default)
-    if notification_tts_opts.volume.is_none() {
-        notification_tts_opts.volume = Some(
-            config.hooks.claude_code.notification_volume.unwrap_or(80), // Default notification volume
-        );
+    // Acquire queue lock for cross-process coordination
+    let lock = acquire_queue_lock(config).await?;
+
+    if let Some((ticket, sequence)) = &coalesce_ticket {
+        if sequence.is_superseded(*ticket) {
+            tracing::info!("Notification superseded by a newer one, skipping");
+            return Ok(());
+        }
+    }
+
+    // Speak the notification message directly (no LLM processing)
+    let utterance = super::with_title(input.title.as_deref(), message);
+    if crate::tts::spoken_dedup::is_repeat(config, &utterance) {
+        return Ok(());
     }
+    tracing::info!("Speaking notification: {}", utterance);
 
-    speak_text(config, &notification_tts_opts, message).await?;
+    let rotated = crate::tts::voice_rotation::with_next_voice(config);
+    let config = rotated.as_ref().unwrap_or(config);
+    let notification_tts_opts = notification_tts_options(config, tts_opts);
+    announce_boundary(config, &notification_tts_opts, &lock).await;
+    print_spoken(tts_opts, &utterance);
+    speak_text(config, &notification_tts_opts, &utterance).await?;
 
     // Lock released on drop
     Ok(())
 }
 
+/// Wait `delay` before a notification is spoken. False when a newer
+/// notification registered after `ticket` in the meantime.
+async fn wait_notification_delay(
+    delay: Duration,
+    ticket: Option<(u64, &NotificationSequence)>,
+) -> bool {
+    tracing::debug!("Waiting {:?} before speaking notification", delay);
+    tokio::time::sleep(delay).await;
+    !ticket.is_some_and(|(ticket, sequence)| sequence.is_superseded(ticket))
+}
+
+/// Re-run this hook input in a detached `sumvox json` child. A Stop child
+/// gets the finished `summary` to play instead of generating its own.
+fn spawn_detached_playback(
+    input: &ClaudeCodeInput,
+    llm_opts: &LlmOptions,
+    summary: Option<&str>,
+) -> Result<()> {
+    let exe = std::env::current_exe()?;
+    let hook_json = serde_json::to_string(input)?;
+    let child_opts = super::detach::ChildOptions {
+        timeout: llm_opts.timeout,
+        budget_override: llm_opts.budget_override,
+        fresh_config: SumvoxConfig::fresh_config_enabled(),
+    };
+    let mut cmd = super::detach::detached_command(&exe, &child_opts);
+    if let Some(summary) = summary {
+        cmd.env(super::detach::DETACHED_SUMMARY_ENV, summary);
+    }
+    super::detach::spawn_detached(cmd, Some(&hook_json))
+}
+
 /// Content source selection result for Stop hook
 enum StopContextSource {
     UseLastMessage(String),
@@ -229,6 +484,289 @@ fn select_stop_context_source(
     }
 }
 
+/// Result of reading the transcript for the Stop hook
+#[derive(Debug, PartialEq)]
+enum TranscriptContext {
+    Texts(Vec<String>),
+    /// The transcript file no longer exists (deleted/moved)
+    Missing,
+}
+
+/// How long the Stop hook waits for assistant text to reach the transcript
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AssistantWait {
+    budget: Duration,
+    interval: Duration,
+}
+
+impl AssistantWait {
+    fn from_config(hook_config: &ClaudeCodeHookConfig) -> Self {
+        Self {
+            budget: Duration::from_millis(hook_config.wait_for_assistant_ms),
+            interval: Duration::from_millis(hook_config.poll_interval_ms.max(1)),
+        }
+    }
+}
+
+impl Default for AssistantWait {
+    fn default() -> Self {
+        Self::from_config(&ClaudeCodeHookConfig::default())
+    }
+}
+
+/// Call `read` until it returns some texts or `wait.budget` has passed,
+/// sleeping `wait.interval` between calls. Returns the last result.
+async fn poll_for_texts<F, Fut>(wait: AssistantWait, mut read: F) -> Result<Vec<String>>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<String>>>,
+{
+    let deadline = tokio::time::Instant::now() + wait.budget;
+    loop {
+        let texts = read().await?;
+        let now = tokio::time::Instant::now();
+        if !texts.is_empty() || now >= deadline {
+            return Ok(texts);
+        }
+        tracing::debug!(
+            "No assistant text yet, re-reading in {:?} ({:?} left)",
+            wait.interval,
+            deadline - now
+        );
+        tokio::time::sleep(wait.interval.min(deadline - now)).await;
+    }
+}
+
+/// Read the last `turns` turns from the transcript, re-reading for up to
+/// `wait.budget` while it has no assistant text yet.
+/// A missing file is reported as `TranscriptContext::Missing` rather than an error.
+///
+/// With a session `marker`, only what was added since the previous Stop is read;
+/// the last `turns` turns are used when there is no marker or nothing new.
+/// The marker is then advanced to the end of the transcript.
+async fn read_transcript_context(
+    path: &Path,
+    summarization: &SummarizationConfig,
+    marker: Option<&Path>,
+    wait: AssistantWait,
+) -> Result<TranscriptContext> {
+    if !path.exists() {
+        return Ok(TranscriptContext::Missing);
+    }
+
+    if let Some(marker) = marker {
+        if let Some(since) = super::incremental::read_marker(marker) {
+            let (texts, line_count) = TranscriptReader::read_texts_after(path, since).await?;
+            if !texts.is_empty() {
+                tracing::debug!(
+                    "Incremental: {} new text blocks since line {}",
+                    texts.len(),
+                    since
+                );
+                record_marker(marker, line_count);
+                return Ok(TranscriptContext::Texts(texts));
+            }
+            tracing::debug!(
+                "Incremental: nothing new since line {}, reading last turns",
+                since
+            );
+        }
+    }
+
+    // The Stop hook can fire before the final reply is written
+    let texts = poll_for_texts(wait, || read_recent_turns(path, summarization)).await?;
+
+    if let Some(marker) = marker {
+        record_marker(marker, TranscriptReader::line_count(path).await?);
+    }
+
+    Ok(TranscriptContext::Texts(texts))
+}
+
+/// Read the turns selected by `summarization.turns`: a fixed count, or as
+/// many as fit the character budget in auto mode
+async fn read_recent_turns(
+    path: &Path,
+    summarization: &SummarizationConfig,
+) -> Result<Vec<String>> {
+    let parser = HookFormat::ClaudeCode.transcript_parser();
+    match summarization.turns {
+        Turns::Count(n) => {
+            TranscriptReader::read_last_n_turns(
+                parser,
+                path,
+                n.max(1),
+                summarization.turn_options(),
+            )
+            .await
+        }
+        Turns::Auto => {
+            TranscriptReader::read_turns_until_budget(
+                parser,
+                path,
+                summarization.auto_turns_char_budget,
+                summarization.auto_turns_max,
+                summarization.turn_options(),
+            )
+            .await
+        }
+    }
+}
+
+/// Replies at most this long ("Done.", "All set!") say nothing about the work
+const TRIVIAL_REPLY_CHARS: usize = 40;
+
+/// Characters of tool changes added to a trivial reply's context
+const TOOL_CHANGES_CHARS: usize = 3000;
+
+fn is_trivial_reply(text: &str) -> bool {
+    text.trim().chars().count() <= TRIVIAL_REPLY_CHARS
+}
+
+/// `context` followed by the file changes tools made in the latest turn
+async fn with_tool_changes(context: String, path: &Path) -> String {
+    match TranscriptReader::read_last_turn_changes(path, TOOL_CHANGES_CHARS).await {
+        Ok(changes) if !changes.is_empty() => {
+            tracing::debug!("Trivial reply, adding {} tool changes", changes.len());
+            format!("{}\n\nChanges made:\n{}", context, changes.join("\n\n"))
+        }
+        Ok(_) => context,
+        Err(e) => {
+            tracing::debug!("Could not read tool changes: {}", e);
+            context
+        }
+    }
+}
+
+/// `context` prefixed with the latest human message, labeled apart from the
+/// assistant's text
+async fn with_user_prompt(context: String, path: &Path) -> String {
+    match TranscriptReader::read_last_user_prompt(path).await {
+        Ok(Some(prompt)) => format!(
+            "User request:\n{}\n\nAssistant response:\n{}",
+            prompt, context
+        ),
+        Ok(None) => context,
+        Err(e) => {
+            tracing::debug!("Could not read user prompt: {}", e);
+            context
+        }
+    }
+}
+
+/// Summarization context for the Stop hook: the assistant's text plus tool
+/// changes and the user's request, as configured
+async fn stop_context(joined: String, path: &Path, summarization: &SummarizationConfig) -> String {
+    let context = if summarization.use_tool_results && is_trivial_reply(&joined) {
+        with_tool_changes(joined, path).await
+    } else {
+        joined
+    };
+    if summarization.include_user_prompt {
+        with_user_prompt(context, path).await
+    } else {
+        context
+    }
+}
+
+fn record_marker(marker: &Path, line_count: usize) {
+    if let Err(e) = super::incremental::write_marker(marker, line_count) {
+        tracing::warn!("Failed to update incremental marker {:?}: {}", marker, e);
+    }
+}
+
+const AUTO_LANGUAGE_INSTRUCTION: &str =
+    "Respond in the same language as the context above, not in English.";
+
+/// Append the mirror-the-session-language instruction when enabled and the
+/// context looks non-English
+fn apply_auto_language(prompt: String, context: &str, enabled: bool) -> String {
+    if enabled && looks_non_english(context) {
+        tracing::debug!("auto_language: non-English context, mirroring its language");
+        format!("{}\n\n{}", prompt, AUTO_LANGUAGE_INSTRUCTION)
+    } else {
+        prompt
+    }
+}
+
+/// Rough language check on the extracted context.
+///
+/// Non-Latin scripts (CJK, Cyrillic, ...) are recognized by their share of
+/// letters; Latin-script text by the absence of common English function words.
+/// Short or ambiguous text counts as English, so no instruction is added.
+fn looks_non_english(context: &str) -> bool {
+    const MIN_LETTERS: usize = 20;
+    const MIN_WORDS: usize = 12;
+    const ENGLISH_WORDS: &[&str] = &[
+        "the", "a", "an", "and", "or", "is", "are", "was", "to", "of", "in", "on", "for", "with",
+        "it", "this", "that", "i", "you", "we", "be", "not", "have", "has",
+    ];
+
+    let letters = context.chars().filter(|c| c.is_alphabetic()).count();
+    if letters < MIN_LETTERS {
+        return false;
+    }
+    let non_ascii = context
+        .chars()
+        .filter(|c| c.is_alphabetic() && !c.is_ascii())
+        .count();
+    if non_ascii * 10 >= letters * 3 {
+        return true;
+    }
+
+    let words: Vec<String> = context
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() < MIN_WORDS {
+        return false;
+    }
+    let english = words
+        .iter()
+        .filter(|w| ENGLISH_WORDS.contains(&w.as_str()))
+        .count();
+    // English prose is ~25-40% function words; allow plenty of code noise
+    english * 100 < words.len() * 5
+}
+
+/// Resolve TTS options for the Notification hook (priority: CLI > hook config > default)
+fn notification_tts_options(config: &SumvoxConfig, tts_opts: &TtsOptions) -> TtsOptions {
+    let mut notification_tts_opts = tts_opts.clone();
+    if let Some(ref provider) = config.hooks.claude_code.notification_tts_provider {
+        tracing::info!("Using configured notification TTS provider: {}", provider);
+        notification_tts_opts.engine = provider.clone();
+    }
+
+    if notification_tts_opts.volume.is_none() {
+        // Default notification volume
+        notification_tts_opts.volume =
+            Some(config.hooks.claude_code.notification_volume.unwrap_or(80));
+    }
+    if notification_tts_opts.device.is_none() {
+        notification_tts_opts.device = config.hooks.claude_code.notification_device.clone();
+    }
+    notification_tts_opts
+}
+
+/// Resolve TTS options for the Stop hook (priority: CLI > hook config > default)
+fn stop_tts_options(config: &SumvoxConfig, tts_opts: &TtsOptions) -> TtsOptions {
+    let mut stop_tts_opts = tts_opts.clone();
+    if let Some(ref provider) = config.hooks.claude_code.stop_tts_provider {
+        tracing::info!("Using configured stop TTS provider: {}", provider);
+        stop_tts_opts.engine = provider.clone();
+    }
+
+    if stop_tts_opts.volume.is_none() {
+        // Default stop/summary volume
+        stop_tts_opts.volume = Some(config.hooks.claude_code.stop_volume.unwrap_or(100));
+    }
+    if stop_tts_opts.device.is_none() {
+        stop_tts_opts.device = config.hooks.claude_code.stop_device.clone();
+    }
+    stop_tts_opts
+}
+
 /// Handle Stop hook - read transcript and generate summary
 async fn handle_stop(
     input: &ClaudeCodeInput,
@@ -238,6 +776,22 @@ async fn handle_stop(
 ) -> Result<()> {
     tracing::info!("Processing Stop hook");
 
+    // Detached child: the parent already generated the summary, just play it
+    if let Some(summary) = super::detach::detached_summary() {
+        return speak_stop_summary(config, tts_opts, &summary).await;
+    }
+
+    if config.summarization.prefer_native_summary {
+        match TranscriptReader::read_native_summary(&input.transcript_path).await {
+            Ok(Some(summary)) => {
+                tracing::info!("Using native transcript summary: {}", summary);
+                return speak_stop_summary(config, tts_opts, &summary).await;
+            }
+            Ok(None) => tracing::debug!("No native summary in transcript, using LLM"),
+            Err(e) => tracing::debug!("Could not read native summary: {}", e),
+        }
+    }
+
     // Determine content source
     let source = select_stop_context_source(
         config.summarization.content_source,
@@ -272,71 +826,229 @@ async fn handle_stop(
             tracing::debug!("Waiting {}ms for filesystem sync", INITIAL_DELAY_MS);
             tokio::time::sleep(initial_delay).await;
 
-            let turns = config.summarization.turns.max(1); // At least 1 turn
-            let mut texts = TranscriptReader::read_last_n_turns(&transcript_path, turns).await?;
-
-            // Retry once if empty (race condition workaround, hardcoded 100ms)
-            if texts.is_empty() {
-                const RETRY_DELAY_MS: u64 = 100;
-                tracing::debug!("No texts found, retrying after {}ms", RETRY_DELAY_MS);
-                let retry_delay = Duration::from_millis(RETRY_DELAY_MS);
-                tokio::time::sleep(retry_delay).await;
-                texts = TranscriptReader::read_last_n_turns(&transcript_path, turns).await?;
-            }
+            let marker = if config.hooks.claude_code.incremental {
+                super::incremental::marker_path(&input.session_id)
+            } else {
+                None
+            };
+            let texts = match read_transcript_context(
+                &transcript_path,
+                &config.summarization,
+                marker.as_deref(),
+                AssistantWait::from_config(&config.hooks.claude_code),
+            )
+            .await?
+            {
+                TranscriptContext::Texts(texts) => texts,
+                TranscriptContext::Missing => {
+                    tracing::warn!(
+                        "Transcript not found at {:?}, speaking fallback message",
+                        transcript_path
+                    );
+                    let lock = acquire_queue_lock(config).await?;
+                    let stop_tts_opts = stop_tts_options(config, tts_opts);
+                    announce_boundary(config, &stop_tts_opts, &lock).await;
+                    let fallback = &config.summarization.fallback_message;
+                    print_spoken(tts_opts, fallback);
+                    // Never fail the hook over a vanished transcript
+                    if let Err(e) = speak_stop_text(config, &stop_tts_opts, fallback).await {
+                        tracing::warn!("Failed to speak fallback message: {}", e);
+                    }
+                    return Ok(());
+                }
+            };
 
             if texts.is_empty() {
-                tracing::warn!("No assistant texts found in transcript after retry");
+                tracing::warn!("No assistant texts found in transcript after waiting");
                 return Ok(());
             }
 
             let joined = texts.join("\n\n");
             tracing::debug!(
-                "Extracted {} text blocks from last {} turn(s), total length: {}",
+                "Extracted {} text blocks ({:?} turns), total length: {}",
                 texts.len(),
-                turns,
+                config.summarization.turns,
                 joined.len()
             );
-            joined
+            stop_context(joined, &transcript_path, &config.summarization).await
         }
     };
 
     // Build summarization prompt
-    let user_prompt = config
-        .summarization
-        .prompt_template
-        .replace("{context}", &context);
+    let user_prompt = apply_auto_language(
+        config.summarization.render_prompt(&context),
+        &context,
+        config.summarization.auto_language,
+    );
 
-    let system_message = Some(config.summarization.system_message.clone());
+    let system_message = Some(config.summarization.effective_system_message());
+
+    // Prefer the provider family matching the session's model, if enabled
+    let session_config;
+    let config = if config.summarization.match_session_model {
+        match TranscriptReader::detect_session_model(&input.transcript_path).await {
+            Some(model) => {
+                tracing::info!("Session model: {}, prioritizing matching provider", model);
+                let mut reordered = config.clone();
+                reordered.llm.providers =
+                    prioritize_session_provider(&config.llm.providers, &model);
+                session_config = reordered;
+                &session_config
+            }
+            None => {
+                tracing::debug!("No session model found in transcript, keeping provider order");
+                config
+            }
+        }
+    } else {
+        config
+    };
 
     // Generate summary with LLM
     let summary = generate_summary(config, llm_opts, system_message, &user_prompt).await?;
+    let summary = config.summarization.strip_preamble(&summary);
+
+    // Hand synthesis + playback to a background process and return to the agent
+    if config.hooks.claude_code.stop_detached && !super::detach::is_detached_child() {
+        match spawn_detached_playback(input, llm_opts, Some(&summary)) {
+            Ok(()) => {
+                // The child's output is detached; print here so it isn't lost
+                if !summary.is_empty() {
+                    print_spoken(tts_opts, &config.summarization.spoken_text(&summary));
+                }
+                return Ok(());
+            }
+            Err(e) => tracing::warn!("{}, speaking in the foreground", e),
+        }
+    }
+
+    speak_stop_summary(config, tts_opts, &summary).await
+}
+
+/// Speak the Stop summary (or the fallback message when it is empty) under the queue lock
+async fn speak_stop_summary(
+    config: &SumvoxConfig,
+    tts_opts: &TtsOptions,
+    summary: &str,
+) -> Result<()> {
+    // Only a real summary is deduped; the fallback message repeats by design
+    let spoken = stop_spoken_text(&config.summarization, summary);
+    if spoken
+        .as_deref()
+        .is_some_and(|spoken| crate::tts::spoken_dedup::is_repeat(config, spoken))
+    {
+        return Ok(());
+    }
 
     // Acquire queue lock before speaking
-    let _lock = acquire_queue_lock(config).await?;
+    let lock = acquire_queue_lock(config).await?;
 
-    // Use configured stop TTS provider if specified
-    let mut stop_tts_opts = tts_opts.clone();
-    if let Some(ref provider) = config.hooks.claude_code.stop_tts_provider {
-        tracing::info!("Using configured stop TTS provider: {}", provider);
-        stop_tts_opts.engine = provider.clone();
+    // One voice for the cue and the summary
+    let rotated = crate::tts::voice_rotation::with_next_voice(config);
+    let config = rotated.as_ref().unwrap_or(config);
+    let stop_tts_opts = stop_tts_options(config, tts_opts);
+    announce_boundary(config, &stop_tts_opts, &lock).await;
+
+    match spoken {
+        None => {
+            tracing::warn!("LLM returned empty summary, using fallback");
+            let fallback = &config.summarization.fallback_message;
+            print_spoken(tts_opts, fallback);
+            speak_stop_text(config, &stop_tts_opts, fallback).await?;
+        }
+        Some(spoken) => {
+            tracing::info!("Generated summary: {}", summary);
+            print_spoken(tts_opts, &spoken);
+            speak_stop_text(config, &stop_tts_opts, &spoken).await?;
+        }
     }
 
-    // Set stop hook specific volume (priority: CLI > hook config > default)
-    if stop_tts_opts.volume.is_none() {
-        stop_tts_opts.volume = Some(config.hooks.claude_code.stop_volume.unwrap_or(100));
-        // Default stop/summary volume
+    Ok(())
+}
+
+/// What the Stop hook says for `summary`, redacted and cut per
+/// `summarization`. None when it is empty, so the fallback message plays.
+fn stop_spoken_text(summarization: &SummarizationConfig, summary: &str) -> Option<String> {
+    (!summary.is_empty()).then(|| summarization.spoken_text(summary))
+}
+
+/// Sound played before the Stop summary (`hooks.claude_code.stop_chime`)
+#[derive(Debug, Clone, PartialEq)]
+enum StopChime {
+    Tone,
+    File(PathBuf),
+}
+
+impl StopChime {
+    fn from_config(value: Option<&str>) -> Option<Self> {
+        match value.map(str::trim) {
+            None | Some("") => None,
+            Some(v) if v.eq_ignore_ascii_case("tone") => Some(Self::Tone),
+            Some(path) => Some(Self::File(PathBuf::from(
+                shellexpand::tilde(path).into_owned(),
+            ))),
+        }
     }
 
-    if summary.is_empty() {
-        tracing::warn!("LLM returned empty summary, using fallback");
-        let fallback = &config.summarization.fallback_message;
-        speak_text(config, &stop_tts_opts, fallback).await?;
-    } else {
-        tracing::info!("Generated summary: {}", summary);
-        speak_text(config, &stop_tts_opts, &summary).await?;
+    fn play(&self, config: &SumvoxConfig, stop_tts_opts: &TtsOptions) -> Result<()> {
+        let _device = crate::audio::device::OutputDeviceGuard::select(
+            crate::audio::device::resolve_output_device(
+                stop_tts_opts.device.as_deref(),
+                config.tts.output_device.as_deref(),
+            ),
+        );
+        // Same place in the stereo field as the summary that follows
+        let _pan = crate::audio::pan::StereoPanGuard::set(config.hooks.claude_code.pan);
+        let volume = stop_tts_opts.volume.unwrap_or(100);
+        match self {
+            Self::Tone => crate::audio::afplay::play_with_afplay(
+                &crate::audio::tone::chime_wav(),
+                volume,
+                "sumvox_chime",
+            ),
+            Self::File(path) => crate::audio::afplay::run_afplay(path, volume),
+        }
     }
+}
 
-    Ok(())
+/// Speak Stop hook `text`, preceded by the stop chime if one is set.
+/// Call with the queue lock held so both play as one unit.
+async fn speak_stop_text(
+    config: &SumvoxConfig,
+    stop_tts_opts: &TtsOptions,
+    text: &str,
+) -> Result<()> {
+    let chime = StopChime::from_config(config.hooks.claude_code.stop_chime.as_deref());
+    chime_then_speak(
+        chime,
+        |chime| chime.play(config, stop_tts_opts),
+        || speak_text(config, stop_tts_opts, text),
+    )
+    .await
+}
+
+/// Play `chime` (if any), then run `speak`. A chime that fails to play is
+/// only logged; the summary is still spoken.
+async fn chime_then_speak<P, S, Fut>(chime: Option<StopChime>, play: P, speak: S) -> Result<()>
+where
+    P: FnOnce(&StopChime) -> Result<()>,
+    S: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    if let Some(chime) = chime {
+        if let Err(e) = play(&chime) {
+            tracing::warn!("Stop chime {:?} failed: {}", chime, e);
+        }
+    }
+    speak().await
+}
+
+/// Print `text` to stdout when `--print-summary` is on, before playback so
+/// the text survives a TTS failure
+fn print_spoken(tts_opts: &TtsOptions, text: &str) {
+    if tts_opts.print_text {
+        println!("{}", text);
+    }
 }
 
 /// Generate summary using LLM
@@ -347,6 +1059,11 @@ async fn generate_summary(
     prompt: &str,
 ) -> Result<String> {
     let llm_config = &config.llm;
+    // One client for every provider tried below
+    let http = HttpContext::new();
+    // Scaled timeouts grow with the context
+    let input_tokens =
+        llm::estimate_tokens(prompt) + system_message.as_deref().map_or(0, llm::estimate_tokens);
 
     // Try providers with fallback
     if llm_opts.provider.is_some() || llm_opts.model.is_some() {
@@ -364,7 +1081,8 @@ async fn generate_summary(
                 return Ok(String::new());
             }
         };
-        let timeout = Duration::from_secs(llm_opts.timeout);
+        let timeout =
+            Duration::from_secs(llm_config.scaled_timeout(llm_opts.timeout, input_tokens));
 
         // Find the matching provider config for model + per-provider override resolution
         let matching_provider = config
@@ -389,6 +1107,11 @@ async fn generate_summary(
         };
 
         let api_key = matching_provider.and_then(|p| p.get_api_key());
+        let model_name = &ProviderFactory::resolve_model_alias(
+            &ProviderFactory::named_config(provider_name, model_name, timeout, api_key.as_deref()),
+            &http,
+        )
+        .await;
 
         // Resolve effective disable_thinking: provider override > global
         let disable_thinking = matching_provider
@@ -401,13 +1124,17 @@ async fn generate_summary(
             max_tokens: llm_config.parameters.max_tokens,
             temperature: llm_config.parameters.temperature,
             disable_thinking,
+            stop: llm_config.parameters.stop.clone(),
+            reasoning_effort: llm_config.parameters.reasoning_effort.clone(),
+            ollama_system_mode: llm_config.ollama_system_mode,
         };
 
-        match ProviderFactory::create_by_name(
+        match ProviderFactory::create_by_name_in(
             provider_name,
             model_name,
             timeout,
             api_key.as_deref(),
+            &http,
         ) {
             Ok(provider) => {
                 if !provider.is_available() {
@@ -415,7 +1142,23 @@ async fn generate_summary(
                     return Ok(String::new());
                 }
 
-                match provider.generate(&request).await {
+                if let Some(estimate) =
+                    llm::over_call_cap(config, provider.as_ref(), model_name, &request)
+                {
+                    tracing::warn!(
+                        "Skipping {}: estimated cost ${:.4} exceeds cost.max_per_call_usd",
+                        provider.name(),
+                        estimate
+                    );
+                    crate::notify_log::record_last_error(&format!(
+                        "LLM provider {} skipped: estimated cost ${:.4} exceeds cost.max_per_call_usd",
+                        provider.name(),
+                        estimate
+                    ));
+                    return Ok(String::new());
+                }
+
+                match llm::generate_within_budget(config, provider.as_ref(), &request).await {
                     Ok(response) => {
                         tracing::debug!(
                             "LLM usage: {} input tokens, {} output tokens",
@@ -426,12 +1169,21 @@ async fn generate_summary(
                     }
                     Err(e) => {
                         tracing::error!("CLI provider {} failed: {}", provider.name(), e);
+                        crate::notify_log::record_last_error(&format!(
+                            "LLM provider {} failed: {}",
+                            provider.name(),
+                            e
+                        ));
                         return Ok(String::new());
                     }
                 }
             }
             Err(e) => {
                 tracing::error!("Failed to create CLI provider {}: {}", provider_name, e);
+                crate::notify_log::record_last_error(&format!(
+                    "LLM provider {} failed: {}",
+                    provider_name, e
+                ));
                 return Ok(String::new());
             }
         }
@@ -439,7 +1191,13 @@ async fn generate_summary(
 
     // Try each provider in config order until one succeeds.
     // Build a per-provider GenerationRequest so each gets its own effective disable_thinking.
+    let mut failures = Vec::new();
     for provider_config in &llm_config.providers {
+        let provider_config = &LlmProviderConfig {
+            model: ProviderFactory::resolve_model_alias(provider_config, &http).await,
+            timeout: llm_config.scaled_timeout(provider_config.timeout, input_tokens),
+            ..provider_config.clone()
+        };
         let disable_thinking = effective_disable_thinking(provider_config, &llm_config.parameters);
 
         let request = GenerationRequest {
@@ -448,12 +1206,32 @@ async fn generate_summary(
             max_tokens: llm_config.parameters.max_tokens,
             temperature: llm_config.parameters.temperature,
             disable_thinking,
+            stop: llm_config.parameters.stop.clone(),
+            reasoning_effort: llm_config.parameters.reasoning_effort.clone(),
+            ollama_system_mode: llm_config.ollama_system_mode,
         };
 
-        match ProviderFactory::create_single(provider_config) {
+        match ProviderFactory::create_single_in(provider_config, &http) {
             Ok(provider) => {
                 if !provider.is_available() {
                     tracing::debug!("Provider {} not available, trying next", provider.name());
+                    failures.push(format!("{}: not available", provider.name()));
+                    continue;
+                }
+
+                if let Some(estimate) =
+                    llm::over_call_cap(config, provider.as_ref(), &provider_config.model, &request)
+                {
+                    tracing::warn!(
+                        "Skipping {}: estimated cost ${:.4} exceeds cost.max_per_call_usd",
+                        provider.name(),
+                        estimate
+                    );
+                    failures.push(format!(
+                        "{}: estimated cost ${:.4} over cap",
+                        provider.name(),
+                        estimate
+                    ));
                     continue;
                 }
 
@@ -463,7 +1241,7 @@ async fn generate_summary(
                     provider_config.model
                 );
 
-                match provider.generate(&request).await {
+                match llm::generate_within_budget(config, provider.as_ref(), &request).await {
                     Ok(response) => {
                         tracing::info!("Provider {} succeeded", provider.name());
                         tracing::debug!(
@@ -476,12 +1254,14 @@ async fn generate_summary(
                     }
                     Err(e) => {
                         tracing::warn!("Provider {} failed: {}, trying next", provider.name(), e);
+                        failures.push(format!("{}: {}", provider.name(), e));
                         continue;
                     }
                 }
             }
             Err(e) => {
                 tracing::debug!("Failed to create provider {}: {}", provider_config.name, e);
+                failures.push(format!("{}: {}", provider_config.name, e));
                 continue;
             }
         }
@@ -489,6 +1269,7 @@ async fn generate_summary(
 
     // All providers failed
     tracing::error!("All LLM providers failed");
+    crate::notify_log::record_last_error(&crate::notify_log::failure_reason("LLM", &failures));
     Ok(String::new())
 }
 
@@ -500,6 +1281,25 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
         tracing::info!("Voice muted via menu bar app, skipping TTS");
         return Ok(());
     }
+    let text = &crate::tts::paths::speakable_paths(config, text);
+    let text = &crate::tts::apply_pronunciations(text, &config.tts.pronunciations);
+    let _status =
+        crate::audio::status_file::StatusFileGuard::start(config.tts.status_file.as_deref(), text);
+
+    // Other audio stays ducked until this function returns
+    let _ducked = crate::audio::ducking::DuckGuard::new(config.tts.duck_other_audio);
+    let _device = crate::audio::device::OutputDeviceGuard::select(
+        crate::audio::device::resolve_output_device(
+            tts_opts.device.as_deref(),
+            config.tts.output_device.as_deref(),
+        ),
+    );
+    let _padding = crate::audio::padding::SilencePaddingGuard::set(
+        config.tts.preroll_ms,
+        config.tts.postroll_ms,
+    );
+    let _pan = crate::audio::pan::StereoPanGuard::set(config.hooks.claude_code.pan);
+    crate::audio::playback_limit::set_max_concurrent_playbacks(config.tts.max_concurrent_playbacks);
 
     let tts_engine = tts_opts.engine.parse().unwrap_or(TtsEngine::Auto);
 
@@ -507,7 +1307,11 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
     let provider: Box<dyn TtsProvider> = match tts_engine {
         TtsEngine::Auto => {
             // Use config fallback chain
-            create_tts_from_config(&config.tts.providers)?
+            create_tts_from_config(
+                &config.tts.providers,
+                config.tts.default_voice.as_deref(),
+                config.tts.wpm,
+            )?
         }
         // An explicitly selected engine overrides which configured provider to use;
         // all attributes come from that config entry, with only explicit CLI/hook
@@ -518,6 +1322,8 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
         )?,
         TtsEngine::Google => resolve_tts_provider(
             &config.tts.providers,
@@ -525,6 +1331,8 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
         )?,
         TtsEngine::CloudTts => resolve_tts_provider(
             &config.tts.providers,
@@ -532,6 +1340,8 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
         )?,
         TtsEngine::AudioFile => resolve_tts_provider(
             &config.tts.providers,
@@ -539,6 +1349,8 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
         )?,
         TtsEngine::Xai => resolve_tts_provider(
             &config.tts.providers,
@@ -546,6 +1358,8 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
         )?,
         TtsEngine::ElevenLabs => resolve_tts_provider(
             &config.tts.providers,
@@ -553,6 +1367,8 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
         )?,
         TtsEngine::OpenAi => resolve_tts_provider(
             &config.tts.providers,
@@ -560,11 +1376,14 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
         )?,
     };
 
     if !provider.is_available() {
-        tracing::warn!("TTS provider {} not available", provider.name());
+        let failure = format!("{}: not available", provider.name());
+        crate::desktop_notification::report_silent_playback(&config.tts, text, &[failure]);
         return Ok(());
     }
 
@@ -573,47 +1392,107 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
     if cost > 0.0 {
         tracing::info!("TTS cost estimate: ${:.6} for {} chars", cost, text.len());
     }
+    // Hooks can't ask, so an over-limit text is only warned about
+    if let Some(warning) =
+        config
+            .tts
+            .char_limit_warning(provider.name(), text.chars().count(), cost)
+    {
+        tracing::warn!("{}", warning);
+    }
 
-    // Speak with error handling and fallback for Auto mode
-    match tts_engine {
+    // Speak with error handling and fallback for Auto mode. Failures are
+    // kept from the last attempt only, and reported once below.
+    let failures = std::sync::Mutex::new(Vec::new());
+    let played = match tts_engine {
         TtsEngine::Auto => {
             // For Auto mode, try all providers in config order
             // Pass volume override so hook-level volume (stop_volume/notification_volume) is applied
-            speak_with_provider_fallback(&config.tts.providers, text, tts_opts.volume).await
+            retry_on_total_failure(&config.hooks.playback, || async {
+                let mut attempt_failures = Vec::new();
+                let played = speak_with_provider_fallback(
+                    &config.tts,
+                    text,
+                    tts_opts.volume,
+                    &mut attempt_failures,
+                )
+                .await;
+                *failures.lock().unwrap() = attempt_failures;
+                played
+            })
+            .await
         }
         _ => {
-            // Single provider mode - just try once
+            // Single provider mode - one provider, retried only if configured
             let text = if provider.supports_audio_tags() {
                 text
             } else {
                 crate::tts::strip_leading_audio_tag(text)
             };
-            match provider.speak(text).await {
-                Ok(_) => {
-                    tracing::debug!("TTS playback completed");
-                    Ok(())
-                }
-                Err(e) => {
-                    tracing::warn!("TTS playback failed: {}. Notification will be silent.", e);
-                    Ok(())
+            retry_on_total_failure(&config.hooks.playback, || async {
+                match provider.speak(text).await {
+                    Ok(_) => {
+                        tracing::debug!("TTS playback completed");
+                        true
+                    }
+                    Err(e) => {
+                        tracing::warn!("TTS playback failed: {}", e);
+                        *failures.lock().unwrap() = vec![format!("{}: {}", provider.name(), e)];
+                        false
+                    }
                 }
-            }
+            })
+            .await
         }
+    };
+
+    if !played {
+        let failures = failures.into_inner().unwrap();
+        crate::desktop_notification::report_silent_playback(&config.tts, text, &failures);
     }
+    Ok(())
+}
+
+/// Run a playback attempt; when it played nothing and
+/// `hooks.playback.retry_on_total_failure` is set, wait `retry_delay_ms` and
+/// run it once more. Returns whether audio was played.
+async fn retry_on_total_failure<F, Fut>(
+    playback: &crate::config::PlaybackConfig,
+    attempt: F,
+) -> bool
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    if attempt().await {
+        return true;
+    }
+    if !playback.retry_on_total_failure {
+        return false;
+    }
+
+    tracing::info!(
+        "All playback attempts failed, retrying once in {}ms",
+        playback.retry_delay_ms
+    );
+    tokio::time::sleep(Duration::from_millis(playback.retry_delay_ms)).await;
+    attempt().await
 }
 
 /// Try TTS providers in order with automatic runtime fallback
 ///
 /// `volume_override` applies hook-level volume (e.g., stop_volume, notification_volume)
 /// over provider-level volume settings. Priority: volume_override > provider config > default.
+/// Why each provider failed is added to `failures`; the caller reports it.
 async fn speak_with_provider_fallback(
-    providers: &[crate::config::TtsProviderConfig],
+    tts: &crate::config::TtsConfig,
     text: &str,
     volume_override: Option<u32>,
-) -> Result<()> {
-    let mut last_error = None;
+    failures: &mut Vec<String>,
+) -> bool {
+    let mut providers = Vec::new();
 
-    for provider_config in providers {
+    for provider_config in &tts.providers {
         // Skip audio_file providers - they play sound effects,
         // not speech synthesis, and cannot render arbitrary text.
         if matches!(
@@ -633,7 +1512,11 @@ async fn speak_with_provider_fallback(
         }
 
         // Try to create provider
-        let provider = match crate::tts::create_single_tts(&config_with_volume) {
+        let provider = match crate::tts::create_single_tts(
+            &config_with_volume,
+            tts.default_voice.as_deref(),
+            tts.wpm,
+        ) {
             Ok(p) => p,
             Err(e) => {
                 tracing::debug!(
@@ -641,7 +1524,7 @@ async fn speak_with_provider_fallback(
                     provider_config.name,
                     e
                 );
-                last_error = Some(format!("{}: {}", provider_config.name, e));
+                failures.push(format!("{}: {}", provider_config.name, e));
                 continue;
             }
         };
@@ -652,57 +1535,38 @@ async fn speak_with_provider_fallback(
                 "TTS provider {} not available, trying next",
                 provider.name()
             );
-            last_error = Some(format!("{}: not available", provider.name()));
+            failures.push(format!("{}: not available", provider.name()));
             continue;
         }
 
-        // Log selected provider
-        tracing::info!(
-            "Using TTS provider: {} (voice: {})",
+        tracing::debug!(
+            "TTS provider {} ready (voice: {})",
             provider_config.name,
             provider_config.voice.as_deref().unwrap_or("default")
         );
-
-        // Estimate and log cost for cloud providers
-        let cost = provider.estimate_cost(text.len());
-        if cost > 0.0 {
-            tracing::info!("TTS cost estimate: ${:.6} for {} chars", cost, text.len());
-        }
-
-        // Try to speak (strip audio tags for providers that would read them aloud)
-        let provider_text = if provider.supports_audio_tags() {
-            text
-        } else {
-            crate::tts::strip_leading_audio_tag(text)
-        };
-        match provider.speak(provider_text).await {
-            Ok(_) => {
-                tracing::debug!("TTS playback completed with {}", provider.name());
-                return Ok(());
-            }
-            Err(e) => {
-                tracing::warn!(
-                    "TTS provider {} failed: {}, trying next provider",
-                    provider.name(),
-                    e
-                );
-                last_error = Some(format!("{}: {}", provider.name(), e));
-                continue;
-            }
-        }
-    }
-
-    // All providers failed
-    if let Some(err) = last_error {
-        tracing::warn!(
-            "All TTS providers failed. Last error: {}. Notification will be silent.",
-            err
-        );
-    } else {
-        tracing::warn!("No TTS providers available. Notification will be silent.");
+        providers.push(provider);
     }
 
-    Ok(())
+    let announced = tts
+        .announce_fallback
+        .then(|| crate::tts::with_fallback_marker(text));
+    crate::tts::speak_in_order(
+        &providers,
+        text,
+        announced.as_deref(),
+        tts.fallback_policy,
+        failures,
+        |provider, text| {
+            // Strip audio tags for providers that would read them aloud
+            let provider_text = if provider.supports_audio_tags() {
+                text
+            } else {
+                crate::tts::strip_leading_audio_tag(text)
+            };
+            provider.speak(provider_text)
+        },
+    )
+    .await
 }
 
 #[cfg(test)]
@@ -723,6 +1587,66 @@ mod tests {
         assert_eq!(input.session_id, "test-session");
         assert_eq!(input.hook_event_name, "Stop");
         assert_eq!(input.stop_hook_active, Some(false));
+        assert_eq!(input.cwd, None);
+    }
+
+    fn input_with_cwd(cwd: Option<&str>, transcript_path: &str) -> ClaudeCodeInput {
+        let mut input = ClaudeCodeInput::parse(&format!(
+            r#"{{"session_id": "s", "transcript_path": "{}", "hook_event_name": "Stop"}}"#,
+            transcript_path
+        ))
+        .unwrap();
+        input.cwd = cwd.map(str::to_string);
+        input
+    }
+
+    #[test]
+    fn test_repo_name_prefers_cwd_git_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("SumVox");
+        let nested = repo.join("src").join("hooks");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir(repo.join(".git")).unwrap();
+
+        // The transcript path points at a different project; cwd wins
+        let transcript = "/Users/me/.claude/projects/-Users-me-code-other/abc.jsonl";
+        let input = input_with_cwd(nested.to_str(), transcript);
+        assert_eq!(input.repo_name().as_deref(), Some("SumVox"));
+
+        // Outside a git repo the cwd itself names the project
+        let plain = dir.path().join("scratch");
+        std::fs::create_dir(&plain).unwrap();
+        let input = input_with_cwd(plain.to_str(), transcript);
+        assert_eq!(input.repo_name().as_deref(), Some("scratch"));
+    }
+
+    #[test]
+    fn test_repo_name_falls_back_to_transcript_path() {
+        let transcript = "/Users/me/.claude/projects/-Users-me-code-SumVox/abc.jsonl";
+        assert_eq!(
+            input_with_cwd(None, transcript).repo_name().as_deref(),
+            Some("SumVox")
+        );
+        assert_eq!(
+            input_with_cwd(Some(""), transcript).repo_name().as_deref(),
+            Some("SumVox")
+        );
+        assert_eq!(
+            input_with_cwd(None, "/path/to/transcript.jsonl").repo_name(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cwd_deserialized() {
+        let json = r#"{
+            "session_id": "s",
+            "transcript_path": "/t.jsonl",
+            "hook_event_name": "Stop",
+            "cwd": "/Users/me/code/SumVox"
+        }"#;
+        let input = ClaudeCodeInput::parse(json).unwrap();
+        assert_eq!(input.cwd.as_deref(), Some("/Users/me/code/SumVox"));
     }
 
     #[test]
@@ -742,13 +1666,126 @@ mod tests {
             input.notification_type,
             Some("permission_prompt".to_string())
         );
+        assert_eq!(input.title, None);
+    }
+
+    #[test]
+    fn test_claude_code_input_notification_title() {
+        let json = r#"{
+            "session_id": "test-session",
+            "transcript_path": "/path/to/transcript.jsonl",
+            "hook_event_name": "Notification",
+            "title": "Permission needed",
+            "message": "Allow bash command?"
+        }"#;
+
+        let input = ClaudeCodeInput::parse(json).unwrap();
+        assert_eq!(input.title.as_deref(), Some("Permission needed"));
+        assert_eq!(
+            super::super::with_title(input.title.as_deref(), input.message.as_deref().unwrap()),
+            "Permission needed. Allow bash command?"
+        );
+    }
+
+    fn notification_input(notification_type: Option<&str>) -> ClaudeCodeInput {
+        let mut input = ClaudeCodeInput::parse(
+            r#"{"session_id": "s", "transcript_path": "/tmp/t.jsonl", "hook_event_name": "Notification", "message": "Claude needs your input"}"#,
+        )
+        .unwrap();
+        input.notification_type = notification_type.map(str::to_string);
+        input
+    }
+
+    #[test]
+    fn test_untyped_notification_spoken_when_enabled() {
+        let mut hook_config = crate::config::ClaudeCodeHookConfig::default();
+        assert!(hook_config.speak_untyped_notifications);
+        assert!(should_speak_notification(
+            &notification_input(None),
+            &hook_config
+        ));
+        assert!(should_speak_notification(
+            &notification_input(Some(" ")),
+            &hook_config
+        ));
+        // Typed notifications still go through the filter
+        assert!(!should_speak_notification(
+            &notification_input(Some("auth_success")),
+            &hook_config
+        ));
+
+        // An empty filter still disables everything
+        hook_config.notification_filter.clear();
+        assert!(!should_speak_notification(
+            &notification_input(None),
+            &hook_config
+        ));
+    }
+
+    #[test]
+    fn test_untyped_notification_dropped_when_disabled() {
+        let mut hook_config = crate::config::ClaudeCodeHookConfig {
+            speak_untyped_notifications: false,
+            ..Default::default()
+        };
+        assert!(!should_speak_notification(
+            &notification_input(None),
+            &hook_config
+        ));
+        assert!(should_speak_notification(
+            &notification_input(Some("permission_prompt")),
+            &hook_config
+        ));
+
+        // Naming "unknown" in the filter speaks them anyway
+        hook_config.notification_filter.push("unknown".to_string());
+        assert!(should_speak_notification(
+            &notification_input(None),
+            &hook_config
+        ));
+    }
+
+    #[test]
+    fn test_pre_compact_dispatches_to_its_handler() {
+        assert_eq!(
+            HookEvent::from_name("PreCompact"),
+            Some(HookEvent::PreCompact)
+        );
+        assert_eq!(HookEvent::from_name("Stop"), Some(HookEvent::Stop));
+        assert_eq!(HookEvent::from_name("SessionStart"), None);
+
+        let input = ClaudeCodeInput::parse(
+            r#"{"session_id": "s", "transcript_path": "/tmp/t.jsonl", "hook_event_name": "PreCompact", "trigger": "auto"}"#,
+        )
+        .unwrap();
+        assert_eq!(input.trigger.as_deref(), Some("auto"));
+    }
+
+    #[test]
+    fn test_pre_compact_warning_respects_enable_flag() {
+        let mut input = ClaudeCodeInput::parse(
+            r#"{"session_id": "s", "transcript_path": "/tmp/t.jsonl", "hook_event_name": "PreCompact", "trigger": "auto"}"#,
+        )
+        .unwrap();
+        let mut config = SumvoxConfig::default();
+        assert_eq!(pre_compact_warning(&input, &config), None);
+
+        config.hooks.claude_code.pre_compact_warning = true;
+        assert_eq!(
+            pre_compact_warning(&input, &config),
+            Some("Context is getting long, time to wrap up")
+        );
+
+        // The user asked for /compact; no need to tell them
+        input.trigger = Some("manual".to_string());
+        assert_eq!(pre_compact_warning(&input, &config), None);
     }
 
     #[test]
     fn test_tts_options_default() {
         let opts = TtsOptions::default();
         assert_eq!(opts.engine, "auto");
-        assert_eq!(opts.rate, 200);
+        assert_eq!(opts.rate, None);
         assert!(opts.voice.is_none());
         assert!(opts.volume.is_none());
     }
@@ -805,6 +1842,41 @@ mod tests {
         assert_eq!(notification_tts_opts.volume, Some(60));
     }
 
+    #[test]
+    fn test_output_device_per_event() {
+        use crate::audio::device::resolve_output_device;
+
+        let mut config = SumvoxConfig::default();
+        config.tts.output_device = Some("MacBook Pro Speakers".to_string());
+        config.hooks.claude_code.stop_device = Some("AirPods Pro".to_string());
+        let tts_opts = TtsOptions::default();
+        let device_for = |config: &SumvoxConfig, opts: &TtsOptions| {
+            resolve_output_device(opts.device.as_deref(), config.tts.output_device.as_deref())
+                .map(str::to_string)
+        };
+
+        // Summaries go to the headphones, notifications to the global default
+        let stop = stop_tts_options(&config, &tts_opts);
+        let notification = notification_tts_options(&config, &tts_opts);
+        assert_eq!(device_for(&config, &stop).as_deref(), Some("AirPods Pro"));
+        assert_eq!(
+            device_for(&config, &notification).as_deref(),
+            Some("MacBook Pro Speakers")
+        );
+
+        config.hooks.claude_code.notification_device = Some("Studio Display".to_string());
+        let notification = notification_tts_options(&config, &tts_opts);
+        assert_eq!(
+            device_for(&config, &notification).as_deref(),
+            Some("Studio Display")
+        );
+
+        // Without any device configured, playback stays on the system default
+        let plain = SumvoxConfig::default();
+        assert_eq!(stop_tts_options(&plain, &tts_opts).device, None);
+        assert_eq!(plain.tts.output_device, None);
+    }
+
     #[test]
     fn test_cli_volume_overrides_hook_config() {
         let mut config = SumvoxConfig::default();
@@ -842,6 +1914,7 @@ mod tests {
             stability: None,
             style: None,
             style_prompt: None,
+            stream: None,
         };
 
         let volume_override = Some(60u32);
@@ -872,6 +1945,7 @@ mod tests {
             stability: None,
             style: None,
             style_prompt: None,
+            stream: None,
         };
 
         let volume_override: Option<u32> = None;
@@ -902,6 +1976,7 @@ mod tests {
             stability: None,
             style: None,
             style_prompt: None,
+            stream: None,
         };
 
         let volume_override = Some(80u32);
@@ -981,6 +2056,370 @@ mod tests {
 
     // ── Contract 3: select_stop_context_source logic ────────────────────
 
+    #[tokio::test]
+    async fn test_missing_transcript_reported_as_missing() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("deleted-session.jsonl");
+
+        let result = read_transcript_context(
+            &path,
+            &SummarizationConfig::default(),
+            None,
+            AssistantWait::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result, TranscriptContext::Missing);
+    }
+
+    #[tokio::test]
+    async fn test_existing_transcript_returns_texts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","message":{"role":"user","content":"Fix it"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Fixed"}]}}
+"#,
+        )
+        .unwrap();
+
+        let result = read_transcript_context(
+            &path,
+            &SummarizationConfig::default(),
+            None,
+            AssistantWait::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result, TranscriptContext::Texts(vec!["Fixed".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_delayed_assistant_text() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(
+            &path,
+            "{\"type\":\"user\",\"message\":{\"role\":\"user\",\"content\":\"Fix it\"}}\n",
+        )
+        .unwrap();
+
+        // The reply lands in the transcript while the hook is polling
+        let append_path = path.clone();
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(60)).await;
+            use std::io::Write;
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(append_path)
+                .unwrap();
+            writeln!(
+                file,
+                r#"{{"type":"assistant","message":{{"role":"assistant","content":[{{"type":"text","text":"Fixed"}}]}}}}"#
+            )
+            .unwrap();
+        });
+
+        let wait = AssistantWait {
+            budget: Duration::from_secs(5),
+            interval: Duration::from_millis(20),
+        };
+        let result = read_transcript_context(&path, &SummarizationConfig::default(), None, wait)
+            .await
+            .unwrap();
+        writer.await.unwrap();
+        assert_eq!(result, TranscriptContext::Texts(vec!["Fixed".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_assistant_text_gives_up_after_budget() {
+        let reads = std::cell::Cell::new(0);
+        let wait = AssistantWait {
+            budget: Duration::from_millis(50),
+            interval: Duration::from_millis(20),
+        };
+        let started = std::time::Instant::now();
+        let texts = poll_for_texts(wait, || {
+            reads.set(reads.get() + 1);
+            async { Ok(Vec::new()) }
+        })
+        .await
+        .unwrap();
+
+        assert!(texts.is_empty());
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        // Reads at 0, 20, 40 and 50ms (fewer if a sleep overruns), then the
+        // budget is spent
+        assert!((2..=4).contains(&reads.get()), "{} reads", reads.get());
+
+        // No budget: a single read
+        reads.set(0);
+        let no_wait = AssistantWait {
+            budget: Duration::ZERO,
+            ..wait
+        };
+        poll_for_texts(no_wait, || {
+            reads.set(reads.get() + 1);
+            async { Ok(Vec::new()) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(reads.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_trivial_reply_gets_tool_changes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","message":{"role":"user","content":"Bump the version"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Edit","input":{"file_path":"Cargo.toml","old_string":"version = \"1.0.0\"","new_string":"version = \"1.1.0\""}}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Done."}]}}
+"#,
+        )
+        .unwrap();
+
+        assert!(is_trivial_reply("Done."));
+        assert!(!is_trivial_reply(
+            "Bumped the version to 1.1.0 and updated the changelog."
+        ));
+        assert_eq!(
+            with_tool_changes("Done.".to_string(), &path).await,
+            "Done.\n\nChanges made:\nEdit Cargo.toml\n- version = \"1.0.0\"\n+ version = \"1.1.0\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_user_prompt_included_only_when_enabled() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","message":{"role":"user","content":"Why is CI red?"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"1 failed"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"A flaky timeout test."}]}}
+"#,
+        )
+        .unwrap();
+        let reply = "A flaky timeout test.".to_string();
+
+        let mut summarization = SummarizationConfig::default();
+        assert_eq!(
+            stop_context(reply.clone(), &path, &summarization).await,
+            "A flaky timeout test."
+        );
+
+        summarization.include_user_prompt = true;
+        assert_eq!(
+            stop_context(reply, &path, &summarization).await,
+            "User request:\nWhy is CI red?\n\nAssistant response:\nA flaky timeout test."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incremental_reads_only_new_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let marker = dir.path().join("session.line");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","message":{"role":"user","content":"Add a parser"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Parser added"}]}}
+"#,
+        )
+        .unwrap();
+
+        // No marker yet: last turn, then the marker points at EOF
+        let result = read_transcript_context(
+            &path,
+            &SummarizationConfig::default(),
+            Some(&marker),
+            AssistantWait::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            result,
+            TranscriptContext::Texts(vec!["Parser added".to_string()])
+        );
+        assert_eq!(crate::hooks::incremental::read_marker(&marker), Some(2));
+
+        // Two more turns arrive; with turns=1 the first would normally be lost
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            br#"{"type":"user","message":{"role":"user","content":"Docs"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Docs written"}]}}
+{"type":"user","message":{"role":"user","content":"Tests"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Tests pass"}]}}
+"#,
+        )
+        .unwrap();
+
+        let result = read_transcript_context(
+            &path,
+            &SummarizationConfig::default(),
+            Some(&marker),
+            AssistantWait::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            result,
+            TranscriptContext::Texts(vec!["Docs written".to_string(), "Tests pass".to_string()])
+        );
+        assert_eq!(crate::hooks::incremental::read_marker(&marker), Some(6));
+
+        // Nothing new: falls back to the last turn
+        let result = read_transcript_context(
+            &path,
+            &SummarizationConfig::default(),
+            Some(&marker),
+            AssistantWait::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            result,
+            TranscriptContext::Texts(vec!["Tests pass".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_auto_language_adds_instruction_for_non_english_context() {
+        let chinese = "已完成設定檔解析器的重構，所有測試都通過了，接下來要更新文件。";
+        let spanish = "He terminado la refactorización del analizador de configuración. \
+                       Todas las pruebas pasan y ahora voy a actualizar la documentación del proyecto.";
+
+        for context in [chinese, spanish] {
+            let prompt = apply_auto_language("Summarize".to_string(), context, true);
+            assert_eq!(
+                prompt,
+                format!("Summarize\n\n{}", AUTO_LANGUAGE_INSTRUCTION)
+            );
+        }
+
+        // Disabled: never added
+        let prompt = apply_auto_language("Summarize".to_string(), chinese, false);
+        assert_eq!(prompt, "Summarize");
+    }
+
+    #[test]
+    fn test_auto_language_skips_english_short_and_ambiguous_context() {
+        let english = "I refactored the config parser and all of the tests are passing now. \
+                       Next I will update the documentation for the new option.";
+        let short = "完成";
+        let ambiguous = "cargo test ok";
+
+        for context in [english, short, ambiguous] {
+            let prompt = apply_auto_language("Summarize".to_string(), context, true);
+            assert_eq!(prompt, "Summarize", "context: {}", context);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_native_summary_is_redacted_before_speaking() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transcript.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","message":{"role":"user","content":"Rotate the key"}}
+{"type":"summary","summary":"Rotated sk-abcdefghijklmnopqrstuvwx in the env file","leafUuid":"a1"}
+"#,
+        )
+        .unwrap();
+        let summary = TranscriptReader::read_native_summary(&path)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let summarization = SummarizationConfig {
+            redact_secrets: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            stop_spoken_text(&summarization, &summary).as_deref(),
+            Some("Rotated redacted in the env file")
+        );
+        assert_eq!(stop_spoken_text(&summarization, ""), None);
+    }
+
+    #[test]
+    fn test_stop_input_cannot_carry_a_summary() {
+        // A payload naming the old hand-over field is parsed as a normal Stop
+        let input = ClaudeCodeInput::parse(
+            r#"{
+                "session_id": "s1",
+                "transcript_path": "/tmp/t.jsonl",
+                "hook_event_name": "Stop",
+                "last_assistant_message": "Done",
+                "detached_summary": "Injected summary"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(input.last_assistant_message.as_deref(), Some("Done"));
+        assert!(!serde_json::to_string(&input)
+            .unwrap()
+            .contains("Injected summary"));
+
+        // Outside a detached child nothing short-circuits generation
+        assert!(!crate::hooks::detach::is_detached_child());
+        assert_eq!(crate::hooks::detach::detached_summary(), None);
+    }
+
+    fn playback_retry(enabled: bool) -> crate::config::PlaybackConfig {
+        crate::config::PlaybackConfig {
+            retry_on_total_failure: enabled,
+            retry_delay_ms: 1,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_total_failure_plays_second_time() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let attempts = AtomicUsize::new(0);
+
+        // Device busy on the first pass, free on the second
+        let played = retry_on_total_failure(&playback_retry(true), || async {
+            attempts.fetch_add(1, Ordering::SeqCst) > 0
+        })
+        .await;
+
+        assert!(played);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_total_failure_disabled_or_unneeded() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let attempts = AtomicUsize::new(0);
+
+        let played = retry_on_total_failure(&playback_retry(false), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            false
+        })
+        .await;
+        assert!(!played);
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        // A first-pass success never triggers the retry
+        attempts.store(0, Ordering::SeqCst);
+        let played = retry_on_total_failure(&playback_retry(true), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            true
+        })
+        .await;
+        assert!(played);
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn test_select_source_transcript_none() {
         use crate::config::ContentSource;
@@ -1025,4 +2464,147 @@ mod tests {
         let source = select_stop_context_source(ContentSource::LastMessage, None);
         assert!(matches!(source, StopContextSource::ReadTranscript));
     }
+
+    #[test]
+    fn test_stop_chime_from_config() {
+        assert_eq!(StopChime::from_config(None), None);
+        assert_eq!(StopChime::from_config(Some(" ")), None);
+        assert_eq!(StopChime::from_config(Some("Tone")), Some(StopChime::Tone));
+        assert_eq!(
+            StopChime::from_config(Some("/sounds/done.aiff")),
+            Some(StopChime::File(PathBuf::from("/sounds/done.aiff")))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_consecutive_spoken_cues_are_both_played() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = crate::tts::spoken_dedup::SpokenLog::at(dir.path().join("spoken"));
+        let window = Duration::from_secs(60);
+        let spoken = std::cell::RefCell::new(Vec::new());
+
+        // Two queued summaries, each announced with the same cue
+        for summary in ["Refactored the parser", "Fixed the flaky test"] {
+            play_boundary_cue(
+                BoundaryCue::Spoken("Next up".into()),
+                &TtsOptions::default(),
+                |text| async {
+                    spoken.borrow_mut().push(text);
+                    Ok(())
+                },
+            )
+            .await
+            .unwrap();
+            if !log.check_and_record(summary, window, std::time::SystemTime::now()) {
+                spoken.borrow_mut().push(summary.to_string());
+            }
+        }
+        assert_eq!(
+            *spoken.borrow(),
+            [
+                "Next up",
+                "Refactored the parser",
+                "Next up",
+                "Fixed the flaky test"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chime_plays_before_summary() {
+        let events = std::cell::RefCell::new(Vec::new());
+        chime_then_speak(
+            Some(StopChime::Tone),
+            |_| {
+                events.borrow_mut().push("chime");
+                Ok(())
+            },
+            || async {
+                events.borrow_mut().push("speak");
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(*events.borrow(), ["chime", "speak"]);
+
+        // A failed chime still lets the summary play; no chime plays nothing
+        events.borrow_mut().clear();
+        chime_then_speak(
+            Some(StopChime::File(PathBuf::from("/missing.wav"))),
+            |_| Err(crate::error::VoiceError::Voice("no such file".into())),
+            || async {
+                events.borrow_mut().push("speak");
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+        chime_then_speak(
+            None,
+            |_| {
+                events.borrow_mut().push("chime");
+                Ok(())
+            },
+            || async {
+                events.borrow_mut().push("speak");
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(*events.borrow(), ["speak", "speak"]);
+    }
+
+    #[test]
+    fn test_boundary_cue_only_between_queued_playbacks() {
+        let mut playback = PlaybackConfig::default();
+
+        // Disabled: never a cue, even when queued
+        assert_eq!(boundary_cue(&playback, true), None);
+
+        playback.announce_boundaries = true;
+        // First item in the queue (lock was free): no cue
+        assert_eq!(boundary_cue(&playback, false), None);
+        // Queued behind another playback: default tone
+        assert_eq!(boundary_cue(&playback, true), Some(BoundaryCue::Tone));
+
+        playback.boundary_cue = Some("next".to_string());
+        assert_eq!(
+            boundary_cue(&playback, true),
+            Some(BoundaryCue::Spoken("next".to_string()))
+        );
+
+        // Blank marker falls back to the tone
+        playback.boundary_cue = Some("  ".to_string());
+        assert_eq!(boundary_cue(&playback, true), Some(BoundaryCue::Tone));
+    }
+    #[tokio::test]
+    async fn test_notification_delay_is_applied() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sequence = NotificationSequence::at(temp_dir.path().join("latest-notification"));
+        let ticket = sequence.register().unwrap();
+
+        let started = std::time::Instant::now();
+        let delay = Duration::from_millis(50);
+        assert!(wait_notification_delay(delay, Some((ticket, &sequence))).await);
+        assert!(started.elapsed() >= delay);
+    }
+
+    #[tokio::test]
+    async fn test_notification_superseded_during_delay_is_dropped() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("latest-notification");
+        let sequence = NotificationSequence::at(path.clone());
+        let ticket = sequence.register().unwrap();
+
+        // A newer notification arrives while the first one waits
+        let newer = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            NotificationSequence::at(path).register().unwrap();
+        });
+        let delay = Duration::from_millis(100);
+        assert!(!wait_notification_delay(delay, Some((ticket, &sequence))).await);
+        newer.await.unwrap();
+    }
 }
@@ -0,0 +1,162 @@
+// Detached hook playback
+// Re-runs `sumvox json` in a background process so the hook returns to the
+// agent immediately while speech continues. The child goes through the normal
+// hook path, so it still takes the cross-process playback lock.
+
+use std::io::Write;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::error::{Result, VoiceError};
+
+/// Set in the detached child so it plays in the foreground instead of detaching again
+pub const DETACHED_ENV: &str = "SUMVOX_DETACHED";
+
+/// Summary a detached Stop child should play, set by the parent that generated it
+pub const DETACHED_SUMMARY_ENV: &str = "SUMVOX_DETACHED_SUMMARY";
+
+/// True when running inside a detached playback child
+pub fn is_detached_child() -> bool {
+    std::env::var_os(DETACHED_ENV).is_some()
+}
+
+/// Summary handed over by the parent Stop hook, when this is its detached child
+pub fn detached_summary() -> Option<String> {
+    detached_summary_from(|name| std::env::var(name).ok())
+}
+
+/// `detached_summary` with the environment lookup supplied. Ignored outside
+/// a detached child, so a stray variable can't replace a real summary.
+fn detached_summary_from(env: impl Fn(&str) -> Option<String>) -> Option<String> {
+    env(DETACHED_ENV)?;
+    env(DETACHED_SUMMARY_ENV)
+}
+
+/// Build a detached `sumvox <args>` command (seam for tests: nothing is spawned here).
+///
+/// The child runs in its own process group so the terminal's signals don't
+/// reach it, and has no stdout/stderr to hold the caller open.
+pub fn background_command<S: AsRef<std::ffi::OsStr>>(exe: &Path, args: &[S]) -> Command {
+    let mut cmd = Command::new(exe);
+    cmd.args(args)
+        .env(DETACHED_ENV, "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0);
+    cmd
+}
+
+/// Parent `sumvox json` options the detached child must run with too
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChildOptions {
+    /// `--timeout`
+    pub timeout: u64,
+    /// `--budget-override`
+    pub budget_override: Option<f64>,
+    /// `--fresh-config`
+    pub fresh_config: bool,
+}
+
+/// Command for a detached hook child, which reads the hook JSON on stdin
+pub fn detached_command(exe: &Path, opts: &ChildOptions) -> Command {
+    let mut args = vec![
+        "json".to_string(),
+        "--format".to_string(),
+        "claude-code".to_string(),
+        "--timeout".to_string(),
+        opts.timeout.to_string(),
+    ];
+    if let Some(usd) = opts.budget_override {
+        args.extend(["--budget-override".to_string(), usd.to_string()]);
+    }
+    if opts.fresh_config {
+        args.push("--fresh-config".to_string());
+    }
+    let mut cmd = background_command(exe, &args);
+    cmd.stdin(Stdio::piped());
+    cmd
+}
+
+/// Spawn the detached child, hand it `stdin_data` (if any) and return without waiting
+pub fn spawn_detached(mut cmd: Command, stdin_data: Option<&str>) -> Result<()> {
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| VoiceError::Voice(format!("Failed to spawn detached playback: {}", e)))?;
+
+    if let (Some(data), Some(mut stdin)) = (stdin_data, child.stdin.take()) {
+        stdin.write_all(data.as_bytes())?;
+    }
+
+    tracing::info!("Detached playback started (pid {})", child.id());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_detached_command_setup() {
+        let opts = ChildOptions {
+            timeout: 15,
+            budget_override: None,
+            fresh_config: false,
+        };
+        let cmd = detached_command(Path::new("/usr/local/bin/sumvox"), &opts);
+
+        assert_eq!(cmd.get_program(), "/usr/local/bin/sumvox");
+        let args: Vec<&OsStr> = cmd.get_args().collect();
+        assert_eq!(args, ["json", "--format", "claude-code", "--timeout", "15"]);
+
+        let envs: Vec<_> = cmd.get_envs().collect();
+        assert_eq!(envs, [(OsStr::new(DETACHED_ENV), Some(OsStr::new("1")))]);
+    }
+
+    #[test]
+    fn test_detached_command_forwards_parent_options() {
+        let opts = ChildOptions {
+            timeout: 20,
+            budget_override: Some(2.5),
+            fresh_config: true,
+        };
+        let cmd = detached_command(Path::new("/usr/local/bin/sumvox"), &opts);
+
+        let args: Vec<&OsStr> = cmd.get_args().collect();
+        assert_eq!(
+            args,
+            [
+                "json",
+                "--format",
+                "claude-code",
+                "--timeout",
+                "20",
+                "--budget-override",
+                "2.5",
+                "--fresh-config"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detached_summary_only_in_detached_child() {
+        let child = |name: &str| match name {
+            DETACHED_ENV => Some("1".to_string()),
+            DETACHED_SUMMARY_ENV => Some("Tests pass now".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            detached_summary_from(child).as_deref(),
+            Some("Tests pass now")
+        );
+
+        let not_child = |name: &str| match name {
+            DETACHED_SUMMARY_ENV => Some("Injected".to_string()),
+            _ => None,
+        };
+        assert_eq!(detached_summary_from(not_child), None);
+        assert_eq!(detached_summary_from(|_| None), None);
+    }
+}
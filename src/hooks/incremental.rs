@@ -0,0 +1,62 @@
+// Incremental Stop summaries
+// Remembers how far into each session's transcript the last summary read, so
+// the next Stop hook only summarizes what was added since.
+
+use std::path::{Path, PathBuf};
+
+/// Marker file for a session: ~/.cache/sumvox/sessions/{session_id}.line
+pub fn marker_path(session_id: &str) -> Option<PathBuf> {
+    // Session ids are UUIDs; anything else is reduced to a safe file name
+    let name: String = session_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    if name.is_empty() {
+        return None;
+    }
+    dirs::home_dir().map(|home| {
+        home.join(".cache")
+            .join("sumvox")
+            .join("sessions")
+            .join(format!("{}.line", name))
+    })
+}
+
+/// Transcript line count recorded after the last summary, if any
+pub fn read_marker(path: &Path) -> Option<usize> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Record the transcript line count that has now been summarized
+pub fn write_marker(path: &Path, line_count: usize) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, line_count.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("sessions").join("abc.line");
+
+        assert_eq!(read_marker(&path), None);
+        write_marker(&path, 42).unwrap();
+        assert_eq!(read_marker(&path), Some(42));
+        write_marker(&path, 57).unwrap();
+        assert_eq!(read_marker(&path), Some(57));
+    }
+
+    #[test]
+    fn test_marker_path_sanitizes_session_id() {
+        let path = marker_path("../../etc/passwd").unwrap();
+        assert_eq!(path.file_name().unwrap(), "etcpasswd.line");
+        assert!(path.ends_with(".cache/sumvox/sessions/etcpasswd.line"));
+
+        assert!(marker_path("../").is_none());
+    }
+}
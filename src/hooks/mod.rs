@@ -2,12 +2,15 @@
 // Handles JSON input from various AI coding tools with format detection
 
 pub mod claude_code;
+pub mod detach;
+pub mod incremental;
 
 use serde::Deserialize;
 use serde_json::Value;
 use std::str::FromStr;
 
 use crate::error::{Result, VoiceError};
+use crate::transcript::{ClaudeCodeParser, TranscriptParser};
 
 /// Detected hook format
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,6 +31,16 @@ impl FromStr for HookFormat {
     }
 }
 
+impl HookFormat {
+    /// Parser for the transcripts this tool writes. Generic input carries no
+    /// transcript of its own, so it gets Claude Code's format.
+    pub fn transcript_parser(&self) -> &'static dyn TranscriptParser {
+        match self {
+            HookFormat::ClaudeCode | HookFormat::Generic => &ClaudeCodeParser,
+        }
+    }
+}
+
 /// Generic hook input for format detection
 #[derive(Debug, Deserialize)]
 pub struct GenericHookInput {
@@ -42,6 +55,10 @@ pub struct GenericHookInput {
     /// Content field (another alternative)
     #[serde(default)]
     pub content: Option<String>,
+
+    /// Optional heading spoken before the text
+    #[serde(default)]
+    pub title: Option<String>,
 }
 
 impl GenericHookInput {
@@ -52,6 +69,30 @@ impl GenericHookInput {
             .or(self.message.as_deref())
             .or(self.content.as_deref())
     }
+
+    /// The text with `title` in front of it, when one is given
+    pub fn get_titled_text(&self) -> Option<String> {
+        self.get_text()
+            .map(|text| with_title(self.title.as_deref(), text))
+    }
+}
+
+/// Join a notification title and message into one utterance with a pause
+/// between them: "Permission needed. Allow bash command?".
+/// Without a (non-blank) title the message is returned unchanged.
+pub fn with_title(title: Option<&str>, message: &str) -> String {
+    let title = title.map(str::trim).unwrap_or("");
+    if title.is_empty() {
+        return message.to_string();
+    }
+
+    let ends_sentence = title.ends_with(['.', '!', '?', ':', ';']);
+    format!(
+        "{}{} {}",
+        title,
+        if ends_sentence { "" } else { "." },
+        message.trim()
+    )
 }
 
 /// Detect the format of JSON input
@@ -72,6 +113,34 @@ pub fn parse_input(input: &str) -> Result<(Value, HookFormat)> {
     Ok((json, format))
 }
 
+/// Parse JSON input in the `requested` format ("auto" detects it).
+/// An explicit format skips detection, and input missing that format's
+/// fields is an error rather than a fallback to detection.
+pub fn parse_input_as(input: &str, requested: &str) -> Result<(Value, HookFormat)> {
+    if requested.eq_ignore_ascii_case("auto") {
+        return parse_input(input);
+    }
+    let format: HookFormat = requested.parse()?;
+    let json: Value = serde_json::from_str(input)?;
+
+    let has_string = |field: &str| json.get(field).is_some_and(Value::is_string);
+    let missing = match format {
+        HookFormat::ClaudeCode => !(has_string("session_id") && has_string("hook_event_name")),
+        HookFormat::Generic => !["text", "message", "content"].into_iter().any(has_string),
+    };
+    if missing {
+        let required = match format {
+            HookFormat::ClaudeCode => "'session_id' and 'hook_event_name'",
+            HookFormat::Generic => "'text', 'message', or 'content'",
+        };
+        return Err(VoiceError::Config(format!(
+            "--format {} requires {} in the input",
+            requested, required
+        )));
+    }
+    Ok((json, format))
+}
+
 /// Parse as generic hook input
 pub fn parse_generic(input: &str) -> Result<GenericHookInput> {
     let generic: GenericHookInput = serde_json::from_str(input)?;
@@ -148,6 +217,42 @@ mod tests {
         assert_eq!(generic.get_text(), Some("Hello from content"));
     }
 
+    #[test]
+    fn test_parse_generic_with_title() {
+        let input = r#"{"title": "Build", "text": "All tests passed"}"#;
+        let generic = parse_generic(input).unwrap();
+        assert_eq!(generic.get_text(), Some("All tests passed"));
+        assert_eq!(
+            generic.get_titled_text().as_deref(),
+            Some("Build. All tests passed")
+        );
+    }
+
+    #[test]
+    fn test_with_title_joins_with_pause() {
+        assert_eq!(
+            with_title(Some("Permission needed"), "Allow bash command?"),
+            "Permission needed. Allow bash command?"
+        );
+        // Existing punctuation is kept rather than doubled
+        assert_eq!(
+            with_title(Some("Heads up!"), "Tests failed"),
+            "Heads up! Tests failed"
+        );
+    }
+
+    #[test]
+    fn test_with_title_falls_back_to_message() {
+        assert_eq!(
+            with_title(None, "Allow bash command?"),
+            "Allow bash command?"
+        );
+        assert_eq!(
+            with_title(Some("  "), "Allow bash command?"),
+            "Allow bash command?"
+        );
+    }
+
     #[test]
     fn test_parse_generic_empty_fails() {
         let input = r#"{}"#;
@@ -155,6 +260,38 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_explicit_format_skips_detection() {
+        // Looks like Claude Code to detection, but generic was asked for
+        let ambiguous = r#"{"text": "Build done", "session_id": "abc", "hook_event_name": "Stop"}"#;
+        let (_, format) = parse_input_as(ambiguous, "generic").unwrap();
+        assert_eq!(format, HookFormat::Generic);
+        let (_, format) = parse_input_as(ambiguous, "auto").unwrap();
+        assert_eq!(format, HookFormat::ClaudeCode);
+    }
+
+    #[test]
+    fn test_explicit_format_mismatch_errors() {
+        let generic = r#"{"text": "Build done"}"#;
+        let err = parse_input_as(generic, "claude-code")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("--format claude-code requires 'session_id' and 'hook_event_name'"),
+            "unexpected error: {err}"
+        );
+
+        let claude = r#"{"session_id": "abc", "hook_event_name": "Stop"}"#;
+        let err = parse_input_as(claude, "generic").unwrap_err().to_string();
+        assert!(err.contains("'text', 'message', or 'content'"), "{err}");
+
+        // An unknown explicit format no longer falls back to detection
+        let err = parse_input_as(generic, "gemini-cli")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown hook format: gemini-cli"), "{err}");
+    }
+
     #[test]
     fn test_parse_input() {
         let input = r#"{"session_id": "test", "hook_event_name": "Stop"}"#;
@@ -0,0 +1,144 @@
+// API keys in the OS keychain (`credentials set --keychain`)
+// Keys live under the service "sumvox", one entry per provider name
+// (lowercased). Provider key lookups check here before the config file and
+// environment. Tests swap the OS keychain for an in-memory store.
+
+use crate::error::{Result, VoiceError};
+
+/// Backend holding keys by account (provider) name
+trait SecretStore: Sync {
+    fn get(&self, account: &str) -> Result<Option<String>>;
+    fn set(&self, account: &str, secret: &str) -> Result<()>;
+    /// Returns whether there was a key to remove
+    fn remove(&self, account: &str) -> Result<bool>;
+}
+
+/// macOS Keychain, Windows Credential Manager or the Linux kernel keyring
+#[cfg_attr(test, allow(dead_code))]
+struct OsKeychain;
+
+#[cfg_attr(test, allow(dead_code))]
+impl OsKeychain {
+    const SERVICE: &'static str = "sumvox";
+
+    fn entry(account: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(Self::SERVICE, account).map_err(Self::error)
+    }
+
+    fn error(e: keyring::Error) -> VoiceError {
+        VoiceError::Config(format!("Keychain error: {}", e))
+    }
+}
+
+impl SecretStore for OsKeychain {
+    fn get(&self, account: &str) -> Result<Option<String>> {
+        match Self::entry(account)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(Self::error(e)),
+        }
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<()> {
+        Self::entry(account)?
+            .set_password(secret)
+            .map_err(Self::error)
+    }
+
+    fn remove(&self, account: &str) -> Result<bool> {
+        match Self::entry(account)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(Self::error(e)),
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn store() -> &'static dyn SecretStore {
+    &OsKeychain
+}
+
+#[cfg(test)]
+fn store() -> &'static dyn SecretStore {
+    static MOCK: tests::MockStore = tests::MockStore::new();
+    &MOCK
+}
+
+fn account(provider: &str) -> String {
+    provider.to_lowercase()
+}
+
+/// Key stored for `provider`, if any. Keychain failures are logged and
+/// treated as no key, so lookups fall through to config and environment.
+pub fn get(provider: &str) -> Option<String> {
+    match store().get(&account(provider)) {
+        Ok(secret) => secret.filter(|s| !s.is_empty()),
+        Err(e) => {
+            tracing::debug!("No keychain key for {}: {}", provider, e);
+            None
+        }
+    }
+}
+
+/// Store `key` for `provider`, replacing any previous one
+pub fn set(provider: &str, key: &str) -> Result<()> {
+    store().set(&account(provider), key)
+}
+
+/// Delete the key for `provider`. Returns whether one was stored.
+pub fn remove(provider: &str) -> Result<bool> {
+    store().remove(&account(provider))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// In-memory stand-in for the OS keychain
+    pub(crate) struct MockStore(Mutex<Option<HashMap<String, String>>>);
+
+    impl MockStore {
+        pub(crate) const fn new() -> Self {
+            Self(Mutex::new(None))
+        }
+    }
+
+    impl SecretStore for MockStore {
+        fn get(&self, account: &str) -> Result<Option<String>> {
+            let map = self.0.lock().unwrap();
+            Ok(map.as_ref().and_then(|m| m.get(account).cloned()))
+        }
+
+        fn set(&self, account: &str, secret: &str) -> Result<()> {
+            let mut map = self.0.lock().unwrap();
+            map.get_or_insert_with(HashMap::new)
+                .insert(account.to_string(), secret.to_string());
+            Ok(())
+        }
+
+        fn remove(&self, account: &str) -> Result<bool> {
+            let mut map = self.0.lock().unwrap();
+            Ok(map.as_mut().and_then(|m| m.remove(account)).is_some())
+        }
+    }
+
+    #[test]
+    fn test_set_get_remove_round_trip() {
+        assert_eq!(get("roundtrip-provider"), None);
+
+        set("roundtrip-provider", "sk-first").unwrap();
+        assert_eq!(get("roundtrip-provider").as_deref(), Some("sk-first"));
+        // Provider names are case-insensitive, like in the config
+        assert_eq!(get("RoundTrip-Provider").as_deref(), Some("sk-first"));
+
+        set("roundtrip-provider", "sk-second").unwrap();
+        assert_eq!(get("roundtrip-provider").as_deref(), Some("sk-second"));
+
+        assert!(remove("roundtrip-provider").unwrap());
+        assert_eq!(get("roundtrip-provider"), None);
+        assert!(!remove("roundtrip-provider").unwrap());
+    }
+}
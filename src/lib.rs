@@ -1,13 +1,20 @@
 // Library exports for sumvox
 
 pub mod audio;
+pub mod benchmark;
 pub mod cli;
 pub mod config;
+pub mod daemon;
+pub mod desktop_notification;
 pub mod error;
 pub mod hooks;
+pub mod keychain;
 pub mod llm;
 pub mod notify_log;
 pub mod provider_factory;
 pub mod queue;
+pub mod retry;
 pub mod transcript;
 pub mod tts;
+pub mod watch;
+pub mod wizard;
@@ -1,11 +1,11 @@
 // Anthropic API provider implementation
 
+use super::http::HttpContext;
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use super::{GenerationRequest, GenerationResponse, LlmProvider};
+use super::{GenerationRequest, GenerationResponse, LlmCapabilities, LlmProvider};
 use crate::error::{LlmError, LlmResult};
 
 #[allow(dead_code)]
@@ -19,12 +19,14 @@ struct AnthropicRequest {
     messages: Vec<Message>,
     #[serde(skip_serializing_if = "Option::is_none")]
     system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize)]
-struct Message {
-    role: String,
-    content: String,
+pub(super) struct Message {
+    pub(super) role: String,
+    pub(super) content: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -55,11 +57,73 @@ struct Usage {
     output_tokens: u32,
 }
 
+#[derive(Debug, Deserialize)]
+struct ModelsResponse {
+    data: Vec<ModelEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    id: String,
+}
+
+/// Parse a Messages API response body (shared with Bedrock, which returns the
+/// same shape), keeping only text blocks
+pub(super) fn parse_messages_response(
+    response_text: &str,
+    model: &str,
+) -> LlmResult<GenerationResponse> {
+    let anthropic_response: AnthropicResponse =
+        serde_json::from_str(response_text).map_err(|e| {
+            LlmError::Request(format!(
+                "Failed to parse Anthropic response: {}. Response body: {}",
+                e, response_text
+            ))
+        })?;
+
+    if anthropic_response.content.is_empty() {
+        return Err(LlmError::Request(
+            "No content in Anthropic response".to_string(),
+        ));
+    }
+
+    // Extract text from content blocks, skipping thinking blocks
+    let text = anthropic_response
+        .content
+        .iter()
+        .filter_map(|c| {
+            match c.content_type.as_str() {
+                "text" => c.text.as_deref(),
+                "thinking" => {
+                    // Log thinking content in debug mode
+                    if let Some(thinking) = &c.thinking {
+                        tracing::debug!("Extended thinking: {}", thinking);
+                    }
+                    None
+                }
+                _ => {
+                    tracing::warn!("Unknown content type: {}", c.content_type);
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("");
+
+    Ok(GenerationResponse {
+        text,
+        input_tokens: anthropic_response.usage.input_tokens,
+        output_tokens: anthropic_response.usage.output_tokens,
+        model: model.to_string(),
+    })
+}
+
 pub struct AnthropicProvider {
     api_key: String,
     model: String,
     base_url: String,
     timeout: Duration,
+    http: HttpContext,
 }
 
 impl AnthropicProvider {
@@ -79,18 +143,27 @@ impl AnthropicProvider {
             model,
             base_url,
             timeout,
+            http: HttpContext::new(),
         }
     }
 
-    fn client(&self) -> Client {
-        Client::builder()
-            .no_proxy() // Disable system proxy detection to avoid CoreFoundation crash
-            .timeout(self.timeout)
-            .build()
-            .unwrap_or_else(|_| Client::new())
+    /// Send requests through a shared client instead of this provider's own
+    pub fn with_http(mut self, http: HttpContext) -> Self {
+        self.http = http;
+        self
     }
 }
 
+/// What SumVox uses through this provider
+pub const CAPABILITIES: LlmCapabilities = LlmCapabilities {
+    system_message: true,
+    // Extended thinking is never requested
+    thinking: false,
+    stop_sequences: true,
+    streaming: false,
+    json_mode: false,
+};
+
 #[async_trait]
 impl LlmProvider for AnthropicProvider {
     fn name(&self) -> &str {
@@ -119,13 +192,14 @@ impl LlmProvider for AnthropicProvider {
                 content: request.prompt.clone(),
             }],
             system: request.system_message.clone(),
+            stop_sequences: request.stop.clone(),
         };
 
         tracing::debug!("Sending request to Anthropic API: {}", self.model);
 
         let response = self
-            .client()
-            .post(&url)
+            .http
+            .post(&url, self.timeout)
             .header("x-api-key", &self.api_key)
             .header("anthropic-version", ANTHROPIC_VERSION)
             .header("content-type", "application/json")
@@ -141,57 +215,26 @@ impl LlmProvider for AnthropicProvider {
             .map_err(|e| LlmError::Request(format!("Failed to read response body: {}", e)))?;
 
         if !status.is_success() {
-            return Err(LlmError::Request(format!(
-                "Anthropic API returned {}: {}",
-                status, response_text
-            )));
+            let message = format!("Anthropic API returned {}: {}", status, response_text);
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Err(LlmError::RateLimited(message));
+            }
+            if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+                return Err(LlmError::ModelUnavailable(message));
+            }
+            return Err(LlmError::Request(message));
         }
 
-        tracing::debug!("Anthropic API response: {}", response_text);
-
-        let anthropic_response: AnthropicResponse =
-            serde_json::from_str(&response_text).map_err(|e| {
-                LlmError::Request(format!(
-                    "Failed to parse Anthropic response: {}. Response body: {}",
-                    e, response_text
-                ))
-            })?;
+        tracing::debug!(
+            "Anthropic API response: {}",
+            super::redact::body_for_log(&response_text)
+        );
 
-        if anthropic_response.content.is_empty() {
-            return Err(LlmError::Request(
-                "No content in Anthropic response".to_string(),
-            ));
-        }
+        parse_messages_response(&response_text, &self.model)
+    }
 
-        // Extract text from content blocks, skipping thinking blocks
-        let text = anthropic_response
-            .content
-            .iter()
-            .filter_map(|c| {
-                match c.content_type.as_str() {
-                    "text" => c.text.as_deref(),
-                    "thinking" => {
-                        // Log thinking content in debug mode
-                        if let Some(thinking) = &c.thinking {
-                            tracing::debug!("Extended thinking: {}", thinking);
-                        }
-                        None
-                    }
-                    _ => {
-                        tracing::warn!("Unknown content type: {}", c.content_type);
-                        None
-                    }
-                }
-            })
-            .collect::<Vec<_>>()
-            .join("");
-
-        Ok(GenerationResponse {
-            text,
-            input_tokens: anthropic_response.usage.input_tokens,
-            output_tokens: anthropic_response.usage.output_tokens,
-            model: self.model.clone(),
-        })
+    fn capabilities(&self) -> LlmCapabilities {
+        CAPABILITIES
     }
 
     fn estimate_cost(&self, input_tokens: u32, output_tokens: u32) -> f64 {
@@ -207,6 +250,38 @@ impl LlmProvider for AnthropicProvider {
 
         input_cost + output_cost
     }
+
+    async fn list_models(&self) -> LlmResult<Vec<String>> {
+        if !self.is_available() {
+            return Err(LlmError::Unavailable(
+                "Anthropic API key not configured".to_string(),
+            ));
+        }
+
+        let url = format!("{}/models", self.base_url);
+        let response = self
+            .http
+            .get(&url, self.timeout)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .send()
+            .await
+            .map_err(|e| LlmError::Request(format!("Anthropic models request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(LlmError::Request(format!(
+                "Anthropic models endpoint returned {}",
+                response.status()
+            )));
+        }
+
+        let models: ModelsResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::Request(format!("Failed to parse Anthropic models: {}", e)))?;
+
+        Ok(models.data.into_iter().map(|m| m.id).collect())
+    }
 }
 
 #[cfg(test)]
@@ -225,6 +300,31 @@ mod tests {
         assert!(provider.is_available());
     }
 
+    #[test]
+    fn test_providers_share_context_client() {
+        let http = HttpContext::new();
+        let first = AnthropicProvider::new(
+            "key-a".to_string(),
+            "claude-haiku-4-5".to_string(),
+            Duration::from_secs(10),
+        )
+        .with_http(http.clone());
+        let second = AnthropicProvider::new(
+            "key-b".to_string(),
+            "claude-sonnet-4-5".to_string(),
+            Duration::from_secs(30),
+        )
+        .with_http(http.clone());
+        let standalone = AnthropicProvider::new(
+            "key-c".to_string(),
+            "claude-haiku-4-5".to_string(),
+            Duration::from_secs(10),
+        );
+
+        assert!(first.http.shares_client_with(&second.http));
+        assert!(!first.http.shares_client_with(&standalone.http));
+    }
+
     #[test]
     fn test_is_available_with_key() {
         let provider = AnthropicProvider::new(
@@ -285,6 +385,9 @@ mod tests {
             max_tokens: 100,
             temperature: 0.3,
             disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
         };
 
         let result = provider.generate(&request).await;
@@ -292,6 +395,39 @@ mod tests {
         assert!(matches!(result.unwrap_err(), LlmError::Unavailable(_)));
     }
 
+    #[tokio::test]
+    async fn test_generate_sends_stop_sequences() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/messages")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "stop_sequences": ["\n\n", "END"],
+            })))
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let provider = AnthropicProvider::with_base_url(
+            "test-key".to_string(),
+            "claude-haiku-4-5".to_string(),
+            server.url(),
+            Duration::from_secs(5),
+        );
+        let request = GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 50,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: Some(vec!["\n\n".to_string(), "END".to_string()]),
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        let _ = provider.generate(&request).await;
+        mock.assert_async().await;
+    }
+
     // ── C4: AnthropicRequestSerialization ───────────────────────────────
 
     /// Build an AnthropicRequest the same way generate() does, for test assertions.
@@ -304,6 +440,7 @@ mod tests {
                 content: "Test".to_string(),
             }],
             system: None,
+            stop_sequences: None,
         }
     }
 
@@ -343,6 +480,7 @@ mod tests {
                     content: "Hi".to_string(),
                 }],
                 system: None,
+                stop_sequences: None,
             };
             let val = serde_json::to_value(&req).unwrap();
             assert!(
@@ -369,6 +507,9 @@ mod tests {
             max_tokens: 50,
             temperature: 0.3,
             disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
         };
 
         let response = provider.generate(&request).await.unwrap();
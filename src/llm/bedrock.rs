@@ -0,0 +1,510 @@
+// Anthropic models via AWS Bedrock
+// Same Messages API body as the public endpoint, minus `model` (it lives in the
+// URL) and with Bedrock's `anthropic_version`. Requests are signed with SigV4
+// using the standard AWS_* credential environment variables.
+
+use super::http::HttpContext;
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+use super::anthropic::{parse_messages_response, Message};
+use super::{GenerationRequest, GenerationResponse, LlmCapabilities, LlmProvider};
+use crate::error::{LlmError, LlmResult};
+
+const BEDROCK_ANTHROPIC_VERSION: &str = "bedrock-2023-05-31";
+const SERVICE: &str = "bedrock";
+
+#[derive(Debug, Serialize)]
+struct BedrockRequest {
+    anthropic_version: &'static str,
+    max_tokens: u32,
+    messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+}
+
+/// AWS credentials used for SigV4 signing
+#[derive(Debug, Clone)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// Read AWS_ACCESS_KEY_ID / AWS_SECRET_ACCESS_KEY / AWS_SESSION_TOKEN
+    pub fn from_env() -> Option<Self> {
+        let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        Some(Self {
+            access_key_id: non_empty("AWS_ACCESS_KEY_ID")?,
+            secret_access_key: non_empty("AWS_SECRET_ACCESS_KEY")?,
+            session_token: non_empty("AWS_SESSION_TOKEN"),
+        })
+    }
+}
+
+/// Bedrock model id for a configured model: full ids (`anthropic.…` or an
+/// inference profile such as `us.anthropic.…`) are kept, a bare Claude model
+/// name gets the `anthropic.` prefix and `-v1:0` version suffix
+pub fn bedrock_model_id(model: &str) -> String {
+    if model.starts_with("anthropic.") || model.contains(".anthropic.") {
+        return model.to_string();
+    }
+    if model.contains(':') {
+        format!("anthropic.{}", model)
+    } else {
+        format!("anthropic.{}-v1:0", model)
+    }
+}
+
+/// Default runtime endpoint for a region
+pub fn bedrock_endpoint(region: &str) -> String {
+    format!("https://bedrock-runtime.{}.amazonaws.com", region)
+}
+
+/// Path of the InvokeModel call for `model_id`, with the id percent-encoded
+/// (`:` in version suffixes becomes `%3A`)
+pub fn invoke_path(model_id: &str) -> String {
+    format!("/model/{}/invoke", uri_encode(model_id))
+}
+
+pub struct BedrockProvider {
+    credentials: Option<AwsCredentials>,
+    region: String,
+    model: String,
+    endpoint: String,
+    timeout: Duration,
+    http: HttpContext,
+}
+
+impl BedrockProvider {
+    pub fn new(
+        credentials: Option<AwsCredentials>,
+        region: String,
+        model: String,
+        timeout: Duration,
+    ) -> Self {
+        let endpoint = bedrock_endpoint(&region);
+        Self::with_endpoint(credentials, region, model, endpoint, timeout)
+    }
+
+    pub fn with_endpoint(
+        credentials: Option<AwsCredentials>,
+        region: String,
+        model: String,
+        endpoint: String,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            credentials,
+            region,
+            model,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            timeout,
+            http: HttpContext::new(),
+        }
+    }
+
+    /// Send requests through a shared client instead of this provider's own
+    pub fn with_http(mut self, http: HttpContext) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+/// What SumVox uses through this provider
+pub const CAPABILITIES: LlmCapabilities = LlmCapabilities {
+    system_message: true,
+    // Same Messages API as the public Anthropic endpoint
+    thinking: false,
+    stop_sequences: true,
+    streaming: false,
+    json_mode: false,
+};
+
+#[async_trait]
+impl LlmProvider for BedrockProvider {
+    fn name(&self) -> &str {
+        "bedrock"
+    }
+
+    fn is_available(&self) -> bool {
+        self.credentials.is_some() && !self.region.is_empty()
+    }
+
+    async fn generate(&self, request: &GenerationRequest) -> LlmResult<GenerationResponse> {
+        let Some(credentials) = self.credentials.as_ref().filter(|_| self.is_available()) else {
+            return Err(LlmError::Unavailable(
+                "Bedrock needs AWS credentials and a region".to_string(),
+            ));
+        };
+
+        let body = serde_json::to_vec(&BedrockRequest {
+            anthropic_version: BEDROCK_ANTHROPIC_VERSION,
+            max_tokens: request.max_tokens,
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: request.prompt.clone(),
+            }],
+            system: request.system_message.clone(),
+            stop_sequences: request.stop.clone(),
+        })
+        .map_err(|e| LlmError::Request(format!("Failed to encode Bedrock request: {}", e)))?;
+
+        let model_id = bedrock_model_id(&self.model);
+        let path = invoke_path(&model_id);
+        let url = format!("{}{}", self.endpoint, path);
+        let host = reqwest::Url::parse(&url)
+            .ok()
+            .and_then(|u| {
+                u.host_str().map(|h| match u.port() {
+                    Some(port) => format!("{}:{}", h, port),
+                    None => h.to_string(),
+                })
+            })
+            .ok_or_else(|| LlmError::Request(format!("Invalid Bedrock endpoint: {}", url)))?;
+
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let headers = signed_headers(credentials, &self.region, &host, &path, &body, &amz_date);
+
+        tracing::debug!("Sending request to Bedrock: {}", model_id);
+
+        let mut builder = self.http.post(&url, self.timeout).body(body);
+        for (name, value) in &headers {
+            if name != "host" {
+                builder = builder.header(name.as_str(), value);
+            }
+        }
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| LlmError::Request(format!("Bedrock request failed: {}", e)))?;
+
+        let status = response.status();
+        let response_text = response
+            .text()
+            .await
+            .map_err(|e| LlmError::Request(format!("Failed to read response body: {}", e)))?;
+
+        if !status.is_success() {
+            let message = format!("Bedrock returned {}: {}", status, response_text);
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Err(LlmError::RateLimited(message));
+            }
+            if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+                return Err(LlmError::ModelUnavailable(message));
+            }
+            return Err(LlmError::Request(message));
+        }
+
+        tracing::debug!(
+            "Bedrock response: {}",
+            super::redact::body_for_log(&response_text)
+        );
+
+        parse_messages_response(&response_text, &self.model)
+    }
+
+    fn capabilities(&self) -> LlmCapabilities {
+        CAPABILITIES
+    }
+
+    fn estimate_cost(&self, input_tokens: u32, output_tokens: u32) -> f64 {
+        // Bedrock on-demand prices match the public API (Claude 4.5 Haiku)
+        const INPUT_COST_PER_1K: f64 = 0.001;
+        const OUTPUT_COST_PER_1K: f64 = 0.005;
+
+        (input_tokens as f64 / 1000.0) * INPUT_COST_PER_1K
+            + (output_tokens as f64 / 1000.0) * OUTPUT_COST_PER_1K
+    }
+}
+
+// ============================================================================
+// SigV4 signing
+// ============================================================================
+
+/// Headers for a signed Bedrock POST, including `authorization`
+fn signed_headers(
+    credentials: &AwsCredentials,
+    region: &str,
+    host: &str,
+    path: &str,
+    body: &[u8],
+    amz_date: &str,
+) -> Vec<(String, String)> {
+    let mut headers = vec![
+        ("content-type".to_string(), "application/json".to_string()),
+        ("host".to_string(), host.to_string()),
+        ("x-amz-date".to_string(), amz_date.to_string()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+
+    let authorization = authorization_header(
+        credentials,
+        region,
+        SERVICE,
+        "POST",
+        &uri_encode_path(path),
+        &headers,
+        body,
+        amz_date,
+    );
+    headers.push(("authorization".to_string(), authorization));
+    headers
+}
+
+/// SigV4 `Authorization` value. `headers` must be lowercase and include
+/// `host` and `x-amz-date`; `canonical_uri` is the already-canonicalized path.
+#[allow(clippy::too_many_arguments)]
+fn authorization_header(
+    credentials: &AwsCredentials,
+    region: &str,
+    service: &str,
+    method: &str,
+    canonical_uri: &str,
+    headers: &[(String, String)],
+    body: &[u8],
+    amz_date: &str,
+) -> String {
+    let mut sorted: Vec<&(String, String)> = headers.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let canonical_headers: String = sorted
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = sorted
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        method,
+        canonical_uri,
+        canonical_headers,
+        signed_headers,
+        hex(&Sha256::digest(body))
+    );
+
+    let date = &amz_date[..8];
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let k_date = hmac_sha256(
+        format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+        date.as_bytes(),
+    );
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    let k_signing = hmac_sha256(&k_service, b"aws4_request");
+    let signature = hex(&hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters
+fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Canonical URI for non-S3 services: each segment of the (already encoded)
+/// path is encoded again
+fn uri_encode_path(path: &str) -> String {
+    path.split('/')
+        .map(uri_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_credentials(session_token: Option<&str>) -> AwsCredentials {
+        AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: session_token.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_bedrock_model_id() {
+        assert_eq!(
+            bedrock_model_id("claude-3-5-haiku-20241022"),
+            "anthropic.claude-3-5-haiku-20241022-v1:0"
+        );
+        assert_eq!(
+            bedrock_model_id("anthropic.claude-3-haiku-20240307-v1:0"),
+            "anthropic.claude-3-haiku-20240307-v1:0"
+        );
+        assert_eq!(
+            bedrock_model_id("us.anthropic.claude-haiku-4-5-20251001-v1:0"),
+            "us.anthropic.claude-haiku-4-5-20251001-v1:0"
+        );
+    }
+
+    #[test]
+    fn test_bedrock_url_construction() {
+        assert_eq!(
+            bedrock_endpoint("us-west-2"),
+            "https://bedrock-runtime.us-west-2.amazonaws.com"
+        );
+        let path = invoke_path("anthropic.claude-3-haiku-20240307-v1:0");
+        assert_eq!(
+            path,
+            "/model/anthropic.claude-3-haiku-20240307-v1%3A0/invoke"
+        );
+        // The canonical URI double-encodes the already-encoded segment
+        assert_eq!(
+            uri_encode_path(&path),
+            "/model/anthropic.claude-3-haiku-20240307-v1%253A0/invoke"
+        );
+    }
+
+    #[test]
+    fn test_sigv4_matches_aws_test_vector() {
+        // AWS SigV4 test suite: get-vanilla
+        let headers = vec![
+            ("host".to_string(), "example.amazonaws.com".to_string()),
+            ("x-amz-date".to_string(), "20150830T123600Z".to_string()),
+        ];
+        let authorization = authorization_header(
+            &example_credentials(None),
+            "us-east-1",
+            "service",
+            "GET",
+            "/",
+            &headers,
+            b"",
+            "20150830T123600Z",
+        );
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn test_signed_headers_present() {
+        let headers = signed_headers(
+            &example_credentials(Some("session-token")),
+            "us-east-1",
+            "bedrock-runtime.us-east-1.amazonaws.com",
+            "/model/anthropic.claude-3-haiku-20240307-v1%3A0/invoke",
+            b"{}",
+            "20250101T000000Z",
+        );
+        let get = |name: &str| {
+            headers
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+
+        assert_eq!(get("x-amz-date"), Some("20250101T000000Z"));
+        assert_eq!(get("x-amz-security-token"), Some("session-token"));
+        let authorization = get("authorization").unwrap();
+        assert!(authorization.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20250101/us-east-1/bedrock/aws4_request"
+        ));
+        assert!(authorization
+            .contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_sends_signed_request() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock(
+                "POST",
+                "/model/anthropic.claude-3-haiku-20240307-v1%3A0/invoke",
+            )
+            .match_header(
+                "authorization",
+                mockito::Matcher::Regex("^AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/".into()),
+            )
+            .match_header("x-amz-date", mockito::Matcher::Any)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "anthropic_version": "bedrock-2023-05-31",
+                "max_tokens": 50,
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"content":[{"type":"text","text":"Done."}],"model":"claude-3-haiku","usage":{"input_tokens":12,"output_tokens":3}}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = BedrockProvider::with_endpoint(
+            Some(example_credentials(None)),
+            "us-east-1".to_string(),
+            "anthropic.claude-3-haiku-20240307-v1:0".to_string(),
+            server.url(),
+            Duration::from_secs(5),
+        );
+        let request = GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 50,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        let response = provider.generate(&request).await.unwrap();
+        assert_eq!(response.text, "Done.");
+        assert_eq!(response.input_tokens, 12);
+        mock.assert_async().await;
+    }
+
+    #[test]
+    fn test_unavailable_without_credentials() {
+        let provider = BedrockProvider::new(
+            None,
+            "us-east-1".to_string(),
+            "claude-3-5-haiku-20241022".to_string(),
+            Duration::from_secs(5),
+        );
+        assert!(!provider.is_available());
+    }
+}
@@ -35,7 +35,6 @@ pub struct CostTracker {
     usage_file: PathBuf,
 }
 
-#[allow(dead_code)]
 impl CostTracker {
     pub fn new(usage_file: impl AsRef<Path>) -> Self {
         let usage_file =
@@ -43,6 +42,12 @@ impl CostTracker {
         Self { usage_file }
     }
 
+    /// Usage file at ~/.cache/sumvox/usage.json
+    pub fn from_home() -> Option<Self> {
+        dirs::home_dir()
+            .map(|home| Self::new(home.join(".cache").join("sumvox").join("usage.json")))
+    }
+
     /// Load usage data from file
     async fn load_usage(&self) -> LlmResult<UsageData> {
         if !self.usage_file.exists() {
@@ -1,11 +1,12 @@
 // Gemini API provider implementation
 
+use super::http::HttpContext;
+use super::redact::{body_for_log, redact_secrets};
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use super::{GenerationRequest, GenerationResponse, LlmProvider};
+use super::{GenerationRequest, GenerationResponse, LlmCapabilities, LlmProvider};
 use crate::error::{LlmError, LlmResult};
 
 #[allow(dead_code)]
@@ -53,6 +54,9 @@ struct GenerationConfig {
     /// When None (disable_thinking=false), the field is omitted entirely.
     #[serde(skip_serializing_if = "Option::is_none", rename = "thinkingConfig")]
     thinking_config: Option<ThinkingConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "stopSequences")]
+    stop_sequences: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -85,11 +89,23 @@ struct UsageMetadata {
     candidates_token_count: u32,
 }
 
+#[derive(Debug, Deserialize)]
+struct ModelsResponse {
+    #[serde(default)]
+    models: Vec<ModelEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    name: String,
+}
+
 pub struct GeminiProvider {
     api_key: String,
     model: String,
     base_url: String,
     timeout: Duration,
+    http: HttpContext,
 }
 
 impl GeminiProvider {
@@ -109,15 +125,14 @@ impl GeminiProvider {
             model,
             base_url,
             timeout,
+            http: HttpContext::new(),
         }
     }
 
-    fn client(&self) -> Client {
-        Client::builder()
-            .no_proxy() // Disable system proxy detection to avoid CoreFoundation crash
-            .timeout(self.timeout)
-            .build()
-            .unwrap_or_else(|_| Client::new())
+    /// Send requests through a shared client instead of this provider's own
+    pub fn with_http(mut self, http: HttpContext) -> Self {
+        self.http = http;
+        self
     }
 
     fn extract_model_name(&self) -> &str {
@@ -130,6 +145,16 @@ impl GeminiProvider {
     }
 }
 
+/// What SumVox uses through this provider
+pub const CAPABILITIES: LlmCapabilities = LlmCapabilities {
+    system_message: true,
+    // thinkingBudget 0 when thinking is disabled
+    thinking: true,
+    stop_sequences: true,
+    streaming: false,
+    json_mode: false,
+};
+
 #[async_trait]
 impl LlmProvider for GeminiProvider {
     fn name(&self) -> &str {
@@ -179,19 +204,29 @@ impl LlmProvider for GeminiProvider {
                 temperature: request.temperature,
                 max_output_tokens: request.max_tokens,
                 thinking_config,
+                stop_sequences: request.stop.clone(),
             },
             system_instruction,
         };
 
-        tracing::debug!("Sending request to Gemini API: {}", model_name);
+        tracing::debug!(
+            "Sending request to Gemini API: {} ({})",
+            model_name,
+            redact_secrets(&url)
+        );
 
         let response = self
-            .client()
-            .post(&url)
+            .http
+            .post(&url, self.timeout)
             .json(&gemini_request)
             .send()
             .await
-            .map_err(|e| LlmError::Request(format!("Gemini API request failed: {}", e)))?;
+            .map_err(|e| {
+                LlmError::Request(format!(
+                    "Gemini API request failed: {}",
+                    redact_secrets(&e.to_string())
+                ))
+            })?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -199,10 +234,14 @@ impl LlmProvider for GeminiProvider {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(LlmError::Request(format!(
-                "Gemini API returned {}: {}",
-                status, error_text
-            )));
+            let message = format!("Gemini API returned {}: {}", status, error_text);
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Err(LlmError::RateLimited(message));
+            }
+            if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+                return Err(LlmError::ModelUnavailable(message));
+            }
+            return Err(LlmError::Request(message));
         }
 
         let response_text = response
@@ -210,6 +249,8 @@ impl LlmProvider for GeminiProvider {
             .await
             .map_err(|e| LlmError::Request(format!("Failed to read Gemini response: {}", e)))?;
 
+        tracing::debug!("Gemini API response: {}", body_for_log(&response_text));
+
         let gemini_response: GeminiResponse = serde_json::from_str(&response_text)
             .map_err(|e| LlmError::Request(format!("Failed to parse Gemini response: {}", e)))?;
 
@@ -242,6 +283,10 @@ impl LlmProvider for GeminiProvider {
         })
     }
 
+    fn capabilities(&self) -> LlmCapabilities {
+        CAPABILITIES
+    }
+
     fn estimate_cost(&self, input_tokens: u32, output_tokens: u32) -> f64 {
         // Gemini Flash 2.0 pricing (per 1K tokens)
         const INPUT_COST_PER_1K: f64 = 0.000075;
@@ -252,6 +297,51 @@ impl LlmProvider for GeminiProvider {
 
         input_cost + output_cost
     }
+
+    async fn list_models(&self) -> LlmResult<Vec<String>> {
+        if !self.is_available() {
+            return Err(LlmError::Unavailable(
+                "Gemini API key not configured".to_string(),
+            ));
+        }
+
+        let url = format!("{}/models?key={}", self.base_url, self.api_key);
+        let response = self
+            .http
+            .get(&url, self.timeout)
+            .send()
+            .await
+            .map_err(|e| {
+                LlmError::Request(format!(
+                    "Gemini models request failed: {}",
+                    redact_secrets(&e.to_string())
+                ))
+            })?;
+
+        if !response.status().is_success() {
+            return Err(LlmError::Request(format!(
+                "Gemini models endpoint returned {}",
+                response.status()
+            )));
+        }
+
+        let models: ModelsResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::Request(format!("Failed to parse Gemini models: {}", e)))?;
+
+        // Names come back as "models/gemini-2.5-flash"
+        Ok(models
+            .models
+            .into_iter()
+            .map(|m| {
+                m.name
+                    .strip_prefix("models/")
+                    .map(str::to_string)
+                    .unwrap_or(m.name)
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -341,6 +431,9 @@ mod tests {
             max_tokens: 100,
             temperature: 0.3,
             disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
         };
 
         let result = provider.generate(&request).await;
@@ -348,6 +441,101 @@ mod tests {
         assert!(matches!(result.unwrap_err(), LlmError::Unavailable(_)));
     }
 
+    #[tokio::test]
+    async fn test_request_error_masks_key() {
+        // Nothing listens on the discard port, so the request itself fails
+        let provider = GeminiProvider::with_base_url(
+            "secret".to_string(),
+            "gemini-2.5-flash".to_string(),
+            "http://127.0.0.1:9".to_string(),
+            Duration::from_secs(2),
+        );
+        let request = GenerationRequest {
+            system_message: None,
+            prompt: "Test".to_string(),
+            max_tokens: 10,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        let message = provider.generate(&request).await.unwrap_err().to_string();
+        assert!(!message.contains("secret"), "key leaked: {message}");
+        assert!(message.contains("?key=***"), "unexpected error: {message}");
+    }
+
+    #[tokio::test]
+    async fn test_generate_429_is_rate_limited() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/models/gemini-2.5-flash:generateContent")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "key".into(),
+                "exhausted".into(),
+            ))
+            .with_status(429)
+            .with_body(r#"{"error":{"status":"RESOURCE_EXHAUSTED"}}"#)
+            .create_async()
+            .await;
+
+        let provider = GeminiProvider::with_base_url(
+            "exhausted".to_string(),
+            "gemini-2.5-flash".to_string(),
+            server.url(),
+            Duration::from_secs(5),
+        );
+        let request = GenerationRequest {
+            system_message: None,
+            prompt: "Test".to_string(),
+            max_tokens: 100,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        let result = provider.generate(&request).await;
+        mock.assert_async().await;
+        assert!(matches!(result.unwrap_err(), LlmError::RateLimited(_)));
+    }
+
+    #[tokio::test]
+    async fn test_generate_sends_stop_sequences() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/models/gemini-2.5-flash:generateContent")
+            .match_query(mockito::Matcher::Any)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "generationConfig": {"stopSequences": ["\n\n", "END"]},
+            })))
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let provider = GeminiProvider::with_base_url(
+            "test-key".to_string(),
+            "gemini-2.5-flash".to_string(),
+            server.url(),
+            Duration::from_secs(5),
+        );
+        let request = GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 50,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: Some(vec!["\n\n".to_string(), "END".to_string()]),
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        let _ = provider.generate(&request).await;
+        mock.assert_async().await;
+    }
+
     // ── C3: GeminiRequestSerialization ──────────────────────────────────
 
     fn make_generation_config(disable_thinking: bool) -> GenerationConfig {
@@ -360,6 +548,7 @@ mod tests {
             temperature: 0.3,
             max_output_tokens: 100,
             thinking_config,
+            stop_sequences: None,
         }
     }
 
@@ -410,6 +599,9 @@ mod tests {
             max_tokens: 50,
             temperature: 0.3,
             disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
         };
 
         let response = provider.generate(&request).await.unwrap();
@@ -0,0 +1,70 @@
+// Shared HTTP client for LLM providers
+// One configured reqwest::Client is built per run and handed to every provider
+// the factory creates, so a fallback chain reuses connection pools and TLS
+// setup. Timeouts differ per provider, so they are set on each request.
+
+use reqwest::{Client, RequestBuilder};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Cheap-to-clone handle to a shared `reqwest::Client`
+#[derive(Clone)]
+pub struct HttpContext {
+    client: Arc<Client>,
+}
+
+impl HttpContext {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .no_proxy() // Disable system proxy detection to avoid CoreFoundation crash
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        Self {
+            client: Arc::new(client),
+        }
+    }
+
+    /// POST `url`, giving up after `timeout`
+    pub fn post(&self, url: &str, timeout: Duration) -> RequestBuilder {
+        self.client.post(url).timeout(timeout)
+    }
+
+    /// GET `url`, giving up after `timeout`
+    pub fn get(&self, url: &str, timeout: Duration) -> RequestBuilder {
+        self.client.get(url).timeout(timeout)
+    }
+
+    /// True when both handles use the same client instance
+    #[allow(dead_code)] // Used in tests
+    pub fn shares_client_with(&self, other: &HttpContext) -> bool {
+        Arc::ptr_eq(&self.client, &other.client)
+    }
+}
+
+impl Default for HttpContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_the_client() {
+        let http = HttpContext::new();
+        assert!(http.shares_client_with(&http.clone()));
+        assert!(!http.shares_client_with(&HttpContext::new()));
+    }
+
+    #[test]
+    fn test_request_carries_timeout() {
+        let http = HttpContext::new();
+        let request = http
+            .post("http://localhost/v1/messages", Duration::from_secs(7))
+            .build()
+            .unwrap();
+        assert_eq!(request.timeout(), Some(&Duration::from_secs(7)));
+    }
+}
@@ -0,0 +1,186 @@
+// API key rotation for providers configured with several keys
+// On HTTP 429 the request is retried with the next key; the last good key index
+// is persisted at ~/.cache/sumvox/key-index-{provider} so later runs start there.
+
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+use super::{GenerationRequest, GenerationResponse, LlmCapabilities, LlmProvider};
+use crate::error::{LlmError, LlmResult};
+
+pub struct KeyRotatingProvider {
+    /// One provider instance per API key, in config order
+    providers: Vec<Box<dyn LlmProvider>>,
+    /// Where the last good key index is persisted (None disables persistence)
+    index_path: Option<PathBuf>,
+}
+
+impl KeyRotatingProvider {
+    pub fn new(providers: Vec<Box<dyn LlmProvider>>, index_path: Option<PathBuf>) -> Self {
+        assert!(
+            !providers.is_empty(),
+            "KeyRotatingProvider needs at least one provider"
+        );
+        Self {
+            providers,
+            index_path,
+        }
+    }
+
+    /// Default index file for a provider: ~/.cache/sumvox/key-index-{provider}
+    pub fn index_path_for(provider: &str) -> Option<PathBuf> {
+        dirs::home_dir().map(|home| {
+            home.join(".cache")
+                .join("sumvox")
+                .join(format!("key-index-{}", provider.to_lowercase()))
+        })
+    }
+
+    /// Index of the key to try first (last good key, or 0)
+    fn start_index(&self) -> usize {
+        self.index_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|s| s.trim().parse::<usize>().ok())
+            .map(|i| i % self.providers.len())
+            .unwrap_or(0)
+    }
+
+    fn save_index(&self, index: usize) {
+        let Some(path) = &self.index_path else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = std::fs::write(path, index.to_string()) {
+            tracing::debug!("Failed to persist key index to {:?}: {}", path, e);
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for KeyRotatingProvider {
+    fn name(&self) -> &str {
+        self.providers[0].name()
+    }
+
+    fn is_available(&self) -> bool {
+        self.providers.iter().any(|p| p.is_available())
+    }
+
+    fn capabilities(&self) -> LlmCapabilities {
+        self.providers[0].capabilities()
+    }
+
+    async fn generate(&self, request: &GenerationRequest) -> LlmResult<GenerationResponse> {
+        let count = self.providers.len();
+        let start = self.start_index();
+
+        for offset in 0..count {
+            let index = (start + offset) % count;
+            match self.providers[index].generate(request).await {
+                Ok(response) => {
+                    if index != start {
+                        self.save_index(index);
+                    }
+                    return Ok(response);
+                }
+                Err(LlmError::RateLimited(e)) => {
+                    tracing::warn!(
+                        "{} key #{} rate limited, rotating to next key: {}",
+                        self.name(),
+                        index + 1,
+                        e
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(LlmError::RateLimited(format!(
+            "All {} {} API keys are rate limited",
+            count,
+            self.name()
+        )))
+    }
+
+    fn estimate_cost(&self, input_tokens: u32, output_tokens: u32) -> f64 {
+        self.providers[0].estimate_cost(input_tokens, output_tokens)
+    }
+
+    async fn list_models(&self) -> LlmResult<Vec<String>> {
+        self.providers[self.start_index()].list_models().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::mock::RecordingProvider;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn keyed(
+        key: &'static str,
+        rate_limited: bool,
+        calls: &Arc<AtomicUsize>,
+    ) -> Box<dyn LlmProvider> {
+        let provider = RecordingProvider::new()
+            .replying(format!("answered with {}", key))
+            .counting(calls);
+        if rate_limited {
+            Box::new(provider.rate_limited())
+        } else {
+            Box::new(provider)
+        }
+    }
+
+    fn request() -> GenerationRequest {
+        GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 10,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotates_to_next_key_on_429() {
+        let dir = TempDir::new().unwrap();
+        let index_path = dir.path().join("key-index-mock");
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let provider = KeyRotatingProvider::new(
+            vec![keyed("key-a", true, &calls), keyed("key-b", false, &calls)],
+            Some(index_path.clone()),
+        );
+
+        let response = provider.generate(&request()).await.unwrap();
+        assert_eq!(response.text, "answered with key-b");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Last good index is persisted, so the next call starts at key-b
+        assert_eq!(std::fs::read_to_string(&index_path).unwrap(), "1");
+        provider.generate(&request()).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_all_keys_rate_limited() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = KeyRotatingProvider::new(
+            vec![keyed("key-a", true, &calls), keyed("key-b", true, &calls)],
+            None,
+        );
+
+        let err = provider.generate(&request()).await.unwrap_err();
+        assert!(matches!(err, LlmError::RateLimited(_)));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
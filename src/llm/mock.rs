@@ -0,0 +1,145 @@
+// Recording LLM provider shared by the llm tests
+// Stands in for a real API: it counts calls, replies with fixed text and
+// usage, prices tokens at a flat rate, and can be made slow, unavailable,
+// failing or rate limited.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::{GenerationRequest, GenerationResponse, LlmProvider};
+use crate::error::{LlmError, LlmResult};
+
+/// Model id every mock reply reports
+pub const MOCK_MODEL: &str = "mock-model";
+
+pub struct RecordingProvider {
+    name: &'static str,
+    reply: String,
+    usage: (u32, u32),
+    available: bool,
+    delay: Duration,
+    failure: Option<&'static str>,
+    rate_limited: bool,
+    price_per_token: f64,
+    calls: Arc<AtomicUsize>,
+}
+
+impl Default for RecordingProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecordingProvider {
+    /// An available, free "mock" provider that answers "Done" right away
+    pub fn new() -> Self {
+        Self {
+            name: "mock",
+            reply: "Done".to_string(),
+            usage: (1, 1),
+            available: true,
+            delay: Duration::ZERO,
+            failure: None,
+            rate_limited: false,
+            price_per_token: 0.0,
+            calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn named(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn replying(mut self, text: impl Into<String>) -> Self {
+        self.reply = text.into();
+        self
+    }
+
+    /// Input and output token counts of every reply
+    pub fn usage(mut self, input_tokens: u32, output_tokens: u32) -> Self {
+        self.usage = (input_tokens, output_tokens);
+        self
+    }
+
+    pub fn unavailable(mut self) -> Self {
+        self.available = false;
+        self
+    }
+
+    /// Every call takes `delay` first
+    pub fn slow(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Every call fails with a request error carrying `message`
+    pub fn failing(mut self, message: &'static str) -> Self {
+        self.failure = Some(message);
+        self
+    }
+
+    /// Every call fails with HTTP 429
+    pub fn rate_limited(mut self) -> Self {
+        self.rate_limited = true;
+        self
+    }
+
+    /// Cost of each input or output token, in USD
+    pub fn priced(mut self, per_token: f64) -> Self {
+        self.price_per_token = per_token;
+        self
+    }
+
+    /// Count calls in `calls`, shared with other mocks
+    pub fn counting(mut self, calls: &Arc<AtomicUsize>) -> Self {
+        self.calls = Arc::clone(calls);
+        self
+    }
+
+    /// `generate` and `list_models` calls so far
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for RecordingProvider {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn is_available(&self) -> bool {
+        self.available
+    }
+
+    async fn generate(&self, _request: &GenerationRequest) -> LlmResult<GenerationResponse> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        if self.rate_limited {
+            return Err(LlmError::RateLimited("429 Too Many Requests".to_string()));
+        }
+        if let Some(message) = self.failure {
+            return Err(LlmError::Request(message.to_string()));
+        }
+        Ok(GenerationResponse {
+            text: self.reply.clone(),
+            input_tokens: self.usage.0,
+            output_tokens: self.usage.1,
+            model: MOCK_MODEL.to_string(),
+        })
+    }
+
+    fn estimate_cost(&self, input_tokens: u32, output_tokens: u32) -> f64 {
+        (input_tokens + output_tokens) as f64 * self.price_per_token
+    }
+
+    /// A new list each call ("model-0", "model-1", ...), so caching shows
+    async fn list_models(&self) -> LlmResult<Vec<String>> {
+        let n = self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![format!("model-{}", n)])
+    }
+}
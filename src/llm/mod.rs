@@ -3,17 +3,38 @@
 use async_trait::async_trait;
 
 pub use anthropic::AnthropicProvider;
+pub use bedrock::BedrockProvider;
 pub use gemini::GeminiProvider;
 pub use ollama::OllamaProvider;
 pub use openai::OpenAIProvider;
 
 pub mod anthropic;
+pub mod bedrock;
 pub mod cost_tracker;
 pub mod gemini;
+pub mod http;
+pub mod key_rotation;
+#[cfg(test)]
+pub(crate) mod mock;
+pub mod model_fallback;
+pub mod models_cache;
 pub mod ollama;
 pub mod openai;
+pub mod redact;
+
+use std::collections::HashMap;
+
+use std::time::Duration;
+
+use crate::config::{ModelPricing, OllamaSystemMode};
+use crate::error::{LlmError, LlmResult};
+use crate::retry::{retry_async, RetryPolicy};
 
-use crate::error::LlmResult;
+/// Retries of one provider on rate limits and overload before falling back
+/// to the next. Kept short: the hook has a time budget.
+pub const RETRY_POLICY: RetryPolicy = RetryPolicy::new(2, Duration::from_millis(500))
+    .with_max_delay(Duration::from_secs(2))
+    .with_jitter(0.25);
 
 #[derive(Debug, Clone)]
 pub struct GenerationRequest {
@@ -22,14 +43,47 @@ pub struct GenerationRequest {
     pub max_tokens: u32,
     pub temperature: f32,
     pub disable_thinking: bool,
+    /// Sequences that end generation early (provider stop parameter)
+    pub stop: Option<Vec<String>>,
+    /// Reasoning effort for OpenAI reasoning models, overriding the one
+    /// derived from `disable_thinking`
+    pub reasoning_effort: Option<String>,
+    /// How Ollama receives `system_message` (`llm.ollama_system_mode`)
+    pub ollama_system_mode: OllamaSystemMode,
 }
 
+/// Features SumVox uses through an LLM provider (`sumvox providers info`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LlmCapabilities {
+    /// `system_message` is sent as a separate system prompt
+    pub system_message: bool,
+    /// `disable_thinking` / `reasoning_effort` change the request
+    pub thinking: bool,
+    /// `llm.parameters.stop` is passed on
+    pub stop_sequences: bool,
+    /// The reply is streamed as it is generated
+    pub streaming: bool,
+    /// Structured JSON output is requested
+    pub json_mode: bool,
+}
+
+/// Capabilities of each LLM provider, by config name
+pub const PROVIDER_CAPABILITIES: &[(&str, LlmCapabilities)] = &[
+    ("google", gemini::CAPABILITIES),
+    ("anthropic", anthropic::CAPABILITIES),
+    ("anthropic (bedrock)", bedrock::CAPABILITIES),
+    ("openai", openai::CAPABILITIES),
+    // xAI speaks the OpenAI API through the same provider
+    ("xai", openai::CAPABILITIES),
+    ("ollama", ollama::CAPABILITIES),
+];
+
 #[derive(Debug, Clone)]
 pub struct GenerationResponse {
     pub text: String,
     pub input_tokens: u32,
     pub output_tokens: u32,
-    #[allow(dead_code)]
+    /// Model that produced the text
     pub model: String,
 }
 
@@ -47,6 +101,146 @@ pub trait LlmProvider: Send + Sync {
     /// Estimate cost for a request
     #[allow(dead_code)]
     fn estimate_cost(&self, input_tokens: u32, output_tokens: u32) -> f64;
+
+    /// Estimate cost for a request served by `model`. Providers that try
+    /// several models price the one given instead of their first.
+    fn estimate_cost_for(&self, _model: &str, input_tokens: u32, output_tokens: u32) -> f64 {
+        self.estimate_cost(input_tokens, output_tokens)
+    }
+
+    /// Features SumVox uses through this provider
+    #[allow(dead_code)] // `providers info` reads the static PROVIDER_CAPABILITIES
+    fn capabilities(&self) -> LlmCapabilities {
+        LlmCapabilities::default()
+    }
+
+    /// List model ids offered by the provider's models endpoint
+    async fn list_models(&self) -> LlmResult<Vec<String>> {
+        Err(LlmError::Unavailable(format!(
+            "{} does not support listing models",
+            self.name()
+        )))
+    }
+}
+
+/// `provider.generate`, retried per `RETRY_POLICY` on transient errors
+pub async fn generate_with_retry(
+    provider: &dyn LlmProvider,
+    request: &GenerationRequest,
+) -> LlmResult<GenerationResponse> {
+    retry_async(
+        || provider.generate(request),
+        &RETRY_POLICY,
+        LlmError::is_transient,
+    )
+    .await
+}
+
+/// Whether `tracker`'s spend today is under `cost.daily_limit_usd`. Always
+/// true without a limit; an unreadable usage file doesn't block calls.
+pub async fn within_daily_budget(
+    config: &crate::config::SumvoxConfig,
+    tracker: &cost_tracker::CostTracker,
+) -> bool {
+    let Some(limit) = config.cost.daily_limit_usd else {
+        return true;
+    };
+    tracker.check_budget(limit).await.unwrap_or_else(|e| {
+        tracing::warn!(
+            "Could not read LLM usage, not enforcing the daily limit: {}",
+            e
+        );
+        true
+    })
+}
+
+/// `generate_with_retry`, refused once today's spend has reached
+/// `cost.daily_limit_usd`. With a limit set, each call's cost is added to
+/// today's spend in ~/.cache/sumvox/usage.json.
+pub async fn generate_within_budget(
+    config: &crate::config::SumvoxConfig,
+    provider: &dyn LlmProvider,
+    request: &GenerationRequest,
+) -> LlmResult<GenerationResponse> {
+    let (Some(limit), Some(tracker)) = (
+        config.cost.daily_limit_usd,
+        cost_tracker::CostTracker::from_home(),
+    ) else {
+        return generate_with_retry(provider, request).await;
+    };
+    if !within_daily_budget(config, &tracker).await {
+        return Err(LlmError::BudgetExceeded(format!(
+            "cost.daily_limit_usd ${:.2} reached",
+            limit
+        )));
+    }
+
+    let response = generate_with_retry(provider, request).await?;
+    // Priced as the model that answered, which a model fallback may have changed
+    let model = response.model.as_str();
+    let cost = price_for(
+        &config.pricing,
+        provider,
+        model,
+        response.input_tokens,
+        response.output_tokens,
+    );
+    if let Err(e) = tracker
+        .record_usage(model, response.input_tokens, response.output_tokens, cost)
+        .await
+    {
+        tracing::warn!("Could not record LLM usage: {}", e);
+    }
+    Ok(response)
+}
+
+/// Cost in USD for a call: the configured `[pricing]` entry for `model` when
+/// there is one, otherwise the provider's built-in rates
+pub fn price_for(
+    pricing: &HashMap<String, ModelPricing>,
+    provider: &dyn LlmProvider,
+    model: &str,
+    input_tokens: u32,
+    output_tokens: u32,
+) -> f64 {
+    match pricing.get(model) {
+        Some(price) => price.cost(input_tokens, output_tokens),
+        None => provider.estimate_cost_for(model, input_tokens, output_tokens),
+    }
+}
+
+/// Rough token count for pre-flight estimates (about 4 characters per token)
+pub fn estimate_tokens(text: &str) -> u32 {
+    text.chars().count().div_ceil(4) as u32
+}
+
+/// Worst-case cost in USD of sending `request` before it is sent: the
+/// estimated prompt tokens plus a reply of the full `max_tokens`
+pub fn preflight_cost(
+    pricing: &HashMap<String, ModelPricing>,
+    provider: &dyn LlmProvider,
+    model: &str,
+    request: &GenerationRequest,
+) -> f64 {
+    let input_tokens = estimate_tokens(&request.prompt)
+        + request
+            .system_message
+            .as_deref()
+            .map(estimate_tokens)
+            .unwrap_or(0);
+    price_for(pricing, provider, model, input_tokens, request.max_tokens)
+}
+
+/// The pre-flight estimate when it is over `cost.max_per_call_usd`, so the
+/// call should not be made
+pub fn over_call_cap(
+    config: &crate::config::SumvoxConfig,
+    provider: &dyn LlmProvider,
+    model: &str,
+    request: &GenerationRequest,
+) -> Option<f64> {
+    let estimate = preflight_cost(&config.pricing, provider, model, request);
+    config.cost.exceeds_call_cap(estimate).then_some(estimate)
 }
 
 #[cfg(test)]
@@ -61,6 +255,9 @@ mod tests {
             max_tokens: 100,
             temperature: 0.3,
             disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
         };
 
         assert_eq!(
@@ -86,4 +283,119 @@ mod tests {
         assert_eq!(response.output_tokens, 20);
         assert_eq!(response.model, "test-model");
     }
+
+    #[test]
+    fn test_configured_pricing_overrides_builtin() {
+        let provider = GeminiProvider::new(
+            "key".to_string(),
+            "gemini-2.5-flash".to_string(),
+            std::time::Duration::from_secs(10),
+        );
+        let mut pricing = HashMap::new();
+        pricing.insert(
+            "gemini-2.5-flash".to_string(),
+            ModelPricing {
+                input_per_1k: 0.0003,
+                output_per_1k: 0.0025,
+            },
+        );
+
+        let cost = price_for(&pricing, &provider, "gemini-2.5-flash", 2000, 1000);
+        assert!((cost - (0.0006 + 0.0025)).abs() < 1e-12);
+
+        // Unlisted model: built-in constants
+        let builtin = price_for(&pricing, &provider, "gemini-2.0-flash", 2000, 1000);
+        assert_eq!(builtin, provider.estimate_cost(2000, 1000));
+    }
+
+    #[test]
+    fn test_preflight_cost_against_call_cap() {
+        let provider = GeminiProvider::new(
+            "key".to_string(),
+            "gemini-2.5-flash".to_string(),
+            std::time::Duration::from_secs(10),
+        );
+        let mut pricing = HashMap::new();
+        pricing.insert(
+            "gemini-2.5-flash".to_string(),
+            ModelPricing {
+                input_per_1k: 0.01,
+                output_per_1k: 0.01,
+            },
+        );
+        let cost = crate::config::CostConfig {
+            max_per_call_usd: Some(0.05),
+            ..Default::default()
+        };
+        let request = |prompt: String| GenerationRequest {
+            system_message: Some("Summarize briefly.".to_string()),
+            prompt,
+            max_tokens: 100,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        // ~250 prompt tokens + 100 reply tokens: well under the cap
+        let normal = preflight_cost(
+            &pricing,
+            &provider,
+            "gemini-2.5-flash",
+            &request("x".repeat(1000)),
+        );
+        assert!(!cost.exceeds_call_cap(normal));
+
+        // ~25K prompt tokens: blocked
+        let oversized = preflight_cost(
+            &pricing,
+            &provider,
+            "gemini-2.5-flash",
+            &request("x".repeat(100_000)),
+        );
+        assert!(oversized > 0.25);
+        assert!(cost.exceeds_call_cap(oversized));
+
+        // No cap configured: never blocked
+        assert!(!crate::config::CostConfig::default().exceeds_call_cap(oversized));
+    }
+
+    fn capabilities_of(name: &str) -> LlmCapabilities {
+        PROVIDER_CAPABILITIES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, c)| *c)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_capability_matrix_reflects_providers() {
+        // Every provider takes a system message and stop sequences
+        assert!(PROVIDER_CAPABILITIES
+            .iter()
+            .all(|(_, c)| c.system_message && c.stop_sequences));
+        assert!(capabilities_of("google").thinking);
+        assert!(capabilities_of("openai").thinking);
+        assert!(capabilities_of("ollama").thinking);
+        assert!(!capabilities_of("anthropic").thinking);
+        assert!(!capabilities_of("anthropic (bedrock)").thinking);
+        // Replies are never streamed
+        assert!(PROVIDER_CAPABILITIES.iter().all(|(_, c)| !c.streaming));
+    }
+
+    #[test]
+    fn test_provider_capabilities_match_matrix() {
+        let timeout = std::time::Duration::from_secs(5);
+        let gemini = GeminiProvider::new("k".into(), "gemini-2.5-flash".into(), timeout);
+        assert_eq!(gemini.capabilities(), capabilities_of("google"));
+
+        let anthropic = AnthropicProvider::new("k".into(), "claude-haiku".into(), timeout);
+        // Wrappers report their inner provider's capabilities
+        let fallback = model_fallback::ModelFallbackProvider::new(vec![(
+            "claude-haiku".to_string(),
+            Box::new(anthropic) as Box<dyn LlmProvider>,
+        )]);
+        assert_eq!(fallback.capabilities(), capabilities_of("anthropic"));
+    }
 }
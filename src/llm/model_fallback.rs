@@ -0,0 +1,245 @@
+// Model fallback within a single provider
+// When a model is overloaded (HTTP 503) or missing (e.g. not pulled into
+// Ollama) the same request is retried with the next configured model before
+// the provider chain moves to another provider.
+
+use async_trait::async_trait;
+
+use super::{GenerationRequest, GenerationResponse, LlmCapabilities, LlmProvider};
+use crate::error::{LlmError, LlmResult};
+
+pub struct ModelFallbackProvider {
+    /// (model id, provider bound to that model) in the order they are tried
+    models: Vec<(String, Box<dyn LlmProvider>)>,
+}
+
+impl ModelFallbackProvider {
+    pub fn new(models: Vec<(String, Box<dyn LlmProvider>)>) -> Self {
+        assert!(
+            !models.is_empty(),
+            "ModelFallbackProvider needs at least one model"
+        );
+        Self { models }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for ModelFallbackProvider {
+    fn name(&self) -> &str {
+        self.models[0].1.name()
+    }
+
+    fn is_available(&self) -> bool {
+        self.models[0].1.is_available()
+    }
+
+    fn capabilities(&self) -> LlmCapabilities {
+        self.models[0].1.capabilities()
+    }
+
+    async fn generate(&self, request: &GenerationRequest) -> LlmResult<GenerationResponse> {
+        let mut last_error = None;
+
+        for (model, provider) in &self.models {
+            match provider.generate(request).await {
+                Ok(response) => return Ok(response),
+                Err(LlmError::ModelUnavailable(e)) => {
+                    tracing::warn!(
+                        "{} model {} unavailable, trying next model: {}",
+                        self.name(),
+                        model,
+                        e
+                    );
+                    last_error = Some(LlmError::ModelUnavailable(e));
+                }
+                Err(LlmError::ModelNotFound(e)) => {
+                    tracing::warn!(
+                        "{} model {} not found, trying next model: {}",
+                        self.name(),
+                        model,
+                        e
+                    );
+                    last_error = Some(LlmError::ModelNotFound(e));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            LlmError::ModelUnavailable(format!("No {} model available", self.name()))
+        }))
+    }
+
+    fn estimate_cost(&self, input_tokens: u32, output_tokens: u32) -> f64 {
+        self.models[0].1.estimate_cost(input_tokens, output_tokens)
+    }
+
+    fn estimate_cost_for(&self, model: &str, input_tokens: u32, output_tokens: u32) -> f64 {
+        self.models
+            .iter()
+            .find(|(id, _)| id == model)
+            .map_or(&self.models[0].1, |(_, provider)| provider)
+            .estimate_cost(input_tokens, output_tokens)
+    }
+
+    async fn list_models(&self) -> LlmResult<Vec<String>> {
+        self.models[0].1.list_models().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{LlmAuth, LlmProviderConfig};
+    use crate::llm::mock::RecordingProvider;
+    use crate::provider_factory::ProviderFactory;
+
+    fn request() -> GenerationRequest {
+        GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 10,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_503_on_first_model_retries_second() {
+        let mut server = mockito::Server::new_async().await;
+        let overloaded = server
+            .mock("POST", "/models/gemini-2.5-flash:generateContent")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .with_body(r#"{"error":{"status":"UNAVAILABLE","message":"The model is overloaded."}}"#)
+            .create_async()
+            .await;
+        let sibling = server
+            .mock("POST", "/models/gemini-2.0-flash:generateContent")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(
+                r#"{"candidates":[{"content":{"parts":[{"text":"Done via sibling"}]}}],
+                    "usageMetadata":{"promptTokenCount":3,"candidatesTokenCount":2}}"#,
+            )
+            .create_async()
+            .await;
+
+        let config = LlmProviderConfig {
+            name: "google".to_string(),
+            model: "gemini-2.5-flash".to_string(),
+            api_key: Some("test-key".into()),
+            base_url: Some(server.url()),
+            timeout: 5,
+            disable_thinking: None,
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: vec!["gemini-2.0-flash".to_string()],
+        };
+        let provider = ProviderFactory::create_single(&config).unwrap();
+
+        let response = provider.generate(&request()).await.unwrap();
+        assert_eq!(response.text, "Done via sibling");
+        overloaded.assert_async().await;
+        sibling.assert_async().await;
+
+        assert_eq!(response.model, "gemini-2.0-flash");
+    }
+
+    #[test]
+    fn test_cost_is_for_the_model_that_answered() {
+        let provider = ModelFallbackProvider::new(vec![
+            (
+                "pro".to_string(),
+                Box::new(RecordingProvider::new().named("pro").priced(0.01)),
+            ),
+            (
+                "flash".to_string(),
+                Box::new(RecordingProvider::new().named("flash").priced(0.001)),
+            ),
+        ]);
+        assert_eq!(provider.estimate_cost_for("flash", 500, 500), 1.0);
+        assert_eq!(provider.estimate_cost_for("pro", 500, 500), 10.0);
+        // Unknown models fall back to the first model's rates
+        assert_eq!(provider.estimate_cost_for("other", 500, 500), 10.0);
+    }
+
+    #[tokio::test]
+    async fn test_non_model_error_does_not_fall_through() {
+        let mut server = mockito::Server::new_async().await;
+        let bad_request = server
+            .mock("POST", "/models/gemini-2.5-flash:generateContent")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .create_async()
+            .await;
+        let sibling = server
+            .mock("POST", "/models/gemini-2.0-flash:generateContent")
+            .match_query(mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let config = LlmProviderConfig {
+            name: "google".to_string(),
+            model: "gemini-2.5-flash".to_string(),
+            api_key: Some("test-key".into()),
+            base_url: Some(server.url()),
+            timeout: 5,
+            disable_thinking: None,
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: vec!["gemini-2.0-flash".to_string()],
+        };
+        let provider = ProviderFactory::create_single(&config).unwrap();
+
+        let err = provider.generate(&request()).await.unwrap_err();
+        assert!(matches!(err, LlmError::Request(_)));
+        bad_request.assert_async().await;
+        sibling.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_unpulled_ollama_model_falls_through_to_next_model() {
+        let mut server = mockito::Server::new_async().await;
+        let missing = server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"model": "llama3.3"}),
+            ))
+            .with_status(404)
+            .with_body(r#"{"error":"model \"llama3.3\" not found, try pulling it first"}"#)
+            .create_async()
+            .await;
+        let pulled = server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"model": "llama3.2"}),
+            ))
+            .with_status(200)
+            .with_body(r#"{"model":"llama3.2","response":"Done locally","done":true}"#)
+            .create_async()
+            .await;
+
+        let config = LlmProviderConfig {
+            name: "ollama".to_string(),
+            model: "llama3.3".to_string(),
+            api_key: None,
+            base_url: Some(server.url()),
+            timeout: 5,
+            disable_thinking: None,
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: vec!["llama3.2".to_string()],
+        };
+        let provider = ProviderFactory::create_single(&config).unwrap();
+
+        let response = provider.generate(&request()).await.unwrap();
+        assert_eq!(response.text, "Done locally");
+        missing.assert_async().await;
+        pulled.assert_async().await;
+    }
+}
@@ -0,0 +1,295 @@
+// On-disk cache for provider model lists
+// Stored at ~/.cache/sumvox/models-{provider}.json with a TTL
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::LlmProvider;
+use crate::error::{LlmError, LlmResult};
+
+/// Default lifetime of a cached model list (24 hours)
+pub const DEFAULT_MODELS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedModels {
+    fetched_at: DateTime<Utc>,
+    models: Vec<String>,
+}
+
+pub struct ModelsCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl ModelsCache {
+    pub fn new(dir: impl AsRef<Path>, ttl: Duration) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            ttl,
+        }
+    }
+
+    /// Cache rooted at ~/.cache/sumvox with the default TTL
+    pub fn from_home() -> LlmResult<Self> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| LlmError::Request("Cannot find home directory".to_string()))?;
+        Ok(Self::new(
+            home.join(".cache").join("sumvox"),
+            DEFAULT_MODELS_TTL,
+        ))
+    }
+
+    /// Path of the cache file for a provider
+    pub fn path_for(&self, provider: &str) -> PathBuf {
+        self.dir
+            .join(format!("models-{}.json", provider.to_lowercase()))
+    }
+
+    /// Return the cached list if present and younger than the TTL
+    pub fn load(&self, provider: &str) -> Option<Vec<String>> {
+        let content = std::fs::read_to_string(self.path_for(provider)).ok()?;
+        let cached: CachedModels = serde_json::from_str(&content).ok()?;
+
+        let age = Utc::now()
+            .signed_duration_since(cached.fetched_at)
+            .to_std()
+            .unwrap_or_default();
+        if age < self.ttl {
+            Some(cached.models)
+        } else {
+            None
+        }
+    }
+
+    /// Write a freshly fetched list to the cache
+    pub fn store(&self, provider: &str, models: &[String]) -> LlmResult<()> {
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| LlmError::Request(format!("Failed to create cache directory: {}", e)))?;
+
+        let cached = CachedModels {
+            fetched_at: Utc::now(),
+            models: models.to_vec(),
+        };
+        let json = serde_json::to_string_pretty(&cached)
+            .map_err(|e| LlmError::Request(format!("Failed to serialize models cache: {}", e)))?;
+
+        std::fs::write(self.path_for(provider), json)
+            .map_err(|e| LlmError::Request(format!("Failed to write models cache: {}", e)))
+    }
+
+    /// Serve from cache when fresh, otherwise fetch from the provider and store.
+    /// `refresh` skips the cache lookup entirely.
+    pub async fn get_or_fetch(
+        &self,
+        cache_key: &str,
+        provider: &dyn LlmProvider,
+        refresh: bool,
+    ) -> LlmResult<Vec<String>> {
+        if !refresh {
+            if let Some(models) = self.load(cache_key) {
+                tracing::debug!("Models cache hit for {}", cache_key);
+                return Ok(models);
+            }
+        }
+
+        let models = provider.list_models().await?;
+        if let Err(e) = self.store(cache_key, &models) {
+            tracing::warn!("Failed to cache models for {}: {}", cache_key, e);
+        }
+        Ok(models)
+    }
+
+    /// Newest model whose id starts with `prefix`, from the (cached) model list
+    pub async fn resolve_latest(
+        &self,
+        cache_key: &str,
+        provider: &dyn LlmProvider,
+        prefix: &str,
+    ) -> LlmResult<String> {
+        let models = self.get_or_fetch(cache_key, provider, false).await?;
+        pick_latest(prefix, &models).ok_or_else(|| {
+            LlmError::ModelNotFound(format!("No {} model matches {}-*", cache_key, prefix))
+        })
+    }
+}
+
+/// The prefix of a `<prefix>-latest` model alias, e.g. "claude-haiku" for
+/// "claude-haiku-latest"
+pub fn latest_alias_prefix(model: &str) -> Option<&str> {
+    model
+        .strip_suffix("-latest")
+        .filter(|prefix| !prefix.is_empty())
+}
+
+/// Pick the newest snapshot among `models` starting with `prefix` (followed by
+/// `-` or nothing). Newest means the latest date stamp (`20251001` or
+/// `2024-07-18`); undated ids count as oldest, and ties go to the higher version.
+pub fn pick_latest(prefix: &str, models: &[String]) -> Option<String> {
+    models
+        .iter()
+        .filter(|m| {
+            m.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+        })
+        .max_by_key(|m| snapshot_key(m))
+        .cloned()
+}
+
+/// (date as YYYYMMDD, numeric version parts) for ordering snapshots
+fn snapshot_key(model: &str) -> (u32, Vec<u32>) {
+    let parts: Vec<&str> = model.split(['-', '.', ':', '@']).collect();
+    let mut date = 0;
+    let mut version = Vec::new();
+    let mut i = 0;
+    while i < parts.len() {
+        let part = parts[i];
+        if part.len() == 8 && part.chars().all(|c| c.is_ascii_digit()) {
+            date = part.parse().unwrap_or(0);
+        } else if part.len() == 4
+            && part.chars().all(|c| c.is_ascii_digit())
+            && parts.get(i + 2).is_some_and(|p| p.len() == 2)
+        {
+            // YYYY-MM-DD
+            let joined = format!("{}{}{}", part, parts[i + 1], parts[i + 2]);
+            if let Ok(d) = joined.parse() {
+                date = d;
+                i += 3;
+                continue;
+            }
+        } else if let Ok(n) = part.parse() {
+            version.push(n);
+        }
+        i += 1;
+    }
+    (date, version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::mock::RecordingProvider;
+    use tempfile::TempDir;
+
+    fn provider() -> RecordingProvider {
+        RecordingProvider::new()
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_within_ttl() {
+        let dir = TempDir::new().unwrap();
+        let cache = ModelsCache::new(dir.path(), DEFAULT_MODELS_TTL);
+        let mock = provider();
+
+        let first = cache.get_or_fetch("google", &mock, false).await.unwrap();
+        let second = cache.get_or_fetch("google", &mock, false).await.unwrap();
+
+        assert_eq!(first, vec!["model-0"]);
+        assert_eq!(second, first);
+        assert_eq!(mock.calls(), 1);
+        assert!(dir.path().join("models-google.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_bypasses_cache() {
+        let dir = TempDir::new().unwrap();
+        let cache = ModelsCache::new(dir.path(), DEFAULT_MODELS_TTL);
+        let mock = provider();
+
+        cache.get_or_fetch("openai", &mock, false).await.unwrap();
+        let refreshed = cache.get_or_fetch("openai", &mock, true).await.unwrap();
+
+        assert_eq!(refreshed, vec!["model-1"]);
+        assert_eq!(mock.calls(), 2);
+        // The refreshed list replaces the cached one
+        assert_eq!(cache.load("openai"), Some(vec!["model-1".to_string()]));
+    }
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_latest_alias_prefix() {
+        assert_eq!(
+            latest_alias_prefix("claude-haiku-latest"),
+            Some("claude-haiku")
+        );
+        assert_eq!(latest_alias_prefix("claude-haiku-4-5-20251001"), None);
+        assert_eq!(latest_alias_prefix("-latest"), None);
+    }
+
+    #[test]
+    fn test_pick_latest_prefers_newest_date() {
+        let models = ids(&[
+            "claude-haiku-4-5-20251001",
+            "claude-3-5-haiku-20241022",
+            "claude-haiku-4-20250514",
+            "claude-haiku-4-5",
+            "claude-sonnet-4-5-20250929",
+        ]);
+        assert_eq!(
+            pick_latest("claude-haiku", &models).as_deref(),
+            Some("claude-haiku-4-5-20251001")
+        );
+        assert_eq!(pick_latest("claude-opus", &models), None);
+    }
+
+    #[test]
+    fn test_pick_latest_dashed_dates_and_versions() {
+        let models = ids(&[
+            "gpt-4o-mini-2024-07-18",
+            "gpt-4o-mini",
+            "gpt-4o-mini-tts",
+            "gpt-4o-2024-11-20",
+        ]);
+        assert_eq!(
+            pick_latest("gpt-4o-mini", &models).as_deref(),
+            Some("gpt-4o-mini-2024-07-18")
+        );
+        // Undated: the higher version wins
+        let models = ids(&["gemini-2.0-flash", "gemini-2.5-flash", "gemini-1.5-flash"]);
+        assert_eq!(
+            pick_latest("gemini", &models).as_deref(),
+            Some("gemini-2.5-flash")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_latest_uses_cached_list() {
+        let dir = TempDir::new().unwrap();
+        let cache = ModelsCache::new(dir.path(), DEFAULT_MODELS_TTL);
+        cache
+            .store(
+                "anthropic",
+                &ids(&["claude-haiku-4-20250514", "claude-haiku-4-5-20251001"]),
+            )
+            .unwrap();
+        let mock = provider();
+
+        let resolved = cache
+            .resolve_latest("anthropic", &mock, "claude-haiku")
+            .await
+            .unwrap();
+        assert_eq!(resolved, "claude-haiku-4-5-20251001");
+        assert_eq!(mock.calls(), 0);
+
+        let missing = cache
+            .resolve_latest("anthropic", &mock, "claude-opus")
+            .await;
+        assert!(matches!(missing, Err(LlmError::ModelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_expired_cache_refetches() {
+        let dir = TempDir::new().unwrap();
+        let cache = ModelsCache::new(dir.path(), Duration::ZERO);
+        let mock = provider();
+
+        cache.get_or_fetch("ollama", &mock, false).await.unwrap();
+        cache.get_or_fetch("ollama", &mock, false).await.unwrap();
+
+        assert_eq!(mock.calls(), 2);
+    }
+}
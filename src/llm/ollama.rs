@@ -1,11 +1,12 @@
 // Ollama local LLM provider implementation
 
+use super::http::HttpContext;
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use super::{GenerationRequest, GenerationResponse, LlmProvider};
+use super::{GenerationRequest, GenerationResponse, LlmCapabilities, LlmProvider};
+use crate::config::OllamaSystemMode;
 use crate::error::{LlmError, LlmResult};
 
 #[derive(Debug, Serialize)]
@@ -27,6 +28,8 @@ struct OllamaRequest {
 struct OllamaOptions {
     temperature: f32,
     num_predict: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -42,10 +45,57 @@ struct OllamaResponse {
     eval_count: u32,
 }
 
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<TagEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagEntry {
+    name: String,
+}
+
+impl OllamaRequest {
+    fn new(model_name: &str, request: &GenerationRequest) -> Self {
+        // Older Ollama versions ignore `system`; those need it in the prompt
+        let (prompt, system) = match (&request.system_message, request.ollama_system_mode) {
+            (Some(system), OllamaSystemMode::Prompt) => {
+                (format!("System: {}\n\n{}", system, request.prompt), None)
+            }
+            (system, _) => (request.prompt.clone(), system.clone()),
+        };
+        Self {
+            model: model_name.to_string(),
+            prompt,
+            stream: false,
+            options: OllamaOptions {
+                temperature: request.temperature,
+                num_predict: request.max_tokens,
+                stop: request.stop.clone(),
+            },
+            system,
+            think: if request.disable_thinking {
+                Some(false)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// Ollama's answer for a model that hasn't been pulled:
+/// HTTP 404 with `{"error":"model \"llama3.2\" not found, try pulling it first"}`
+fn is_model_not_found(status: reqwest::StatusCode, body: &str) -> bool {
+    let body = body.to_lowercase();
+    status == reqwest::StatusCode::NOT_FOUND && body.contains("model") && body.contains("not found")
+}
+
 pub struct OllamaProvider {
     base_url: String,
     model: String,
     timeout: Duration,
+    http: HttpContext,
 }
 
 impl OllamaProvider {
@@ -54,15 +104,14 @@ impl OllamaProvider {
             base_url,
             model,
             timeout,
+            http: HttpContext::new(),
         }
     }
 
-    fn client(&self) -> Client {
-        Client::builder()
-            .no_proxy() // Disable system proxy detection to avoid CoreFoundation crash
-            .timeout(self.timeout)
-            .build()
-            .unwrap_or_else(|_| Client::new())
+    /// Send requests through a shared client instead of this provider's own
+    pub fn with_http(mut self, http: HttpContext) -> Self {
+        self.http = http;
+        self
     }
 
     fn extract_model_name(&self) -> &str {
@@ -75,6 +124,16 @@ impl OllamaProvider {
     }
 }
 
+/// What SumVox uses through this provider
+pub const CAPABILITIES: LlmCapabilities = LlmCapabilities {
+    system_message: true,
+    // Top-level `think` flag
+    thinking: true,
+    stop_sequences: true,
+    streaming: false,
+    json_mode: false,
+};
+
 #[async_trait]
 impl LlmProvider for OllamaProvider {
     fn name(&self) -> &str {
@@ -91,27 +150,13 @@ impl LlmProvider for OllamaProvider {
         let model_name = self.extract_model_name();
         let url = format!("{}/api/generate", self.base_url);
 
-        let ollama_request = OllamaRequest {
-            model: model_name.to_string(),
-            prompt: request.prompt.clone(),
-            stream: false,
-            options: OllamaOptions {
-                temperature: request.temperature,
-                num_predict: request.max_tokens,
-            },
-            system: request.system_message.clone(),
-            think: if request.disable_thinking {
-                Some(false)
-            } else {
-                None
-            },
-        };
+        let ollama_request = OllamaRequest::new(model_name, request);
 
         tracing::debug!("Sending request to Ollama API: {}", model_name);
 
         let response = self
-            .client()
-            .post(&url)
+            .http
+            .post(&url, self.timeout)
             .json(&ollama_request)
             .send()
             .await
@@ -123,6 +168,17 @@ impl LlmProvider for OllamaProvider {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
+            if is_model_not_found(status, &error_text) {
+                tracing::warn!(
+                    "Ollama model {} is not installed; run `ollama pull {}`",
+                    model_name,
+                    model_name
+                );
+                return Err(LlmError::ModelNotFound(format!(
+                    "Ollama model {} not found (run `ollama pull {}`)",
+                    model_name, model_name
+                )));
+            }
             return Err(LlmError::Request(format!(
                 "Ollama API returned {}: {}",
                 status, error_text
@@ -142,10 +198,38 @@ impl LlmProvider for OllamaProvider {
         })
     }
 
+    fn capabilities(&self) -> LlmCapabilities {
+        CAPABILITIES
+    }
+
     fn estimate_cost(&self, _input_tokens: u32, _output_tokens: u32) -> f64 {
         // Ollama is free (local)
         0.0
     }
+
+    async fn list_models(&self) -> LlmResult<Vec<String>> {
+        let url = format!("{}/api/tags", self.base_url);
+        let response = self
+            .http
+            .get(&url, self.timeout)
+            .send()
+            .await
+            .map_err(|e| LlmError::Request(format!("Ollama tags request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(LlmError::Request(format!(
+                "Ollama tags endpoint returned {}",
+                response.status()
+            )));
+        }
+
+        let tags: TagsResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::Request(format!("Failed to parse Ollama tags: {}", e)))?;
+
+        Ok(tags.models.into_iter().map(|m| m.name).collect())
+    }
 }
 
 #[cfg(test)]
@@ -231,6 +315,9 @@ mod tests {
             max_tokens: 100,
             temperature: 0.3,
             disable_thinking,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
         }
     }
 
@@ -244,6 +331,7 @@ mod tests {
             options: OllamaOptions {
                 temperature: request.temperature,
                 num_predict: request.max_tokens,
+                stop: request.stop.clone(),
             },
             system: request.system_message.clone(),
             think: if request.disable_thinking {
@@ -269,6 +357,7 @@ mod tests {
             options: OllamaOptions {
                 temperature: request.temperature,
                 num_predict: request.max_tokens,
+                stop: request.stop.clone(),
             },
             system: request.system_message.clone(),
             think: if request.disable_thinking {
@@ -284,6 +373,113 @@ mod tests {
         assert!(val["options"].get("think").is_none());
     }
 
+    #[test]
+    fn test_system_mode_field_sends_system_field() {
+        let mut request = make_request(false);
+        request.system_message = Some("Be brief.".to_string());
+
+        let val = serde_json::to_value(OllamaRequest::new("llama3.2", &request)).unwrap();
+        assert_eq!(val["system"], "Be brief.");
+        assert_eq!(val["prompt"], "Hello");
+    }
+
+    #[test]
+    fn test_system_mode_prompt_embeds_system_message() {
+        let mut request = make_request(false);
+        request.ollama_system_mode = OllamaSystemMode::Prompt;
+        let val = serde_json::to_value(OllamaRequest::new("llama3.2", &request)).unwrap();
+        // Nothing to embed without a system message
+        assert_eq!(val["prompt"], "Hello");
+
+        request.system_message = Some("Be brief.".to_string());
+        let val = serde_json::to_value(OllamaRequest::new("llama3.2", &request)).unwrap();
+        assert!(val.get("system").is_none());
+        assert_eq!(val["prompt"], "System: Be brief.\n\nHello");
+    }
+
+    #[test]
+    fn test_model_not_found_detection() {
+        let not_pulled = r#"{"error":"model \"llama3.2\" not found, try pulling it first"}"#;
+        assert!(is_model_not_found(
+            reqwest::StatusCode::NOT_FOUND,
+            not_pulled
+        ));
+
+        // Other failures keep their generic mapping
+        assert!(!is_model_not_found(
+            reqwest::StatusCode::NOT_FOUND,
+            "404 page not found"
+        ));
+        assert!(!is_model_not_found(
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+            not_pulled
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_generate_maps_unpulled_model_to_model_not_found() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/generate")
+            .with_status(404)
+            .with_body(r#"{"error":"model \"llama3.2\" not found, try pulling it first"}"#)
+            .create_async()
+            .await;
+
+        let provider = OllamaProvider::with_base_url(
+            server.url(),
+            "llama3.2".to_string(),
+            Duration::from_secs(5),
+        );
+        let request = GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 10,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        let err = provider.generate(&request).await.unwrap_err();
+        assert!(matches!(err, LlmError::ModelNotFound(_)));
+        assert!(err.to_string().contains("ollama pull llama3.2"));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_generate_sends_stop_in_options() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "options": {"stop": ["\n\n", "END"]},
+            })))
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let provider = OllamaProvider::with_base_url(
+            server.url(),
+            "llama3.2".to_string(),
+            Duration::from_secs(5),
+        );
+        let request = GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 50,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: Some(vec!["\n\n".to_string(), "END".to_string()]),
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        let _ = provider.generate(&request).await;
+        mock.assert_async().await;
+    }
+
     // Integration test - requires actual Ollama service running
     #[tokio::test]
     #[ignore]
@@ -300,6 +496,9 @@ mod tests {
             max_tokens: 10,
             temperature: 0.3,
             disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
         };
 
         let response = provider.generate(&request).await.unwrap();
@@ -1,11 +1,11 @@
 // OpenAI API provider implementation
 
+use super::http::HttpContext;
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use super::{GenerationRequest, GenerationResponse, LlmProvider};
+use super::{GenerationRequest, GenerationResponse, LlmCapabilities, LlmProvider};
 use crate::error::{LlmError, LlmResult};
 
 #[allow(dead_code)]
@@ -35,6 +35,10 @@ struct OpenAIRequest {
     /// API docs: https://platform.openai.com/docs/guides/reasoning
     #[serde(skip_serializing_if = "Option::is_none")]
     reasoning_effort: Option<String>,
+
+    /// Stop sequences; not accepted by reasoning models
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -65,11 +69,22 @@ struct Usage {
     completion_tokens: u32,
 }
 
+#[derive(Debug, Deserialize)]
+struct ModelsResponse {
+    data: Vec<ModelEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    id: String,
+}
+
 pub struct OpenAIProvider {
     api_key: String,
     model: String,
     base_url: String,
     timeout: Duration,
+    http: HttpContext,
 }
 
 impl OpenAIProvider {
@@ -89,15 +104,14 @@ impl OpenAIProvider {
             model,
             base_url,
             timeout,
+            http: HttpContext::new(),
         }
     }
 
-    fn client(&self) -> Client {
-        Client::builder()
-            .no_proxy() // Disable system proxy detection to avoid CoreFoundation crash
-            .timeout(self.timeout)
-            .build()
-            .unwrap_or_else(|_| Client::new())
+    /// Send requests through a shared client instead of this provider's own
+    pub fn with_http(mut self, http: HttpContext) -> Self {
+        self.http = http;
+        self
     }
 
     fn extract_model_name(&self) -> &str {
@@ -122,6 +136,30 @@ fn is_reasoning_model(model_name: &str) -> bool {
         || model_name.starts_with("gpt-5")
 }
 
+/// `reasoning_effort` to send for `request` to `model_name`.
+///
+/// Derived solely from the disable_thinking flag (no model-name heuristic):
+/// disable_thinking=true → "low" (minimize reasoning effort), false → omit
+/// the field entirely. A configured `reasoning_effort` replaces this for
+/// reasoning models only.
+fn reasoning_effort(model_name: &str, request: &GenerationRequest) -> Option<String> {
+    match &request.reasoning_effort {
+        Some(effort) if is_reasoning_model(model_name) => Some(effort.clone()),
+        _ if request.disable_thinking => Some("low".to_string()),
+        _ => None,
+    }
+}
+
+/// What SumVox uses through this provider
+pub const CAPABILITIES: LlmCapabilities = LlmCapabilities {
+    system_message: true,
+    // reasoning_effort for reasoning models
+    thinking: true,
+    stop_sequences: true,
+    streaming: false,
+    json_mode: false,
+};
+
 #[async_trait]
 impl LlmProvider for OpenAIProvider {
     fn name(&self) -> &str {
@@ -156,14 +194,7 @@ impl LlmProvider for OpenAIProvider {
             content: request.prompt.clone(),
         });
 
-        // Set reasoning_effort based solely on disable_thinking flag (no model-name heuristic).
-        // disable_thinking=true  → "low" (minimize reasoning effort)
-        // disable_thinking=false → omit the field entirely
-        let reasoning_effort = if request.disable_thinking {
-            Some("low".to_string())
-        } else {
-            None
-        };
+        let reasoning_effort = reasoning_effort(model_name, request);
 
         // Reasoning models (o1, o3, o4, gpt-5) use max_completion_tokens and no temperature.
         // Standard models use max_tokens and temperature.
@@ -172,6 +203,11 @@ impl LlmProvider for OpenAIProvider {
         } else {
             (None, Some(request.max_tokens), Some(request.temperature))
         };
+        let stop = if is_reasoning_model(model_name) {
+            None
+        } else {
+            request.stop.clone()
+        };
 
         let openai_request = OpenAIRequest {
             model: model_name.to_string(),
@@ -180,13 +216,14 @@ impl LlmProvider for OpenAIProvider {
             max_tokens,
             temperature,
             reasoning_effort,
+            stop,
         };
 
         tracing::debug!("Sending request to OpenAI API: {}", model_name);
 
         let response = self
-            .client()
-            .post(&url)
+            .http
+            .post(&url, self.timeout)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&openai_request)
@@ -200,10 +237,14 @@ impl LlmProvider for OpenAIProvider {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(LlmError::Request(format!(
-                "OpenAI API returned {}: {}",
-                status, error_text
-            )));
+            let message = format!("OpenAI API returned {}: {}", status, error_text);
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Err(LlmError::RateLimited(message));
+            }
+            if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+                return Err(LlmError::ModelUnavailable(message));
+            }
+            return Err(LlmError::Request(message));
         }
 
         let openai_response: OpenAIResponse = response
@@ -227,6 +268,10 @@ impl LlmProvider for OpenAIProvider {
         })
     }
 
+    fn capabilities(&self) -> LlmCapabilities {
+        CAPABILITIES
+    }
+
     fn estimate_cost(&self, input_tokens: u32, output_tokens: u32) -> f64 {
         // GPT-4o-mini pricing (per 1K tokens)
         const INPUT_COST_PER_1K: f64 = 0.00015;
@@ -237,6 +282,37 @@ impl LlmProvider for OpenAIProvider {
 
         input_cost + output_cost
     }
+
+    async fn list_models(&self) -> LlmResult<Vec<String>> {
+        if !self.is_available() {
+            return Err(LlmError::Unavailable(
+                "OpenAI API key not configured".to_string(),
+            ));
+        }
+
+        let url = format!("{}/models", self.base_url);
+        let response = self
+            .http
+            .get(&url, self.timeout)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send()
+            .await
+            .map_err(|e| LlmError::Request(format!("OpenAI models request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(LlmError::Request(format!(
+                "OpenAI models endpoint returned {}",
+                response.status()
+            )));
+        }
+
+        let models: ModelsResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::Request(format!("Failed to parse OpenAI models: {}", e)))?;
+
+        Ok(models.data.into_iter().map(|m| m.id).collect())
+    }
 }
 
 #[cfg(test)]
@@ -337,6 +413,9 @@ mod tests {
             max_tokens: 100,
             temperature: 0.3,
             disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
         };
 
         let result = provider.generate(&request).await;
@@ -344,6 +423,94 @@ mod tests {
         assert!(matches!(result.unwrap_err(), LlmError::Unavailable(_)));
     }
 
+    #[tokio::test]
+    async fn test_generate_sends_stop() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "stop": ["\n\n", "END"],
+            })))
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let provider = OpenAIProvider::with_base_url(
+            "test-key".to_string(),
+            "gpt-4o-mini".to_string(),
+            server.url(),
+            Duration::from_secs(5),
+        );
+        let request = GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 50,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: Some(vec!["\n\n".to_string(), "END".to_string()]),
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        let _ = provider.generate(&request).await;
+        mock.assert_async().await;
+    }
+
+    fn effort_request(disable_thinking: bool, effort: Option<&str>) -> GenerationRequest {
+        GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 50,
+            temperature: 0.3,
+            disable_thinking,
+            stop: None,
+            reasoning_effort: effort.map(str::to_string),
+            ollama_system_mode: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_configured_reasoning_effort_only_for_reasoning_models() {
+        let high = effort_request(true, Some("high"));
+        assert_eq!(reasoning_effort("o3-mini", &high).as_deref(), Some("high"));
+        assert_eq!(
+            reasoning_effort("gpt-5-nano", &high).as_deref(),
+            Some("high")
+        );
+        // Non-reasoning models keep the disable_thinking-derived value
+        assert_eq!(reasoning_effort("gpt-4o", &high).as_deref(), Some("low"));
+        let xhigh = effort_request(false, Some("xhigh"));
+        assert_eq!(reasoning_effort("gpt-4o-mini", &xhigh), None);
+        // Unset keeps the derived value everywhere
+        let unset = effort_request(false, None);
+        assert_eq!(reasoning_effort("o3-mini", &unset), None);
+    }
+
+    #[tokio::test]
+    async fn test_generate_sends_configured_reasoning_effort() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "model": "o4-mini",
+                "reasoning_effort": "medium",
+            })))
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let provider = OpenAIProvider::with_base_url(
+            "test-key".to_string(),
+            "o4-mini".to_string(),
+            server.url(),
+            Duration::from_secs(5),
+        );
+        let _ = provider
+            .generate(&effort_request(true, Some("medium")))
+            .await;
+        mock.assert_async().await;
+    }
+
     // ── C5: OpenAIRequestSerialization ───────────────────────────────────
 
     fn build_openai_request(model: &str, disable_thinking: bool) -> OpenAIRequest {
@@ -367,6 +534,7 @@ mod tests {
             max_tokens,
             temperature,
             reasoning_effort,
+            stop: None,
         }
     }
 
@@ -461,6 +629,9 @@ mod tests {
             max_tokens: 50,
             temperature: 0.3,
             disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
         };
 
         let response = provider.generate(&request).await.unwrap();
@@ -0,0 +1,99 @@
+// Redaction for provider logs and error messages
+// Gemini passes its API key as a `?key=` query parameter, and reqwest errors
+// include the request URL, so URLs are masked before they reach a log line.
+// Response bodies are cut to SUMVOX_LOG_BODY_CHARS characters (default 1000).
+
+/// Query parameters whose values are secrets
+const SECRET_PARAMS: &[&str] = &["key", "api_key", "apikey", "access_token", "token"];
+
+/// Body length logged when SUMVOX_LOG_BODY_CHARS is unset or invalid
+const DEFAULT_BODY_CHARS: usize = 1000;
+
+/// Replace the value of every secret query parameter in `text` with `***`.
+/// Works on bare URLs and on messages that contain one.
+pub fn redact_secrets(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(i) = rest.find(['?', '&']) {
+        let (head, tail) = rest.split_at(i + 1);
+        out.push_str(head);
+        rest = tail;
+
+        let Some(eq) = rest.find('=') else { continue };
+        let name = &rest[..eq];
+        if !SECRET_PARAMS.contains(&name.to_lowercase().as_str()) {
+            continue;
+        }
+        let value_end = rest[eq + 1..]
+            .find(|c: char| {
+                c == '&' || c == '#' || c == '"' || c == '\'' || c == ')' || c.is_whitespace()
+            })
+            .map_or(rest.len(), |end| eq + 1 + end);
+        out.push_str(name);
+        out.push_str("=***");
+        rest = &rest[value_end..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Maximum body characters to log, from SUMVOX_LOG_BODY_CHARS
+fn max_body_chars() -> usize {
+    std::env::var("SUMVOX_LOG_BODY_CHARS")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(DEFAULT_BODY_CHARS)
+}
+
+/// `body` made safe for a debug log: secrets masked, cut to the length limit
+pub fn body_for_log(body: &str) -> String {
+    truncate(&redact_secrets(body), max_body_chars())
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars).collect();
+    format!("{}… [{} more chars]", kept, total - max_chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gemini_url_key_is_masked() {
+        let url = "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-flash:generateContent?key=secret";
+        let redacted = redact_secrets(url);
+        assert!(!redacted.contains("secret"));
+        assert!(redacted.ends_with(":generateContent?key=***"));
+    }
+
+    #[test]
+    fn test_key_inside_error_message_is_masked() {
+        let message = "error sending request for url (https://example.com/v1beta/models?key=AIzaSecret&pageSize=50)";
+        assert_eq!(
+            redact_secrets(message),
+            "error sending request for url (https://example.com/v1beta/models?key=***&pageSize=50)"
+        );
+    }
+
+    #[test]
+    fn test_other_params_untouched() {
+        let url = "http://localhost:11434/api/tags?keyword=llama&monkey=1";
+        assert_eq!(redact_secrets(url), url);
+        assert_eq!(redact_secrets("no query here"), "no query here");
+    }
+
+    #[test]
+    fn test_truncate_long_body() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("abcdefghij", 4), "abcd… [6 more chars]");
+        // Counts characters, not bytes
+        assert_eq!(truncate("摘要完成了", 2), "摘要… [3 more chars]");
+    }
+}
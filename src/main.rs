@@ -2,30 +2,50 @@
 // LLM summarization with TTS - supporting multiple AI coding tools
 
 mod audio;
+mod benchmark;
 mod cli;
 mod config;
+mod daemon;
+mod desktop_notification;
 mod error;
 mod hooks;
+mod keychain;
 mod llm;
 mod notify_log;
 mod provider_factory;
 mod queue;
+mod retry;
 mod transcript;
 mod tts;
+mod watch;
+mod wizard;
 
+use std::collections::HashMap;
 use std::io::{IsTerminal, Read};
+use std::sync::Arc;
 use std::time::Duration;
 
-use clap::Parser;
-use cli::{Cli, Commands, InitArgs, JsonArgs, SayArgs, SumArgs};
-use config::{effective_disable_thinking, SumvoxConfig, TtsProviderConfig};
+use cli::{
+    BenchmarkArgs, Cli, Commands, CredentialsAction, CredentialsArgs, CredentialsListArgs,
+    CredentialsRemoveArgs, CredentialsSetArgs, CredentialsTestArgs, DaemonArgs, DoctorArgs,
+    InitArgs, JsonArgs, ModelsArgs, NotificationsAction, NotificationsArgs, ProvidersAction,
+    ProvidersArgs, SayArgs, SumArgs, TranscriptStatsArgs, WatchArgs,
+};
+use config::{
+    effective_disable_thinking, LlmProviderConfig, ModelPricing, SummarizationConfig, SumvoxConfig,
+    TerminalFallback, TtsConfig, TtsProviderConfig,
+};
 use error::{Result, VoiceError};
 use hooks::claude_code::{ClaudeCodeInput, LlmOptions, TtsOptions};
 use hooks::HookFormat;
+use llm::http::HttpContext;
+use llm::models_cache::ModelsCache;
 use llm::GenerationRequest;
 use provider_factory::ProviderFactory;
+use transcript::TranscriptReader;
 use tts::{
-    create_single_tts, create_tts_from_config, resolve_tts_provider, TtsEngine, TtsProvider,
+    create_single_tts, create_tts_from_config, resolve_tts_provider, speak_with_timeout, TtsEngine,
+    TtsProvider,
 };
 
 #[tokio::main]
@@ -36,7 +56,8 @@ async fn main() -> Result<()> {
     }
 
     // Parse CLI arguments
-    let cli = Cli::parse();
+    let cli = Cli::parse_with_env();
+    SumvoxConfig::use_fresh_config(cli.fresh_config);
 
     // Initialize tracing
     tracing_subscriber::fmt()
@@ -52,47 +73,196 @@ async fn main() -> Result<()> {
         Some(Commands::Sum(args)) => handle_sum(args).await,
         Some(Commands::Json(args)) => handle_json(args).await,
         Some(Commands::Init(args)) => handle_init(args).await,
+        Some(Commands::Models(args)) => handle_models(args).await,
+        Some(Commands::Credentials(args)) => handle_credentials(args).await,
+        Some(Commands::WhySilent) => {
+            match notify_log::last_error() {
+                Some((at, reason)) => println!("{}  {}", at, reason),
+                None => println!("No failures recorded"),
+            }
+            Ok(())
+        }
+        Some(Commands::Doctor(args)) => handle_doctor(args),
+        Some(Commands::Notifications(args)) => {
+            handle_notifications(args);
+            Ok(())
+        }
+        Some(Commands::Providers(args)) => {
+            handle_providers(args);
+            Ok(())
+        }
+        Some(Commands::Daemon(args)) => handle_daemon(args).await,
+        Some(Commands::Benchmark(args)) => handle_benchmark(args).await,
+        Some(Commands::Watch(args)) => handle_watch(args).await,
+        Some(Commands::TranscriptStats(args)) => handle_transcript_stats(args).await,
         None => {
-            // No subcommand provided - check if stdin is available (hook mode)
-            if !std::io::stdin().is_terminal() {
-                tracing::info!("No subcommand provided, auto-detecting json mode from stdin");
-                handle_json(JsonArgs {
-                    format: "auto".to_string(),
-                    timeout: 10,
-                })
-                .await
-            } else {
-                // No stdin available, show help
-                eprintln!("Error: No subcommand provided and stdin is not available");
-                eprintln!("Run 'sumvox --help' for usage information");
-                Err(VoiceError::Config("No subcommand provided".into()))
+            let flag = cli.terminal_fallback;
+            let fallback = no_subcommand_fallback(std::io::stdin().is_terminal(), || {
+                flag.as_deref()
+                    .and_then(TerminalFallback::from_name)
+                    .unwrap_or_else(|| {
+                        SumvoxConfig::load_from_home_cached()
+                            .map(|config| config.hooks.terminal_fallback)
+                            .unwrap_or_default()
+                    })
+            });
+            match fallback {
+                // No subcommand provided - stdin is piped (hook mode)
+                None => {
+                    tracing::info!("No subcommand provided, auto-detecting json mode from stdin");
+                    handle_json(JsonArgs {
+                        format: "auto".to_string(),
+                        timeout: 10,
+                        print_summary: None,
+                        budget_override: None,
+                    })
+                    .await
+                }
+                Some(TerminalFallback::Error) => {
+                    eprintln!("Error: No subcommand provided and stdin is not available");
+                    eprintln!("Run 'sumvox --help' for usage information");
+                    Err(VoiceError::Config("No subcommand provided".into()))
+                }
+                Some(TerminalFallback::QuickStart) => {
+                    print!("{}", QUICK_START);
+                    Ok(())
+                }
+                Some(TerminalFallback::Wizard) => {
+                    handle_init(InitArgs {
+                        force: false,
+                        interactive: true,
+                    })
+                    .await
+                }
             }
         }
     }
 }
 
+/// Printed by bare `sumvox` in a terminal with `hooks.terminal_fallback: quick_start`
+const QUICK_START: &str = "\
+SumVox speaks summaries of AI coding sessions. Try:
+
+  sumvox init -i                  set up providers interactively
+  sumvox say \"Hello\"              speak text
+  echo \"...\" | sumvox sum -       summarize text, then speak it
+  sumvox credentials test --all   check API keys
+  sumvox --help                   all commands
+
+As a Claude Code hook, sumvox reads the event JSON from stdin.
+";
+
+/// What bare `sumvox` should do besides hook mode: None when stdin is piped
+/// (a hook payload to read), otherwise the configured fallback. `fallback`
+/// is only consulted in a terminal.
+fn no_subcommand_fallback(
+    stdin_is_terminal: bool,
+    fallback: impl FnOnce() -> TerminalFallback,
+) -> Option<TerminalFallback> {
+    stdin_is_terminal.then(fallback)
+}
+
 // ============================================================================
 // Say Command - Direct TTS
 // ============================================================================
 
 async fn handle_say(args: SayArgs) -> Result<()> {
-    tracing::info!("sumvox say: {}", args.text);
+    if args.test_tone {
+        // Deliberately skips config loading and TTS: only the audio stack is exercised
+        let volume = args.volume.unwrap_or(100);
+        tracing::info!("sumvox say: playing 440Hz test tone (volume {})", volume);
+        let wav = audio::tone::test_tone_wav();
+        audio::afplay::play_with_afplay(&wav, volume, "sumvox_test_tone")?;
+        println!("Test tone played. If you heard nothing, check your audio output device.");
+        return Ok(());
+    }
+
+    if args.async_mode && !hooks::detach::is_detached_child() {
+        let exe = std::env::current_exe()?;
+        let child_args = async_say_args(&args);
+        hooks::detach::spawn_detached(hooks::detach::background_command(&exe, &child_args), None)?;
+        return Ok(());
+    }
 
-    let config = SumvoxConfig::load_from_home()?;
+    let config = SumvoxConfig::load_from_home_cached()?;
 
     let tts_opts = TtsOptions {
         engine: args.tts,
         voice: args.voice,
         rate: args.rate,
         volume: args.volume,
+        device: None,
+        print_text: false,
     };
 
-    speak_text(&config, &tts_opts, &args.text).await?;
+    if let Some(ssml_path) = args.ssml_file {
+        tracing::info!("sumvox say: SSML from {:?}", ssml_path);
+        let ssml = std::fs::read_to_string(&ssml_path).map_err(|e| {
+            VoiceError::Config(format!("Failed to read SSML file {:?}: {}", ssml_path, e))
+        })?;
+        speak_ssml(&config, &tts_opts, &ssml).await?;
+    } else {
+        let text = args.text.unwrap_or_default();
+        tracing::info!("sumvox say: {}", text);
+        if tts::spoken_dedup::is_repeat(&config, &text) {
+            return Ok(());
+        }
+        // One voice for every sentence
+        let rotated = tts::voice_rotation::with_next_voice(&config);
+        let config = rotated.as_ref().unwrap_or(&config);
+        let speak_timeout = Duration::from_secs(args.timeout);
+        if args.split_sentences {
+            let pause = Duration::from_millis(config.tts.pause_between_sentences_ms);
+            let sentences = tts::split_sentences(&text);
+            let tts_opts = &tts_opts;
+            tts::speak_with_pauses(&sentences, pause, |sentence| async move {
+                speak_text(config, tts_opts, &sentence, Some(speak_timeout)).await
+            })
+            .await?;
+        } else {
+            speak_text(config, &tts_opts, &text, Some(speak_timeout)).await?;
+        }
+    }
 
     tracing::info!("sumvox say completed");
     Ok(())
 }
 
+/// Arguments for the background `sumvox say` child of `say --async`:
+/// the same request, minus `--async`, so the child speaks in the foreground
+fn async_say_args(args: &SayArgs) -> Vec<String> {
+    let mut child = vec!["say".to_string()];
+    if let Some(ssml_file) = &args.ssml_file {
+        // Pin the path so the child does not depend on our working directory
+        let path = std::fs::canonicalize(ssml_file).unwrap_or_else(|_| ssml_file.clone());
+        child.push("--ssml-file".to_string());
+        child.push(path.to_string_lossy().into_owned());
+    }
+    child.extend([
+        "--tts".to_string(),
+        args.tts.clone(),
+        "--timeout".to_string(),
+        args.timeout.to_string(),
+    ]);
+    if let Some(rate) = args.rate {
+        child.extend(["--rate".to_string(), rate.to_string()]);
+    }
+    if let Some(voice) = &args.voice {
+        child.extend(["--voice".to_string(), voice.clone()]);
+    }
+    if let Some(volume) = args.volume {
+        child.extend(["--volume".to_string(), volume.to_string()]);
+    }
+    if args.split_sentences {
+        child.push("--split-sentences".to_string());
+    }
+    if let Some(text) = &args.text {
+        // `--` keeps text starting with a dash from being read as a flag
+        child.extend(["--".to_string(), text.clone()]);
+    }
+    child
+}
+
 // ============================================================================
 // Sum Command - LLM Summarization + TTS
 // ============================================================================
@@ -115,32 +285,52 @@ async fn handle_sum(args: SumArgs) -> Result<()> {
 
     tracing::info!("sumvox sum: {} chars", text.len());
 
-    let config = SumvoxConfig::load_from_home()?;
+    let mut config = Arc::unwrap_or_clone(SumvoxConfig::load_from_home_cached()?);
+    if args.focus.is_some() {
+        config.summarization.focus = args.focus.clone();
+    }
+    if args.persona.is_some() {
+        config.summarization.persona = args.persona.clone();
+    }
+    if args.reasoning_effort.is_some() {
+        config.llm.parameters.reasoning_effort = args.reasoning_effort.clone();
+    }
+    apply_budget_override(&mut config, args.budget_override);
 
     // Build summarization prompt
-    let user_prompt = config
-        .summarization
-        .prompt_template
-        .replace("{context}", &text);
+    let user_prompt = config.summarization.render_prompt(&text);
 
-    let system_message = Some(config.summarization.system_message.clone());
+    let system_message = Some(config.summarization.effective_system_message());
 
     // Generate summary
     let llm_opts = LlmOptions {
         provider: args.provider,
         model: args.model,
         timeout: args.timeout,
+        budget_override: None,
     };
 
-    let summary = generate_summary(&config, &llm_opts, system_message, &user_prompt).await?;
+    let attempt =
+        generate_summary_outcome(&config, &llm_opts, system_message, &user_prompt).await?;
+
+    if args.output_json {
+        let output = SumJsonOutput::from_attempt(&attempt);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).map_err(VoiceError::Json)?
+        );
+    }
 
+    let summary = attempt.map(|o| o.text).unwrap_or_default();
     if summary.is_empty() {
         eprintln!("Warning: Empty summary generated");
         return Ok(());
     }
 
-    // Output summary
-    println!("{}", summary);
+    // Output summary (the JSON object already carries it)
+    if !args.output_json {
+        println!("{}", summary);
+    }
 
     // Speak if not --no-speak
     if !args.no_speak {
@@ -149,9 +339,16 @@ async fn handle_sum(args: SumArgs) -> Result<()> {
             voice: args.voice,
             rate: args.rate,
             volume: args.volume,
+            device: None,
+            print_text: false,
         };
 
-        speak_text(&config, &tts_opts, &summary).await?;
+        let spoken = config.summarization.spoken_text(&summary);
+        if !tts::spoken_dedup::is_repeat(&config, &spoken) {
+            let rotated = tts::voice_rotation::with_next_voice(&config);
+            let config = rotated.as_ref().unwrap_or(&config);
+            speak_text(config, &tts_opts, &spoken, None).await?;
+        }
     }
 
     tracing::info!("sumvox sum completed");
@@ -175,21 +372,26 @@ async fn handle_json(args: JsonArgs) -> Result<()> {
         return Err(VoiceError::Config("Empty JSON input".into()));
     }
 
-    // Detect or use specified format
-    let (_json, detected_format) = hooks::parse_input(&input_buffer)?;
-
-    let format = args.format.parse().unwrap_or(detected_format);
+    // Detect the format, or require the one specified
+    let (_json, format) = hooks::parse_input_as(&input_buffer, &args.format)?;
 
     tracing::info!("Hook format: {:?}", format);
 
-    let config = SumvoxConfig::load_from_home()?;
+    let mut config = Arc::unwrap_or_clone(SumvoxConfig::load_from_home_cached()?);
+    apply_budget_override(&mut config, args.budget_override);
 
     match format {
         HookFormat::ClaudeCode => {
             let input = ClaudeCodeInput::parse(&input_buffer)?;
-            let tts_opts = TtsOptions::default();
+            let tts_opts = TtsOptions {
+                print_text: args
+                    .print_summary
+                    .unwrap_or_else(|| !std::io::stdout().is_terminal()),
+                ..Default::default()
+            };
             let llm_opts = LlmOptions {
                 timeout: args.timeout,
+                budget_override: args.budget_override,
                 ..Default::default()
             };
 
@@ -198,15 +400,12 @@ async fn handle_json(args: JsonArgs) -> Result<()> {
         HookFormat::Generic => {
             // Generic format: extract text and summarize
             let generic = hooks::parse_generic(&input_buffer)?;
-            let text = generic.get_text().unwrap(); // Already validated
+            let text = generic.get_titled_text().unwrap(); // Already validated
 
             // Use sum logic
-            let user_prompt = config
-                .summarization
-                .prompt_template
-                .replace("{context}", text);
+            let user_prompt = config.summarization.render_prompt(&text);
 
-            let system_message = Some(config.summarization.system_message.clone());
+            let system_message = Some(config.summarization.effective_system_message());
 
             let llm_opts = LlmOptions {
                 timeout: args.timeout,
@@ -219,7 +418,12 @@ async fn handle_json(args: JsonArgs) -> Result<()> {
             if !summary.is_empty() {
                 println!("{}", summary);
                 let tts_opts = TtsOptions::default();
-                speak_text(&config, &tts_opts, &summary).await?;
+                let spoken = config.summarization.spoken_text(&summary);
+                if !tts::spoken_dedup::is_repeat(&config, &spoken) {
+                    let rotated = tts::voice_rotation::with_next_voice(&config);
+                    let config = rotated.as_ref().unwrap_or(&config);
+                    speak_text(config, &tts_opts, &spoken, None).await?;
+                }
             }
         }
     }
@@ -228,6 +432,18 @@ async fn handle_json(args: JsonArgs) -> Result<()> {
     Ok(())
 }
 
+/// Replace `cost.daily_limit_usd` for this run with `--budget-override`
+fn apply_budget_override(config: &mut SumvoxConfig, budget_override: Option<f64>) {
+    if let Some(limit) = budget_override {
+        tracing::info!(
+            "Budget override: daily LLM limit ${:.2} for this run (cost.daily_limit_usd: {:?})",
+            limit,
+            config.cost.daily_limit_usd
+        );
+        config.cost.daily_limit_usd = Some(limit);
+    }
+}
+
 // ============================================================================
 // Init Command
 // ============================================================================
@@ -255,7 +471,54 @@ async fn handle_init(args: InitArgs) -> Result<()> {
         std::fs::remove_file(&json_path).ok();
     }
 
-    // Create default config with recommended settings
+    let mut config = recommended_config();
+
+    if args.interactive {
+        config = wizard::build_config(&mut wizard::TerminalPrompter, config)?;
+
+        eprintln!();
+        eprintln!("{}", wizard::describe(&config));
+        eprintln!();
+        let path = SumvoxConfig::toml_config_path()?;
+        let write = wizard::Prompter::confirm(
+            &mut wizard::TerminalPrompter,
+            &format!("Write this config to {:?}?", path),
+            true,
+        )?;
+        if !write {
+            eprintln!("Nothing written");
+            return Ok(());
+        }
+        config.save_to_home()?;
+        eprintln!("✓ Created config at: {:?}", path);
+        eprintln!();
+        eprintln!("Test it with: sumvox say \"Hello, SumVox!\"");
+        return Ok(());
+    }
+
+    // Save as YAML (preferred format)
+    config.save_to_home()?;
+
+    eprintln!("✓ Created config at: {:?}", yaml_path);
+    eprintln!();
+    eprintln!("Next steps:");
+    eprintln!("1. Edit config file and set your API keys:");
+    eprintln!("   open ~/.config/sumvox/config.yaml");
+    eprintln!(r#"   # Replace ${{PROVIDER_API_KEY}} with your actual API keys"#);
+    eprintln!("   # Google: https://ai.google.dev");
+    eprintln!("   # Anthropic: https://console.anthropic.com");
+    eprintln!("   # OpenAI: https://platform.openai.com");
+    eprintln!();
+    eprintln!("2. Test voice notification:");
+    eprintln!("   sumvox say \"Hello, SumVox!\"");
+    eprintln!();
+    eprintln!("3. See config/recommended.yaml for more examples");
+
+    Ok(())
+}
+
+/// Default config with the recommended settings `init` writes
+fn recommended_config() -> SumvoxConfig {
     let mut config = SumvoxConfig::default();
 
     // Apply recommended settings
@@ -282,6 +545,7 @@ async fn handle_init(args: InitArgs) -> Result<()> {
             stability: None,
             style: None,
             style_prompt: None,
+            stream: None,
         },
         TtsProviderConfig {
             name: "google".to_string(),
@@ -297,30 +561,781 @@ async fn handle_init(args: InitArgs) -> Result<()> {
             stability: None,
             style: None,
             style_prompt: None,
+            stream: None,
         },
     ];
 
-    // Save as YAML (preferred format)
+    config
+}
+
+// ============================================================================
+// Models Command
+// ============================================================================
+
+async fn handle_models(args: ModelsArgs) -> Result<()> {
+    let config = SumvoxConfig::load_from_home_cached()?;
+    let cache = ModelsCache::from_home()?;
+
+    let providers: Vec<_> = config
+        .llm
+        .providers
+        .iter()
+        .filter(|p| {
+            args.provider
+                .as_deref()
+                .is_none_or(|name| p.name.eq_ignore_ascii_case(name))
+        })
+        .collect();
+
+    if providers.is_empty() {
+        return Err(VoiceError::Config(format!(
+            "Provider '{}' not found in config",
+            args.provider.unwrap_or_default()
+        )));
+    }
+
+    let http = HttpContext::new();
+    for provider_config in providers {
+        let provider = match ProviderFactory::create_single_in(provider_config, &http) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("{}: {}", provider_config.name, e);
+                continue;
+            }
+        };
+
+        match cache
+            .get_or_fetch(&provider_config.name, provider.as_ref(), args.refresh)
+            .await
+        {
+            Ok(models) => {
+                println!("{}:", provider_config.name);
+                for model in models {
+                    println!("  {}", model);
+                }
+            }
+            Err(e) => eprintln!("{}: {}", provider_config.name, e),
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Notifications Command
+// ============================================================================
+
+fn handle_notifications(args: NotificationsArgs) {
+    match args.action {
+        NotificationsAction::Observed => {
+            let types = notify_log::observed_notification_types();
+            if types.is_empty() {
+                println!("No notification types recorded");
+                println!("Set hooks.claude_code.observe_notification_types: true to record them");
+            }
+            for notification_type in types {
+                println!("{}", notification_type);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Providers Command
+// ============================================================================
+
+fn handle_providers(args: ProvidersArgs) {
+    match args.action {
+        ProvidersAction::Info => {
+            let llm_rows = llm::PROVIDER_CAPABILITIES.iter().map(|(name, c)| {
+                let flags = [
+                    c.system_message,
+                    c.thinking,
+                    c.stop_sequences,
+                    c.streaming,
+                    c.json_mode,
+                ];
+                (*name, flags.to_vec())
+            });
+            print!(
+                "{}",
+                capability_table(
+                    &["LLM", "system", "thinking", "stop", "streaming", "json"],
+                    llm_rows
+                )
+            );
+            println!();
+
+            let tts_rows = tts::PROVIDER_CAPABILITIES.iter().map(|(name, c)| {
+                let flags = [c.ssml, c.rate, c.volume, c.voices, c.streaming];
+                (*name, flags.to_vec())
+            });
+            print!(
+                "{}",
+                capability_table(
+                    &["TTS", "ssml", "rate", "volume", "voices", "streaming"],
+                    tts_rows
+                )
+            );
+        }
+    }
+}
+
+/// A ✓/✗ table: one row per provider, one column per header after the first
+fn capability_table<'a>(
+    headers: &[&str],
+    rows: impl IntoIterator<Item = (&'a str, Vec<bool>)>,
+) -> String {
+    let rows: Vec<_> = rows.into_iter().collect();
+    let name_width = rows
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .chain([headers[0].len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!("{:<name_width$}", headers[0]);
+    for header in &headers[1..] {
+        out.push_str(&format!("  {}", header));
+    }
+    out.push('\n');
+    for (name, flags) in &rows {
+        out.push_str(&format!("{:<name_width$}", name));
+        for (header, supported) in headers[1..].iter().zip(flags) {
+            let mark = if *supported { "✓" } else { "✗" };
+            out.push_str(&format!("  {:<width$}", mark, width = header.len()));
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+    }
+    out
+}
+
+// ============================================================================
+// Credentials Command
+// ============================================================================
+
+async fn handle_credentials(args: CredentialsArgs) -> Result<()> {
+    match args.action {
+        CredentialsAction::List(list_args) => handle_credentials_list(list_args),
+        CredentialsAction::Test(test_args) => handle_credentials_test(test_args).await,
+        CredentialsAction::Set(set_args) => handle_credentials_set(set_args),
+        CredentialsAction::Remove(remove_args) => handle_credentials_remove(remove_args),
+    }
+}
+
+fn handle_credentials_set(args: CredentialsSetArgs) -> Result<()> {
+    let key = if let Some(value) = args.value {
+        tracing::warn!("API key passed with --value; it stays in your shell history");
+        eprintln!("Warning: --value leaves the key in your shell history; prefer --stdin");
+        value
+    } else if args.stdin {
+        read_key_line(&mut std::io::stdin().lock())?
+    } else {
+        wizard::Prompter::ask_secret(
+            &mut wizard::TerminalPrompter,
+            &format!("API key for {}", args.provider),
+        )?
+    };
+    let key = validate_api_key(&key)?;
+
+    let mut config = SumvoxConfig::load_from_home()?;
+    let updated = config.set_api_key(&args.provider, &key);
+    if updated == 0 {
+        return Err(VoiceError::Config(format!(
+            "Provider '{}' not found in config",
+            args.provider
+        )));
+    }
+    if args.keychain {
+        // The config file stays untouched; lookups check the keychain first
+        keychain::set(&args.provider, &key)?;
+        eprintln!(
+            "✓ Saved API key for {} in the system keychain",
+            args.provider
+        );
+        return Ok(());
+    }
     config.save_to_home()?;
+    eprintln!(
+        "✓ Saved API key for {} ({} provider entr{})",
+        args.provider,
+        updated,
+        if updated == 1 { "y" } else { "ies" }
+    );
+    Ok(())
+}
 
-    eprintln!("✓ Created config at: {:?}", yaml_path);
-    eprintln!();
-    eprintln!("Next steps:");
-    eprintln!("1. Edit config file and set your API keys:");
-    eprintln!("   open ~/.config/sumvox/config.yaml");
-    eprintln!(r#"   # Replace ${{PROVIDER_API_KEY}} with your actual API keys"#);
-    eprintln!("   # Google: https://ai.google.dev");
-    eprintln!("   # Anthropic: https://console.anthropic.com");
-    eprintln!("   # OpenAI: https://platform.openai.com");
-    eprintln!();
-    eprintln!("2. Test voice notification:");
-    eprintln!("   sumvox say \"Hello, SumVox!\"");
-    eprintln!();
-    eprintln!("3. See config/recommended.yaml for more examples");
+fn handle_credentials_remove(args: CredentialsRemoveArgs) -> Result<()> {
+    if keychain::remove(&args.provider)? {
+        eprintln!("✓ Removed keychain API key for {}", args.provider);
+    } else {
+        eprintln!("No keychain API key stored for {}", args.provider);
+    }
+    Ok(())
+}
+
+/// First line of `reader`, for `credentials set --stdin`
+fn read_key_line(reader: &mut dyn std::io::BufRead) -> Result<String> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    Ok(line)
+}
 
+/// Trimmed `key`, rejecting an empty one
+fn validate_api_key(key: &str) -> Result<String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(VoiceError::Config("API key must not be empty".into()));
+    }
+    Ok(key.to_string())
+}
+
+fn handle_credentials_list(args: CredentialsListArgs) -> Result<()> {
+    let config = SumvoxConfig::load_from_home_cached()?;
+    let mut statuses = list_llm_providers(&config);
+    statuses.extend(list_tts_providers(&config));
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&statuses).map_err(VoiceError::Json)?
+        );
+    } else {
+        for status in &statuses {
+            println!(
+                "{} {:<3}  {}",
+                if status.configured { "✓" } else { "✗" },
+                status.kind,
+                status.name
+            );
+        }
+    }
     Ok(())
 }
 
+/// Credential status of one configured provider (`credentials list`)
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct ProviderStatus {
+    kind: &'static str,
+    name: String,
+    configured: bool,
+}
+
+/// LLM providers in config order; keys may come from config or the environment
+fn list_llm_providers(config: &SumvoxConfig) -> Vec<ProviderStatus> {
+    config
+        .llm
+        .providers
+        .iter()
+        .map(|p| ProviderStatus {
+            kind: "llm",
+            name: p.name.clone(),
+            configured: matches!(p.name.to_lowercase().as_str(), "ollama" | "local")
+                || !p.get_api_keys().is_empty(),
+        })
+        .collect()
+}
+
+/// TTS providers in config order
+fn list_tts_providers(config: &SumvoxConfig) -> Vec<ProviderStatus> {
+    config
+        .tts
+        .providers
+        .iter()
+        .map(|p| ProviderStatus {
+            kind: "tts",
+            name: p.name.clone(),
+            configured: p.is_configured(),
+        })
+        .collect()
+}
+
+// ============================================================================
+// Doctor Command - Health Report
+// ============================================================================
+
+fn handle_doctor(args: DoctorArgs) -> Result<()> {
+    let checks = doctor_checks(
+        SumvoxConfig::load_from_home_cached().map(Arc::unwrap_or_clone),
+        notify_log::last_error(),
+    );
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&checks).map_err(VoiceError::Json)?
+        );
+    } else {
+        for check in &checks {
+            let mark = match check.status {
+                CheckStatus::Pass => "✓",
+                CheckStatus::Warn => "!",
+                CheckStatus::Fail => "✗",
+            };
+            println!("{} {:<13} {}", mark, check.check, check.detail);
+        }
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        return Err(VoiceError::Config(format!(
+            "{} doctor check(s) failed",
+            failed
+        )));
+    }
+    Ok(())
+}
+
+/// Outcome of one `doctor` check
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    /// Worth a look, but doesn't fail the report
+    Warn,
+    Fail,
+}
+
+/// One line of the `doctor` report
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct DoctorCheck {
+    check: &'static str,
+    status: CheckStatus,
+    detail: String,
+}
+
+impl DoctorCheck {
+    fn new(check: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            check,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Health checks from the loaded config and the last recorded failure
+fn doctor_checks(
+    config: Result<SumvoxConfig>,
+    last_failure: Option<(String, String)>,
+) -> Vec<DoctorCheck> {
+    let mut checks = Vec::new();
+    let config = match config {
+        Ok(config) => {
+            checks.push(DoctorCheck::new("config", CheckStatus::Pass, "loaded"));
+            config
+        }
+        Err(e) => {
+            checks.push(DoctorCheck::new("config", CheckStatus::Fail, e.to_string()));
+            return checks;
+        }
+    };
+
+    for (check, statuses) in [
+        ("llm_providers", list_llm_providers(&config)),
+        ("tts_providers", list_tts_providers(&config)),
+    ] {
+        let ready: Vec<_> = statuses
+            .iter()
+            .filter(|s| s.configured)
+            .map(|s| s.name.as_str())
+            .collect();
+        checks.push(if ready.is_empty() {
+            DoctorCheck::new(check, CheckStatus::Fail, "no provider has credentials")
+        } else {
+            DoctorCheck::new(check, CheckStatus::Pass, ready.join(", "))
+        });
+    }
+
+    checks.push(match last_failure {
+        Some((at, reason)) => DoctorCheck::new(
+            "last_failure",
+            CheckStatus::Warn,
+            format!("{}  {}", at, reason),
+        ),
+        None => DoctorCheck::new("last_failure", CheckStatus::Pass, "none recorded"),
+    });
+    checks
+}
+
+async fn handle_credentials_test(args: CredentialsTestArgs) -> Result<()> {
+    let config = SumvoxConfig::load_from_home_cached()?;
+    let timeout = Duration::from_secs(args.timeout);
+    let selected = |name: &str| {
+        args.all
+            || args
+                .provider
+                .as_deref()
+                .is_some_and(|p| p.eq_ignore_ascii_case(name))
+    };
+
+    let http = HttpContext::new();
+    let mut results = Vec::new();
+    for provider_config in config.llm.providers.iter().filter(|p| selected(&p.name)) {
+        let result = match ProviderFactory::create_single_in(provider_config, &http) {
+            Ok(provider) => probe_llm(&provider_config.name, provider.as_ref(), timeout).await,
+            Err(e) => ProbeResult::failed("llm", &provider_config.name, e.to_string()),
+        };
+        results.push(result);
+    }
+    for provider_config in config.tts.providers.iter().filter(|p| selected(&p.name)) {
+        let result = match tts::create_single_tts(
+            provider_config,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
+        ) {
+            Ok(provider) => probe_tts(&provider_config.name, provider.as_ref()),
+            Err(e) => ProbeResult::failed("tts", &provider_config.name, e.to_string()),
+        };
+        results.push(result);
+    }
+
+    if results.is_empty() {
+        return Err(VoiceError::Config(format!(
+            "Provider '{}' not found in config",
+            args.provider.unwrap_or_default()
+        )));
+    }
+
+    print!("{}", format_probe_table(&results));
+
+    let failed = results.iter().filter(|r| !r.ok).count();
+    if failed > 0 {
+        return Err(VoiceError::Config(format!(
+            "{} of {} provider(s) failed",
+            failed,
+            results.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Outcome of probing one provider for `credentials test`
+#[derive(Debug, Clone, PartialEq)]
+struct ProbeResult {
+    kind: &'static str,
+    name: String,
+    ok: bool,
+    /// Round trip of the live request; None when nothing was sent
+    latency: Option<Duration>,
+    detail: String,
+}
+
+impl ProbeResult {
+    fn failed(kind: &'static str, name: &str, detail: String) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            ok: false,
+            latency: None,
+            detail,
+        }
+    }
+}
+
+/// Minimal live LLM request: a few output tokens, bounded by `timeout`
+async fn probe_llm(name: &str, provider: &dyn llm::LlmProvider, timeout: Duration) -> ProbeResult {
+    if !provider.is_available() {
+        return ProbeResult::failed("llm", name, "not configured (missing API key?)".into());
+    }
+
+    let request = GenerationRequest {
+        system_message: None,
+        prompt: "Reply with OK.".to_string(),
+        max_tokens: 8,
+        temperature: 0.0,
+        disable_thinking: true,
+        stop: None,
+        reasoning_effort: None,
+        ollama_system_mode: Default::default(),
+    };
+
+    let started = std::time::Instant::now();
+    let outcome = tokio::time::timeout(timeout, provider.generate(&request)).await;
+    let latency = Some(started.elapsed());
+
+    let (ok, detail) = match outcome {
+        Ok(Ok(response)) => (true, response.model),
+        Ok(Err(e)) => (false, e.to_string()),
+        Err(_) => (false, format!("timed out after {}s", timeout.as_secs())),
+    };
+    ProbeResult {
+        kind: "llm",
+        name: name.to_string(),
+        ok,
+        latency,
+        detail,
+    }
+}
+
+/// TTS availability check. Nothing is synthesized, so probing stays silent and free.
+fn probe_tts(name: &str, provider: &dyn tts::TtsProvider) -> ProbeResult {
+    if !provider.is_available() {
+        return ProbeResult::failed("tts", name, "not available (missing credentials?)".into());
+    }
+    ProbeResult {
+        kind: "tts",
+        name: name.to_string(),
+        ok: true,
+        latency: None,
+        detail: "credentials present (not played)".to_string(),
+    }
+}
+
+fn format_probe_table(results: &[ProbeResult]) -> String {
+    let name_width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    let mut table = String::new();
+    for r in results {
+        let latency = r
+            .latency
+            .map(|d| format!("{}ms", d.as_millis()))
+            .unwrap_or_else(|| "-".to_string());
+        table.push_str(&format!(
+            "{} {:<3}  {:<width$}  {:>7}  {}\n",
+            if r.ok { "✓" } else { "✗" },
+            r.kind,
+            r.name,
+            latency,
+            r.detail,
+            width = name_width
+        ));
+    }
+    table
+}
+
+// ============================================================================
+// Benchmark Command
+// ============================================================================
+
+async fn handle_benchmark(args: BenchmarkArgs) -> Result<()> {
+    let config = SumvoxConfig::load_from_home_cached()?;
+    let timeout = Duration::from_secs(args.timeout);
+
+    let outcomes = if args.tts {
+        let mut providers = Vec::new();
+        for provider_config in &config.tts.providers {
+            match tts::create_single_tts(
+                provider_config,
+                config.tts.default_voice.as_deref(),
+                config.tts.wpm,
+            ) {
+                Ok(p) => providers.push((provider_config.name.as_str(), p)),
+                Err(e) => eprintln!("Skipping {}: {}", provider_config.name, e),
+            }
+        }
+        let probes = providers.iter().map(|(name, provider)| async {
+            let result = benchmark::bench_tts(name, provider.as_ref(), &args.text, timeout).await;
+            (*name, result)
+        });
+        benchmark::run_concurrently(probes, args.max_concurrency).await
+    } else {
+        let llm_config = &config.llm;
+        let prompt = config.summarization.render_prompt(&args.text);
+        let http = HttpContext::new();
+        let mut providers = Vec::new();
+        for provider_config in &llm_config.providers {
+            let provider = match ProviderFactory::create_single_in(provider_config, &http) {
+                Ok(p) => p,
+                Err(e) => {
+                    eprintln!("Skipping {}: {}", provider_config.name, e);
+                    continue;
+                }
+            };
+            let request = GenerationRequest {
+                system_message: Some(config.summarization.effective_system_message()),
+                prompt: prompt.clone(),
+                max_tokens: llm_config.parameters.max_tokens,
+                temperature: llm_config.parameters.temperature,
+                disable_thinking: effective_disable_thinking(
+                    provider_config,
+                    &llm_config.parameters,
+                ),
+                stop: llm_config.parameters.stop.clone(),
+                reasoning_effort: llm_config.parameters.reasoning_effort.clone(),
+                ollama_system_mode: llm_config.ollama_system_mode,
+            };
+            providers.push((provider_config, provider, request));
+        }
+        let probes = providers
+            .iter()
+            .map(|(provider_config, provider, request)| async {
+                let result = benchmark::bench_llm(
+                    &provider_config.name,
+                    provider.as_ref(),
+                    &provider_config.model,
+                    request,
+                    &config.pricing,
+                    timeout,
+                )
+                .await;
+                (provider_config.name.as_str(), result)
+            });
+        benchmark::run_concurrently(probes, args.max_concurrency).await
+    };
+
+    let mut results = Vec::new();
+    for (name, result) in outcomes {
+        match result {
+            Some(result) => results.push(result),
+            None => eprintln!("Skipping {}: not available", name),
+        }
+    }
+
+    if results.is_empty() {
+        return Err(VoiceError::Config(
+            "No available providers to benchmark".into(),
+        ));
+    }
+
+    benchmark::sort_results(&mut results);
+    print!("{}", benchmark::format_table(&results));
+    Ok(())
+}
+
+// ============================================================================
+// Daemon Command - Speak lines from a FIFO
+// ============================================================================
+
+async fn handle_daemon(args: DaemonArgs) -> Result<()> {
+    let startup_config = SumvoxConfig::load_from_home_cached()?;
+
+    let tts_opts = TtsOptions {
+        engine: args.tts,
+        voice: args.voice,
+        rate: args.rate,
+        volume: args.volume,
+        device: None,
+        print_text: false,
+    };
+    let speak_timeout = Duration::from_secs(args.timeout);
+
+    let listen = daemon::run_fifo(&args.fifo, |line| {
+        let (startup_config, tts_opts) = (&startup_config, &tts_opts);
+        async move {
+            let config = event_config(startup_config, SumvoxConfig::load_from_home_cached);
+            let config = &*config;
+            // Take the playback lock per line so hooks and the daemon never talk over each other
+            let _lock = playback_lock(config).await?;
+            tracing::info!("sumvox daemon: {}", line);
+            let rotated = tts::voice_rotation::with_next_voice(config);
+            let config = rotated.as_ref().unwrap_or(config);
+            speak_text(config, tts_opts, &line, Some(speak_timeout)).await
+        }
+    });
+
+    // Dropping the listener on Ctrl-C ends any playback guards (status file,
+    // ducking) before exiting
+    tokio::select! {
+        result = listen => result,
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("sumvox daemon interrupted");
+            Ok(())
+        }
+    }
+}
+
+/// Config for one daemon line or watch summary. Loaded again per event, so
+/// with `--fresh-config` an edited file takes effect without a restart; a
+/// file that no longer loads keeps the startup config.
+fn event_config(
+    startup: &Arc<SumvoxConfig>,
+    load: impl FnOnce() -> Result<Arc<SumvoxConfig>>,
+) -> Arc<SumvoxConfig> {
+    load().unwrap_or_else(|e| {
+        tracing::warn!("Failed to reload config, keeping the startup one: {}", e);
+        Arc::clone(startup)
+    })
+}
+
+/// The cross-process playback lock, or None when queuing is disabled
+async fn playback_lock(config: &SumvoxConfig) -> Result<Option<queue::QueueLock>> {
+    let lock_timeout = config.hooks.claude_code.queue_timeout.unwrap_or(30);
+    if lock_timeout == 0 {
+        return Ok(None);
+    }
+    let queue = queue::NotificationQueue::new(Some(Duration::from_secs(lock_timeout)))?;
+    let lock = queue::QueueLock::acquire(&queue).await.ok();
+    if let Some(lock) = &lock {
+        lock.keep_gap(Duration::from_millis(config.hooks.playback.gap_ms))
+            .await;
+    }
+    Ok(lock)
+}
+
+// ============================================================================
+// Transcript Stats Command
+// ============================================================================
+
+async fn handle_transcript_stats(args: TranscriptStatsArgs) -> Result<()> {
+    // Use the configured turn options so the preview matches the Stop hook
+    let options = SumvoxConfig::load_from_home_cached()
+        .map(|config| config.summarization.turn_options())
+        .unwrap_or_default();
+    let stats = TranscriptReader::stats(&args.transcript, options).await?;
+    print!("{}", stats);
+    Ok(())
+}
+
+// ============================================================================
+// Watch Command
+// ============================================================================
+
+async fn handle_watch(args: WatchArgs) -> Result<()> {
+    let startup_config = SumvoxConfig::load_from_home_cached()?;
+    let tts_opts = TtsOptions {
+        engine: args.tts,
+        volume: args.volume,
+        ..Default::default()
+    };
+    let llm_opts = LlmOptions {
+        timeout: args.timeout,
+        ..Default::default()
+    };
+
+    let watcher = watch::TranscriptWatcher::new(&args.transcript).await?;
+    tracing::info!("Watching {:?}", args.transcript);
+
+    let run = watch::watch(
+        watcher,
+        Duration::from_secs(args.interval.max(1)),
+        Duration::from_secs(args.min_interval),
+        |texts| {
+            let (startup_config, tts_opts, llm_opts) = (&startup_config, &tts_opts, &llm_opts);
+            async move {
+                let config = event_config(startup_config, SumvoxConfig::load_from_home_cached);
+                let config = &*config;
+                let context = texts.join("\n\n");
+                let user_prompt = config.summarization.render_prompt(&context);
+                let system_message = Some(config.summarization.effective_system_message());
+                let summary =
+                    generate_summary(config, llm_opts, system_message, &user_prompt).await?;
+                if summary.is_empty() {
+                    return Ok(());
+                }
+
+                println!("{}", summary);
+                let _lock = playback_lock(config).await?;
+                let spoken = config.summarization.spoken_text(&summary);
+                let rotated = tts::voice_rotation::with_next_voice(config);
+                let config = rotated.as_ref().unwrap_or(config);
+                speak_text(config, tts_opts, &spoken, None).await
+            }
+        },
+    );
+
+    tokio::select! {
+        result = run => result,
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("sumvox watch interrupted");
+            Ok(())
+        }
+    }
+}
+
 // ============================================================================
 // Shared Utilities
 // ============================================================================
@@ -332,7 +1347,117 @@ async fn generate_summary(
     system_message: Option<String>,
     prompt: &str,
 ) -> Result<String> {
+    Ok(
+        generate_summary_outcome(config, llm_opts, system_message, prompt)
+            .await?
+            .map(|outcome| outcome.text)
+            .unwrap_or_default(),
+    )
+}
+
+/// Generated summary plus the provider that produced it and its usage
+#[derive(Debug, Clone)]
+struct SummaryOutcome {
+    text: String,
+    provider: String,
+    model: String,
+    input_tokens: u32,
+    output_tokens: u32,
+    cost_usd: f64,
+}
+
+impl SummaryOutcome {
+    /// Model and cost come from `response`: with model fallback, the model
+    /// that answered may not be the one configured first
+    fn new(
+        provider: &dyn llm::LlmProvider,
+        response: llm::GenerationResponse,
+        pricing: &HashMap<String, ModelPricing>,
+    ) -> Self {
+        Self {
+            cost_usd: llm::price_for(
+                pricing,
+                provider,
+                &response.model,
+                response.input_tokens,
+                response.output_tokens,
+            ),
+            text: response.text.trim().to_string(),
+            provider: provider.name().to_string(),
+            model: response.model,
+            input_tokens: response.input_tokens,
+            output_tokens: response.output_tokens,
+        }
+    }
+
+    /// Drop a "Here's a summary:"-style preamble from the text
+    fn strip_preamble(mut self, summarization: &SummarizationConfig) -> Self {
+        self.text = summarization.strip_preamble(&self.text);
+        self
+    }
+}
+
+/// A summary, or why no provider produced one
+type SummaryAttempt = std::result::Result<SummaryOutcome, String>;
+
+/// Machine-readable result of `sum --output-json`
+#[derive(Debug, serde::Serialize)]
+struct SumJsonOutput {
+    summary: String,
+    provider: Option<String>,
+    model: Option<String>,
+    input_tokens: u32,
+    output_tokens: u32,
+    cost_usd: f64,
+    /// True when there is no real summary (generation failed or came back empty)
+    fallback: bool,
+    /// Why generation failed, when it did
+    error: Option<String>,
+}
+
+impl SumJsonOutput {
+    /// An empty summary with null provider/model and the failure reason when
+    /// every provider failed
+    fn from_attempt(attempt: &SummaryAttempt) -> Self {
+        match attempt {
+            Ok(o) => Self {
+                summary: o.text.clone(),
+                provider: Some(o.provider.clone()),
+                model: Some(o.model.clone()),
+                input_tokens: o.input_tokens,
+                output_tokens: o.output_tokens,
+                cost_usd: o.cost_usd,
+                fallback: o.text.is_empty(),
+                error: None,
+            },
+            Err(reason) => Self {
+                summary: String::new(),
+                provider: None,
+                model: None,
+                input_tokens: 0,
+                output_tokens: 0,
+                cost_usd: 0.0,
+                fallback: true,
+                error: Some(reason.clone()),
+            },
+        }
+    }
+}
+
+/// Like `generate_summary`, but keeps provider and usage details.
+/// The inner `Err` carries why no provider produced a summary.
+async fn generate_summary_outcome(
+    config: &SumvoxConfig,
+    llm_opts: &LlmOptions,
+    system_message: Option<String>,
+    prompt: &str,
+) -> Result<SummaryAttempt> {
     let llm_config = &config.llm;
+    // One client for every provider tried below
+    let http = HttpContext::new();
+    // Scaled timeouts grow with the context
+    let input_tokens =
+        llm::estimate_tokens(prompt) + system_message.as_deref().map_or(0, llm::estimate_tokens);
 
     // Try providers with fallback
     if llm_opts.provider.is_some() || llm_opts.model.is_some() {
@@ -347,10 +1472,11 @@ async fn generate_summary(
             Some(name) => name,
             None => {
                 tracing::error!("No LLM provider specified and none configured");
-                return Ok(String::new());
+                return Ok(Err("No LLM provider specified and none configured".into()));
             }
         };
-        let timeout = Duration::from_secs(llm_opts.timeout);
+        let timeout =
+            Duration::from_secs(llm_config.scaled_timeout(llm_opts.timeout, input_tokens));
 
         // Find the matching provider config for model + per-provider override resolution
         let matching_provider = config
@@ -366,15 +1492,21 @@ async fn generate_summary(
         {
             Some(model) => model,
             None => {
-                tracing::error!(
+                let reason = format!(
                     "CLI provider '{}' not found in config and no --model provided",
                     provider_name
                 );
-                return Ok(String::new());
+                tracing::error!("{}", reason);
+                return Ok(Err(reason));
             }
         };
 
         let api_key = matching_provider.and_then(|p| p.get_api_key());
+        let model_name = &ProviderFactory::resolve_model_alias(
+            &ProviderFactory::named_config(provider_name, model_name, timeout, api_key.as_deref()),
+            &http,
+        )
+        .await;
 
         // Resolve effective disable_thinking: provider override > global
         let disable_thinking = matching_provider
@@ -387,45 +1519,81 @@ async fn generate_summary(
             max_tokens: llm_config.parameters.max_tokens,
             temperature: llm_config.parameters.temperature,
             disable_thinking,
+            stop: llm_config.parameters.stop.clone(),
+            reasoning_effort: llm_config.parameters.reasoning_effort.clone(),
+            ollama_system_mode: llm_config.ollama_system_mode,
         };
 
-        match ProviderFactory::create_by_name(
+        match ProviderFactory::create_by_name_in(
             provider_name,
             model_name,
             timeout,
             api_key.as_deref(),
+            &http,
         ) {
             Ok(provider) => {
                 if !provider.is_available() {
                     tracing::warn!("CLI provider {} not available", provider.name());
-                    return Ok(String::new());
+                    return Ok(Err(format!("{}: not available", provider.name())));
+                }
+
+                if let Some(estimate) =
+                    llm::over_call_cap(config, provider.as_ref(), model_name, &request)
+                {
+                    tracing::warn!(
+                        "Skipping {}: estimated cost ${:.4} exceeds cost.max_per_call_usd",
+                        provider.name(),
+                        estimate
+                    );
+                    let reason = format!(
+                        "LLM provider {} skipped: estimated cost ${:.4} exceeds cost.max_per_call_usd",
+                        provider.name(),
+                        estimate
+                    );
+                    crate::notify_log::record_last_error(&reason);
+                    return Ok(Err(reason));
                 }
 
-                match provider.generate(&request).await {
+                match llm::generate_within_budget(config, provider.as_ref(), &request).await {
                     Ok(response) => {
                         tracing::debug!(
                             "LLM usage: {} input tokens, {} output tokens",
                             response.input_tokens,
                             response.output_tokens
                         );
-                        return Ok(response.text.trim().to_string());
+                        return Ok(Ok(SummaryOutcome::new(
+                            provider.as_ref(),
+                            response,
+                            &config.pricing,
+                        )
+                        .strip_preamble(&config.summarization)));
                     }
                     Err(e) => {
                         tracing::error!("CLI provider {} failed: {}", provider.name(), e);
-                        return Ok(String::new());
+                        let reason = format!("LLM provider {} failed: {}", provider.name(), e);
+                        crate::notify_log::record_last_error(&reason);
+                        return Ok(Err(reason));
                     }
                 }
             }
             Err(e) => {
                 tracing::error!("Failed to create CLI provider {}: {}", provider_name, e);
-                return Ok(String::new());
+                let reason = format!("LLM provider {} failed: {}", provider_name, e);
+                crate::notify_log::record_last_error(&reason);
+                return Ok(Err(reason));
             }
         }
     }
 
     // Try each provider in config order until one succeeds.
     // Build a per-provider GenerationRequest so each gets its own effective disable_thinking.
+    let mut failures = Vec::new();
     for provider_config in &llm_config.providers {
+        let provider_config = &LlmProviderConfig {
+            model: ProviderFactory::resolve_model_alias(provider_config, &http).await,
+            timeout: llm_config.scaled_timeout(provider_config.timeout, input_tokens),
+            ..provider_config.clone()
+        };
         let disable_thinking = effective_disable_thinking(provider_config, &llm_config.parameters);
 
         let request = GenerationRequest {
@@ -434,12 +1602,32 @@ async fn generate_summary(
             max_tokens: llm_config.parameters.max_tokens,
             temperature: llm_config.parameters.temperature,
             disable_thinking,
+            stop: llm_config.parameters.stop.clone(),
+            reasoning_effort: llm_config.parameters.reasoning_effort.clone(),
+            ollama_system_mode: llm_config.ollama_system_mode,
         };
 
-        match ProviderFactory::create_single(provider_config) {
+        match ProviderFactory::create_single_in(provider_config, &http) {
             Ok(provider) => {
                 if !provider.is_available() {
                     tracing::debug!("Provider {} not available, trying next", provider.name());
+                    failures.push(format!("{}: not available", provider.name()));
+                    continue;
+                }
+
+                if let Some(estimate) =
+                    llm::over_call_cap(config, provider.as_ref(), &provider_config.model, &request)
+                {
+                    tracing::warn!(
+                        "Skipping {}: estimated cost ${:.4} exceeds cost.max_per_call_usd",
+                        provider.name(),
+                        estimate
+                    );
+                    failures.push(format!(
+                        "{}: estimated cost ${:.4} over cap",
+                        provider.name(),
+                        estimate
+                    ));
                     continue;
                 }
 
@@ -449,7 +1637,7 @@ async fn generate_summary(
                     provider_config.model
                 );
 
-                match provider.generate(&request).await {
+                match llm::generate_within_budget(config, provider.as_ref(), &request).await {
                     Ok(response) => {
                         tracing::info!("Provider {} succeeded", provider.name());
                         tracing::debug!(
@@ -458,16 +1646,23 @@ async fn generate_summary(
                             response.output_tokens
                         );
 
-                        return Ok(response.text.trim().to_string());
+                        return Ok(Ok(SummaryOutcome::new(
+                            provider.as_ref(),
+                            response,
+                            &config.pricing,
+                        )
+                        .strip_preamble(&config.summarization)));
                     }
                     Err(e) => {
                         tracing::warn!("Provider {} failed: {}, trying next", provider.name(), e);
+                        failures.push(format!("{}: {}", provider.name(), e));
                         continue;
                     }
                 }
             }
             Err(e) => {
                 tracing::debug!("Failed to create provider {}: {}", provider_config.name, e);
+                failures.push(format!("{}: {}", provider_config.name, e));
                 continue;
             }
         }
@@ -475,21 +1670,60 @@ async fn generate_summary(
 
     // All providers failed
     tracing::error!("All LLM providers failed");
-    Ok(String::new())
+    let reason = crate::notify_log::failure_reason("LLM", &failures);
+    crate::notify_log::record_last_error(&reason);
+    Ok(Err(reason))
 }
 
-/// Speak text using TTS
-async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) -> Result<()> {
+/// Speak authored SSML verbatim. Auto mode picks the first configured provider
+/// that accepts SSML; an explicit engine that can't do SSML is an error.
+async fn speak_ssml(config: &SumvoxConfig, tts_opts: &TtsOptions, ssml: &str) -> Result<()> {
     let tts_engine = tts_opts.engine.parse().unwrap_or(TtsEngine::Auto);
+
+    let provider = match tts_engine {
+        TtsEngine::Auto => config
+            .tts
+            .providers
+            .iter()
+            .filter_map(|p| {
+                create_single_tts(p, config.tts.default_voice.as_deref(), config.tts.wpm).ok()
+            })
+            .find(|p| p.is_available() && p.supports_ssml())
+            .ok_or_else(|| VoiceError::Voice("No configured TTS provider supports SSML".into()))?,
+        _ => {
+            let provider = select_tts_provider(config, tts_opts, tts_engine)?;
+            if !provider.supports_ssml() {
+                return Err(VoiceError::Voice(format!(
+                    "TTS engine {} does not support SSML",
+                    tts_opts.engine
+                )));
+            }
+            provider
+        }
+    };
+
+    provider.speak_ssml(ssml).await?;
+    Ok(())
+}
+
+/// Create the TTS provider for the requested engine: CLI override or config fallback chain
+fn select_tts_provider(
+    config: &SumvoxConfig,
+    tts_opts: &TtsOptions,
+    tts_engine: TtsEngine,
+) -> Result<Box<dyn TtsProvider>> {
     // The raw engine name disambiguates entries that share one TtsEngine
     // (cloud_tts vs gemini_tts); resolve_tts_provider matches it exactly first.
     let engine_name = tts_opts.engine.to_lowercase();
 
-    // Create TTS provider: CLI override or config fallback chain
-    let provider: Box<dyn TtsProvider> = match tts_engine {
+    match tts_engine {
         TtsEngine::Auto => {
             // Use config fallback chain
-            create_tts_from_config(&config.tts.providers)?
+            create_tts_from_config(
+                &config.tts.providers,
+                config.tts.default_voice.as_deref(),
+                config.tts.wpm,
+            )
         }
         // For an explicitly selected engine, `--tts X` overrides which configured
         // provider to use; all attributes are sourced from that config entry, with
@@ -500,14 +1734,18 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
-        )?,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
+        ),
         TtsEngine::Google => resolve_tts_provider(
             &config.tts.providers,
             &["google", "google_tts", "gcloud", "gemini"],
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
-        )?,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
+        ),
         TtsEngine::CloudTts => resolve_tts_provider(
             &config.tts.providers,
             &[
@@ -520,39 +1758,68 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
-        )?,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
+        ),
         TtsEngine::AudioFile => resolve_tts_provider(
             &config.tts.providers,
             &["audio_file", "audio", "file"],
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
-        )?,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
+        ),
         TtsEngine::Xai => resolve_tts_provider(
             &config.tts.providers,
             &["xai", "xai_tts", "grok"],
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
-        )?,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
+        ),
         TtsEngine::ElevenLabs => resolve_tts_provider(
             &config.tts.providers,
             &["elevenlabs", "eleven_labs", "11labs"],
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
-        )?,
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
+        ),
         TtsEngine::OpenAi => resolve_tts_provider(
             &config.tts.providers,
             &["openai", "openai_tts"],
             tts_opts.voice.as_deref(),
             tts_opts.rate,
             tts_opts.volume,
-        )?,
-    };
+            config.tts.default_voice.as_deref(),
+            config.tts.wpm,
+        ),
+    }
+}
+
+/// Speak text using TTS
+///
+/// `speak_timeout` bounds each provider's speak call; in Auto mode a timed-out
+/// provider falls through to the next one.
+async fn speak_text(
+    config: &SumvoxConfig,
+    tts_opts: &TtsOptions,
+    text: &str,
+    speak_timeout: Option<Duration>,
+) -> Result<()> {
+    let text = &tts::paths::speakable_paths(config, text);
+    let text = &tts::apply_pronunciations(text, &config.tts.pronunciations);
+    let _status =
+        audio::status_file::StatusFileGuard::start(config.tts.status_file.as_deref(), text);
+    let tts_engine = tts_opts.engine.parse().unwrap_or(TtsEngine::Auto);
+    let provider = select_tts_provider(config, tts_opts, tts_engine)?;
 
     if !provider.is_available() {
-        tracing::warn!("TTS provider {} not available", provider.name());
+        let failure = format!("{}: not available", provider.name());
+        desktop_notification::report_silent_playback(&config.tts, text, &[failure]);
         return Ok(());
     }
 
@@ -561,47 +1828,97 @@ async fn speak_text(config: &SumvoxConfig, tts_opts: &TtsOptions, text: &str) ->
     if cost > 0.0 {
         tracing::info!("TTS cost estimate: ${:.6} for {} chars", cost, text.len());
     }
+    if let Some(warning) =
+        config
+            .tts
+            .char_limit_warning(provider.name(), text.chars().count(), cost)
+    {
+        tracing::warn!("{}", warning);
+        if config.tts.confirm_over_chars && std::io::stdin().is_terminal() {
+            eprintln!("Warning: {}", warning);
+            if !wizard::Prompter::confirm(&mut wizard::TerminalPrompter, "Speak it anyway?", false)?
+            {
+                tracing::info!("Speaking cancelled at the character limit prompt");
+                return Ok(());
+            }
+        }
+    }
+
+    // Other audio stays ducked until this function returns
+    let _ducked = audio::ducking::DuckGuard::new(config.tts.duck_other_audio);
+    let _device = audio::device::OutputDeviceGuard::select(audio::device::resolve_output_device(
+        tts_opts.device.as_deref(),
+        config.tts.output_device.as_deref(),
+    ));
+    let _padding =
+        audio::padding::SilencePaddingGuard::set(config.tts.preroll_ms, config.tts.postroll_ms);
+    audio::playback_limit::set_max_concurrent_playbacks(config.tts.max_concurrent_playbacks);
 
     // Speak with error handling and fallback for Auto mode
-    match tts_engine {
+    let mut failures = Vec::new();
+    let played = match tts_engine {
         TtsEngine::Auto => {
             // For Auto mode, try all providers in config order
-            speak_with_provider_fallback(&config.tts.providers, text).await
+            speak_with_provider_fallback(&config.tts, text, speak_timeout, &mut failures).await
         }
         _ => {
             // Single provider mode - just try once
-            match provider.speak(text).await {
+            match speak_once(provider.as_ref(), text, speak_timeout).await {
                 Ok(_) => {
                     tracing::debug!("TTS playback completed");
-                    Ok(())
+                    true
                 }
                 Err(e) => {
-                    tracing::warn!("TTS playback failed: {}. Notification will be silent.", e);
-                    Ok(())
+                    failures.push(format!("{}: {}", provider.name(), e));
+                    false
                 }
             }
         }
+    };
+
+    if !played {
+        desktop_notification::report_silent_playback(&config.tts, text, &failures);
     }
+    Ok(())
 }
 
-/// Try TTS providers in order with automatic runtime fallback
-async fn speak_with_provider_fallback(providers: &[TtsProviderConfig], text: &str) -> Result<()> {
-    let mut last_error = None;
+/// Speak with a single provider, bounded by `speak_timeout` when set
+async fn speak_once(
+    provider: &dyn TtsProvider,
+    text: &str,
+    speak_timeout: Option<Duration>,
+) -> Result<bool> {
+    match speak_timeout {
+        Some(timeout) => speak_with_timeout(provider, text, timeout).await,
+        None => provider.speak(text).await,
+    }
+}
 
-    for provider_config in providers {
+/// Try TTS providers in order with automatic runtime fallback
+/// (`failures` collects why each one failed; the caller reports it)
+async fn speak_with_provider_fallback(
+    tts: &TtsConfig,
+    text: &str,
+    speak_timeout: Option<Duration>,
+    failures: &mut Vec<String>,
+) -> bool {
+    let mut providers = Vec::new();
+
+    for provider_config in &tts.providers {
         // Try to create provider
-        let provider = match create_single_tts(provider_config) {
-            Ok(p) => p,
-            Err(e) => {
-                tracing::debug!(
-                    "Failed to create TTS provider {}: {}",
-                    provider_config.name,
-                    e
-                );
-                last_error = Some(format!("{}: {}", provider_config.name, e));
-                continue;
-            }
-        };
+        let provider =
+            match create_single_tts(provider_config, tts.default_voice.as_deref(), tts.wpm) {
+                Ok(p) => p,
+                Err(e) => {
+                    tracing::debug!(
+                        "Failed to create TTS provider {}: {}",
+                        provider_config.name,
+                        e
+                    );
+                    failures.push(format!("{}: {}", provider_config.name, e));
+                    continue;
+                }
+            };
 
         // Check availability
         if !provider.is_available() {
@@ -609,58 +1926,137 @@ async fn speak_with_provider_fallback(providers: &[TtsProviderConfig], text: &st
                 "TTS provider {} not available, trying next",
                 provider.name()
             );
-            last_error = Some(format!("{}: not available", provider.name()));
+            failures.push(format!("{}: not available", provider.name()));
             continue;
         }
 
-        // Log selected provider
-        tracing::info!(
-            "Using TTS provider: {} (voice: {})",
+        tracing::debug!(
+            "TTS provider {} ready (voice: {})",
             provider_config.name,
             provider_config.voice.as_deref().unwrap_or("default")
         );
+        providers.push(provider);
+    }
 
-        // Estimate and log cost for cloud providers
-        let cost = provider.estimate_cost(text.len());
-        if cost > 0.0 {
-            tracing::info!("TTS cost estimate: ${:.6} for {} chars", cost, text.len());
-        }
+    let announced = tts
+        .announce_fallback
+        .then(|| tts::with_fallback_marker(text));
+    tts::speak_in_order(
+        &providers,
+        text,
+        announced.as_deref(),
+        tts.fallback_policy,
+        failures,
+        |provider, text| speak_once(provider, text, speak_timeout),
+    )
+    .await
+}
 
-        // Try to speak
-        match provider.speak(text).await {
-            Ok(_) => {
-                tracing::debug!("TTS playback completed with {}", provider.name());
-                return Ok(());
-            }
-            Err(e) => {
-                tracing::warn!(
-                    "TTS provider {} failed: {}, trying next provider",
-                    provider.name(),
-                    e
-                );
-                last_error = Some(format!("{}: {}", provider.name(), e));
-                continue;
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use config::{LlmAuth, LlmParameters, LlmProviderConfig};
+    use llm::mock::{RecordingProvider, MOCK_MODEL};
+
+    #[tokio::test]
+    async fn test_budget_override_replaces_daily_limit() {
+        let usage = tempfile::NamedTempFile::new().unwrap();
+        let tracker = llm::cost_tracker::CostTracker::new(usage.path());
+        tracker.record_usage("model", 1000, 500, 2.0).await.unwrap();
+
+        let mut config = SumvoxConfig::default();
+        config.cost.daily_limit_usd = Some(1.0);
+        assert!(!llm::within_daily_budget(&config, &tracker).await);
+
+        // No override leaves the configured limit alone
+        apply_budget_override(&mut config, None);
+        assert_eq!(config.cost.daily_limit_usd, Some(1.0));
+
+        apply_budget_override(&mut config, Some(5.0));
+        assert_eq!(config.cost.daily_limit_usd, Some(5.0));
+        assert!(llm::within_daily_budget(&config, &tracker).await);
+
+        // Without any limit there is nothing to enforce
+        config.cost.daily_limit_usd = None;
+        assert!(llm::within_daily_budget(&config, &tracker).await);
     }
 
-    // All providers failed
-    if let Some(err) = last_error {
-        tracing::warn!(
-            "All TTS providers failed. Last error: {}. Notification will be silent.",
-            err
+    #[test]
+    fn test_doctor_json_mixed_report() {
+        let mut config = recommended_config();
+        config.llm.providers.clear();
+        let checks = doctor_checks(
+            Ok(config),
+            Some((
+                "2026-10-16 09:30:00".into(),
+                "all TTS providers failed".into(),
+            )),
+        );
+
+        let json = serde_json::to_value(&checks).unwrap();
+        assert_eq!(
+            json[0],
+            serde_json::json!({ "check": "config", "status": "pass", "detail": "loaded" })
+        );
+        assert_eq!(
+            json[1],
+            serde_json::json!({
+                "check": "llm_providers",
+                "status": "fail",
+                "detail": "no provider has credentials"
+            })
+        );
+        assert_eq!(json[2]["check"], "tts_providers");
+        assert_eq!(json[2]["status"], "pass");
+        assert!(json[2]["detail"].as_str().unwrap().starts_with("macos"));
+        assert_eq!(
+            json[3],
+            serde_json::json!({
+                "check": "last_failure",
+                "status": "warn",
+                "detail": "2026-10-16 09:30:00  all TTS providers failed"
+            })
         );
-    } else {
-        tracing::warn!("No TTS providers available. Notification will be silent.");
     }
 
-    Ok(())
-}
+    #[test]
+    fn test_doctor_stops_at_broken_config() {
+        let checks = doctor_checks(Err(VoiceError::Config("bad toml".into())), None);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].check, "config");
+        assert_eq!(checks[0].status, CheckStatus::Fail);
+        assert!(checks[0].detail.contains("bad toml"));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use config::{LlmParameters, LlmProviderConfig};
+    #[test]
+    fn test_no_subcommand_piped_stdin_is_hook_mode() {
+        let fallback = no_subcommand_fallback(false, || panic!("fallback read in hook mode"));
+        assert_eq!(fallback, None);
+    }
+
+    #[test]
+    fn test_no_subcommand_terminal_uses_fallback() {
+        for configured in [
+            TerminalFallback::Error,
+            TerminalFallback::QuickStart,
+            TerminalFallback::Wizard,
+        ] {
+            assert_eq!(
+                no_subcommand_fallback(true, || configured),
+                Some(configured)
+            );
+        }
+        assert_eq!(
+            TerminalFallback::from_name("quick_start"),
+            Some(TerminalFallback::QuickStart)
+        );
+        assert_eq!(TerminalFallback::from_name("help"), None);
+        assert_eq!(
+            SumvoxConfig::default().hooks.terminal_fallback,
+            TerminalFallback::Error
+        );
+    }
 
     // ── A1: per-provider disable_thinking in main.rs generate_summary ────
 
@@ -683,6 +2079,8 @@ mod tests {
             max_tokens: 100,
             temperature: 0.3,
             disable_thinking: false, // global default: false
+            stop: None,
+            reasoning_effort: None,
         };
         let providers = [LlmProviderConfig {
             name: "openai".to_string(),
@@ -691,6 +2089,9 @@ mod tests {
             base_url: None,
             timeout: 10,
             disable_thinking: Some(true), // per-provider override: true
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: Vec::new(),
         }];
 
         let result = resolve_disable_thinking_for_provider("openai", &providers, &params);
@@ -706,6 +2107,8 @@ mod tests {
             max_tokens: 100,
             temperature: 0.3,
             disable_thinking: true, // global: true
+            stop: None,
+            reasoning_effort: None,
         };
         let providers = [LlmProviderConfig {
             name: "google".to_string(),
@@ -714,6 +2117,9 @@ mod tests {
             base_url: None,
             timeout: 10,
             disable_thinking: None, // no override → falls back to global
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: Vec::new(),
         }];
 
         let result = resolve_disable_thinking_for_provider("google", &providers, &params);
@@ -729,6 +2135,8 @@ mod tests {
             max_tokens: 100,
             temperature: 0.3,
             disable_thinking: true,
+            stop: None,
+            reasoning_effort: None,
         };
         let providers: [LlmProviderConfig; 0] = []; // no matching provider
 
@@ -746,6 +2154,8 @@ mod tests {
             max_tokens: 100,
             temperature: 0.3,
             disable_thinking: false, // global: false
+            stop: None,
+            reasoning_effort: None,
         };
         let providers = [
             LlmProviderConfig {
@@ -755,6 +2165,9 @@ mod tests {
                 base_url: None,
                 timeout: 10,
                 disable_thinking: None, // inherits global: false
+                auth: LlmAuth::ApiKey,
+                region: None,
+                models: Vec::new(),
             },
             LlmProviderConfig {
                 name: "openai".to_string(),
@@ -763,6 +2176,9 @@ mod tests {
                 base_url: None,
                 timeout: 10,
                 disable_thinking: Some(true), // override: true
+                auth: LlmAuth::ApiKey,
+                region: None,
+                models: Vec::new(),
             },
         ];
 
@@ -782,11 +2198,16 @@ mod tests {
     #[test]
     fn test_tts_options_from_say_args() {
         let args = SayArgs {
-            text: "Hello".to_string(),
+            text: Some("Hello".to_string()),
+            ssml_file: None,
             tts: "macos".to_string(),
             voice: Some("Tingting".to_string()),
-            rate: 200,
+            rate: Some(200),
             volume: Some(80),
+            timeout: 60,
+            split_sentences: false,
+            async_mode: false,
+            test_tone: false,
         };
 
         let opts = TtsOptions {
@@ -794,11 +2215,354 @@ mod tests {
             voice: args.voice,
             rate: args.rate,
             volume: args.volume,
+            device: None,
+            print_text: false,
         };
 
         assert_eq!(opts.engine, "macos");
         assert_eq!(opts.voice, Some("Tingting".to_string()));
-        assert_eq!(opts.rate, 200);
+        assert_eq!(opts.rate, Some(200));
         assert_eq!(opts.volume, Some(80));
     }
+
+    fn priced_mock() -> RecordingProvider {
+        RecordingProvider::new().named("gemini").priced(0.001)
+    }
+
+    #[test]
+    fn test_sum_json_output_shape() {
+        let response = llm::GenerationResponse {
+            text: "  Tests pass now.\n".to_string(),
+            input_tokens: 120,
+            output_tokens: 8,
+            model: "gemini-2.5-flash".to_string(),
+        };
+        let outcome = SummaryOutcome::new(&priced_mock(), response, &HashMap::new());
+
+        let json = serde_json::to_value(SumJsonOutput::from_attempt(&Ok(outcome))).unwrap();
+        assert_eq!(json["summary"], "Tests pass now.");
+        assert_eq!(json["provider"], "gemini");
+        assert_eq!(json["model"], "gemini-2.5-flash");
+        assert_eq!(json["input_tokens"], 120);
+        assert_eq!(json["output_tokens"], 8);
+        assert!((json["cost_usd"].as_f64().unwrap() - 0.128).abs() < 1e-9);
+        assert_eq!(json["fallback"], false);
+        assert!(json["error"].is_null());
+    }
+
+    #[test]
+    fn test_sum_json_output_reports_the_model_that_answered() {
+        // Configured gemini-2.5-flash first, but the fallback model replied
+        let response = llm::GenerationResponse {
+            text: "Done.".to_string(),
+            input_tokens: 1000,
+            output_tokens: 0,
+            model: "gemini-2.0-flash".to_string(),
+        };
+        let pricing = HashMap::from([(
+            "gemini-2.0-flash".to_string(),
+            ModelPricing {
+                input_per_1k: 0.1,
+                output_per_1k: 0.4,
+            },
+        )]);
+        let outcome = SummaryOutcome::new(&priced_mock(), response, &pricing);
+
+        let json = serde_json::to_value(SumJsonOutput::from_attempt(&Ok(outcome))).unwrap();
+        assert_eq!(json["model"], "gemini-2.0-flash");
+        assert!((json["cost_usd"].as_f64().unwrap() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sum_json_output_without_summary() {
+        let attempt: SummaryAttempt = Err("all LLM providers failed: gemini: 503".to_string());
+        let json = serde_json::to_value(SumJsonOutput::from_attempt(&attempt)).unwrap();
+        assert_eq!(json["summary"], "");
+        assert!(json["provider"].is_null());
+        assert_eq!(json["input_tokens"], 0);
+        assert_eq!(json["cost_usd"], 0.0);
+        assert_eq!(json["fallback"], true);
+        assert_eq!(json["error"], "all LLM providers failed: gemini: 503");
+    }
+
+    #[tokio::test]
+    async fn test_unpulled_ollama_model_skips_to_next_provider() {
+        let mut missing_server = mockito::Server::new_async().await;
+        let missing = missing_server
+            .mock("POST", "/api/generate")
+            .with_status(404)
+            .with_body(r#"{"error":"model \"qwen3\" not found, try pulling it first"}"#)
+            .create_async()
+            .await;
+        let mut local_server = mockito::Server::new_async().await;
+        let local = local_server
+            .mock("POST", "/api/generate")
+            .with_status(200)
+            .with_body(r#"{"model":"llama3.2","response":"Tests pass.","done":true}"#)
+            .create_async()
+            .await;
+
+        let ollama = |model: &str, base_url: String| LlmProviderConfig {
+            name: "ollama".to_string(),
+            model: model.to_string(),
+            api_key: None,
+            base_url: Some(base_url),
+            timeout: 5,
+            disable_thinking: None,
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: Vec::new(),
+        };
+        let mut config = SumvoxConfig::default();
+        config.llm.providers = vec![
+            ollama("qwen3", missing_server.url()),
+            ollama("llama3.2", local_server.url()),
+        ];
+        let llm_opts = LlmOptions {
+            provider: None,
+            model: None,
+            timeout: 5,
+            budget_override: None,
+        };
+
+        let outcome = generate_summary_outcome(&config, &llm_opts, None, "prompt")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.text, "Tests pass.");
+        assert_eq!(outcome.model, "llama3.2");
+        missing.assert_async().await;
+        local.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_failed_generation_captures_error() {
+        std::env::remove_var("XAI_API_KEY");
+        let mut config = SumvoxConfig::default();
+        config.llm.providers = vec![LlmProviderConfig {
+            name: "xai".to_string(),
+            model: "grok-4".to_string(),
+            api_key: None,
+            base_url: None,
+            timeout: 5,
+            disable_thinking: None,
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: Vec::new(),
+        }];
+        let llm_opts = LlmOptions {
+            provider: None,
+            model: None,
+            timeout: 5,
+            budget_override: None,
+        };
+
+        let attempt = generate_summary_outcome(&config, &llm_opts, None, "prompt")
+            .await
+            .unwrap();
+        let json = serde_json::to_value(SumJsonOutput::from_attempt(&attempt)).unwrap();
+        assert_eq!(json["fallback"], true);
+        let error = json["error"].as_str().unwrap();
+        assert!(error.starts_with("all LLM providers failed"), "{}", error);
+        assert!(error.contains("xai"), "{}", error);
+    }
+
+    #[test]
+    fn test_async_say_args_drop_async_and_keep_options() {
+        let cli = Cli::try_parse_from([
+            "sumvox",
+            "say",
+            "--async",
+            "--tts",
+            "google",
+            "--voice",
+            "Aoede",
+            "--volume",
+            "40",
+            "--",
+            "-n retries left",
+        ])
+        .unwrap();
+        let Some(Commands::Say(args)) = cli.command else {
+            panic!("Expected Say command");
+        };
+
+        let child = async_say_args(&args);
+        assert!(!child.contains(&"--async".to_string()));
+
+        // The child's arguments parse back to the same request, in the foreground
+        let reparsed =
+            Cli::try_parse_from(std::iter::once("sumvox".to_string()).chain(child)).unwrap();
+        let Some(Commands::Say(child_args)) = reparsed.command else {
+            panic!("Expected Say command");
+        };
+        assert!(!child_args.async_mode);
+        assert_eq!(child_args.text.as_deref(), Some("-n retries left"));
+        assert_eq!(child_args.tts, "google");
+        assert_eq!(child_args.voice.as_deref(), Some("Aoede"));
+        assert_eq!(child_args.volume, Some(40));
+        assert_eq!(child_args.rate, args.rate);
+        assert_eq!(child_args.timeout, args.timeout);
+    }
+
+    fn probe_mock() -> RecordingProvider {
+        RecordingProvider::new().replying("OK").usage(4, 1)
+    }
+
+    #[tokio::test]
+    async fn test_probe_llm_success_and_failure() {
+        let timeout = Duration::from_secs(1);
+
+        let ok = probe_llm("google", &probe_mock(), timeout).await;
+        assert!(ok.ok);
+        assert!(ok.latency.is_some());
+        assert_eq!(ok.detail, MOCK_MODEL);
+
+        let failed = probe_llm("openai", &probe_mock().failing("401 Unauthorized"), timeout).await;
+        assert!(!failed.ok);
+        assert!(failed.detail.contains("401 Unauthorized"));
+
+        // Unavailable providers are reported without a request
+        let missing = probe_llm("anthropic", &probe_mock().unavailable(), timeout).await;
+        assert!(!missing.ok);
+        assert_eq!(missing.latency, None);
+    }
+
+    #[tokio::test]
+    async fn test_probe_llm_times_out() {
+        let slow = probe_mock().slow(Duration::from_secs(5));
+        let result = probe_llm("ollama", &slow, Duration::from_millis(20)).await;
+        assert!(!result.ok);
+        assert!(result.detail.contains("timed out"));
+    }
+
+    #[test]
+    fn test_probe_tts_and_table() {
+        let macos = tts::MacOsTtsProvider::new(None, 200, 100);
+        let tts_result = probe_tts("macos", &macos);
+
+        let results = vec![
+            ProbeResult {
+                kind: "llm",
+                name: "google".to_string(),
+                ok: true,
+                latency: Some(Duration::from_millis(412)),
+                detail: "gemini-2.5-flash".to_string(),
+            },
+            ProbeResult::failed("llm", "openai", "401 Unauthorized".to_string()),
+            tts_result.clone(),
+        ];
+        let table = format_probe_table(&results);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "✓ llm  google    412ms  gemini-2.5-flash");
+        assert_eq!(lines[1], "✗ llm  openai        -  401 Unauthorized");
+        assert!(lines[2].contains(" tts  macos "));
+        assert_eq!(tts_result.latency, None);
+    }
+
+    #[test]
+    fn test_credentials_set_reads_stdin_key() {
+        let mut input = std::io::Cursor::new("sk-test-123\nignored second line\n");
+        let key = validate_api_key(&read_key_line(&mut input).unwrap()).unwrap();
+        assert_eq!(key, "sk-test-123");
+
+        let mut config = SumvoxConfig::default();
+        assert_eq!(config.set_api_key("GOOGLE", &key), 2); // LLM and TTS entries
+        assert_eq!(config.llm.providers[0].get_api_keys(), vec!["sk-test-123"]);
+        assert_eq!(config.set_api_key("nonexistent", &key), 0);
+    }
+
+    #[test]
+    fn test_credentials_set_rejects_empty_key() {
+        for input in ["", "\n", "   \n"] {
+            let key = read_key_line(&mut std::io::Cursor::new(input)).unwrap();
+            assert!(validate_api_key(&key).is_err());
+        }
+    }
+
+    #[test]
+    fn test_credentials_list_json_structure() {
+        // The xai entry only has an unexpanded placeholder, so it counts as unconfigured
+        std::env::remove_var("XAI_API_KEY");
+        let config: SumvoxConfig = serde_json::from_str(
+            r#"{
+                "llm": {"providers": [
+                    {"name": "google", "model": "gemini-2.5-flash", "api_key": "g-key"},
+                    {"name": "xai", "model": "grok-3-mini", "api_key": "${XAI_API_KEY}"},
+                    {"name": "ollama", "model": "llama3.2"}
+                ]},
+                "tts": {"providers": [
+                    {"name": "macos"},
+                    {"name": "cloud_tts", "api_key": "c-key"},
+                    {"name": "audio_file"}
+                ]}
+            }"#,
+        )
+        .unwrap();
+
+        let mut statuses = list_llm_providers(&config);
+        statuses.extend(list_tts_providers(&config));
+        let json = serde_json::to_value(&statuses).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"kind": "llm", "name": "google", "configured": true},
+                {"kind": "llm", "name": "xai", "configured": false},
+                {"kind": "llm", "name": "ollama", "configured": true},
+                {"kind": "tts", "name": "macos", "configured": true},
+                {"kind": "tts", "name": "cloud_tts", "configured": true},
+                {"kind": "tts", "name": "audio_file", "configured": false}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_capability_table_layout() {
+        let table = capability_table(
+            &["TTS", "ssml", "streaming"],
+            [
+                ("macos", vec![false, false]),
+                ("cloud_tts", vec![true, false]),
+            ],
+        );
+        assert_eq!(
+            table,
+            "TTS        ssml  streaming\n\
+             macos      ✗     ✗\n\
+             cloud_tts  ✓     ✗\n"
+        );
+    }
+
+    #[test]
+    fn test_event_config_reloads_per_event() {
+        let startup = Arc::new(SumvoxConfig::default());
+        let cache = config::ConfigCache::new();
+
+        // Cached: every event shares the one parsed config
+        let first = event_config(&startup, || {
+            cache.get_or_load(|| Ok(SumvoxConfig::default()))
+        });
+        let second = event_config(&startup, || {
+            cache.get_or_load(|| panic!("cached config should be reused"))
+        });
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // --fresh-config: an edit shows up on the next event
+        cache.set_fresh(true);
+        let edited = event_config(&startup, || {
+            cache.get_or_load(|| {
+                let mut config = SumvoxConfig::default();
+                config.tts.global_dedup = true;
+                Ok(config)
+            })
+        });
+        assert!(edited.tts.global_dedup);
+
+        // A file that no longer loads keeps the startup config
+        let broken = event_config(&startup, || {
+            cache.get_or_load(|| Err(VoiceError::Config("invalid TOML".into())))
+        });
+        assert!(Arc::ptr_eq(&broken, &startup));
+    }
 }
@@ -59,8 +59,86 @@ pub fn set_now_playing(path: &Path) {
     let _ = fs::write(dir.join("now_playing"), path.to_string_lossy().as_bytes());
 }
 
+/// Record why the last voice report went silent, for `sumvox why-silent`.
+/// Stored as a single "RFC3339\treason" line in ~/.config/sumvox/last_error.
+/// Best-effort, like `record`.
+pub fn record_last_error(reason: &str) {
+    let Some(dir) = config_dir() else { return };
+    let _ = fs::create_dir_all(&dir);
+    let _ = write_last_error(&dir.join("last_error"), reason);
+}
+
+/// The last recorded failure as (RFC3339 timestamp, reason)
+pub fn last_error() -> Option<(String, String)> {
+    read_last_error(&config_dir()?.join("last_error"))
+}
+
+fn write_last_error(path: &Path, reason: &str) -> std::io::Result<()> {
+    let line = format!(
+        "{}\t{}\n",
+        chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        reason.replace(['\n', '\r'], " ")
+    );
+    fs::write(path, line)
+}
+
+fn read_last_error(path: &Path) -> Option<(String, String)> {
+    let content = fs::read_to_string(path).ok()?;
+    let (at, reason) = content.trim_end().split_once('\t')?;
+    Some((at.to_string(), reason.to_string()))
+}
+
+/// Add a notification_type to the observed set in
+/// ~/.config/sumvox/notification_types (one per line, sorted).
+/// Best-effort, like `record`.
+pub fn record_notification_type(notification_type: &str) {
+    let Some(dir) = config_dir() else { return };
+    let _ = fs::create_dir_all(&dir);
+    let _ = add_notification_type(&dir.join("notification_types"), notification_type);
+}
+
+/// Every notification_type recorded so far, sorted
+pub fn observed_notification_types() -> Vec<String> {
+    config_dir()
+        .map(|d| read_notification_types(&d.join("notification_types")))
+        .unwrap_or_default()
+}
+
+fn add_notification_type(path: &Path, notification_type: &str) -> std::io::Result<()> {
+    let notification_type = notification_type.trim();
+    let mut types = read_notification_types(path);
+    if notification_type.is_empty() || types.iter().any(|t| t == notification_type) {
+        return Ok(());
+    }
+    types.push(notification_type.to_string());
+    types.sort();
+    fs::write(path, types.join("\n") + "\n")
+}
+
+fn read_notification_types(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Summarize a failed provider chain, e.g.
+/// "all TTS providers failed: google: 403; macos: not available"
+pub fn failure_reason(kind: &str, failures: &[String]) -> String {
+    if failures.is_empty() {
+        format!("no {} providers configured", kind)
+    } else {
+        format!("all {} providers failed: {}", kind, failures.join("; "))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn record_line_is_single_line() {
         // The invariant the menu app depends on: one entry == one line.
@@ -68,4 +146,58 @@ mod tests {
         let flattened = text.replace(['\n', '\r'], " ");
         assert!(!flattened.contains('\n') && !flattened.contains('\r'));
     }
+
+    #[test]
+    fn last_error_roundtrip_keeps_latest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("last_error");
+        assert_eq!(read_last_error(&path), None);
+
+        write_last_error(&path, "all LLM providers failed: google: 429").unwrap();
+        write_last_error(&path, "all TTS providers failed: google: 403\nmacos: x").unwrap();
+
+        let (at, reason) = read_last_error(&path).unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(&at).is_ok());
+        assert_eq!(reason, "all TTS providers failed: google: 403 macos: x");
+    }
+
+    #[test]
+    fn notification_types_are_deduplicated_and_sorted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notification_types");
+        assert!(read_notification_types(&path).is_empty());
+
+        for kind in [
+            "permission_prompt",
+            "idle_prompt",
+            "permission_prompt",
+            " idle_prompt ",
+            "",
+        ] {
+            add_notification_type(&path, kind).unwrap();
+        }
+        add_notification_type(&path, "auth_success").unwrap();
+
+        assert_eq!(
+            read_notification_types(&path),
+            ["auth_success", "idle_prompt", "permission_prompt"]
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "auth_success\nidle_prompt\npermission_prompt\n"
+        );
+    }
+
+    #[test]
+    fn failure_reason_lists_every_provider() {
+        let failures = vec![
+            "google: 403".to_string(),
+            "macos: not available".to_string(),
+        ];
+        assert_eq!(
+            failure_reason("TTS", &failures),
+            "all TTS providers failed: google: 403; macos: not available"
+        );
+        assert_eq!(failure_reason("LLM", &[]), "no LLM providers configured");
+    }
 }
@@ -1,11 +1,19 @@
 // Provider factory for creating LLM providers with fallback support
 
-use crate::config::LlmProviderConfig;
+use crate::config::{ApiKey, LlmAuth, LlmProviderConfig};
 use crate::error::{Result, VoiceError};
-use crate::llm::{AnthropicProvider, GeminiProvider, LlmProvider, OllamaProvider, OpenAIProvider};
+use crate::llm::bedrock::AwsCredentials;
+use crate::llm::http::HttpContext;
+use crate::llm::key_rotation::KeyRotatingProvider;
+use crate::llm::model_fallback::ModelFallbackProvider;
+use crate::llm::models_cache::{latest_alias_prefix, ModelsCache};
+use crate::llm::{
+    AnthropicProvider, BedrockProvider, GeminiProvider, LlmProvider, OllamaProvider, OpenAIProvider,
+};
 use std::str::FromStr;
 use std::time::Duration;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Provider {
     Google,
     Anthropic,
@@ -29,6 +37,49 @@ impl FromStr for Provider {
     }
 }
 
+impl Provider {
+    /// Map a model id (e.g. from a Claude Code transcript) to the provider family
+    /// that serves it. Returns None for ids that don't identify a known family.
+    pub fn for_model(model: &str) -> Option<Self> {
+        let model = model.to_lowercase();
+        let model = model.rsplit('/').next().unwrap_or(&model);
+
+        if model.starts_with("claude") {
+            Some(Provider::Anthropic)
+        } else if model.starts_with("gemini") {
+            Some(Provider::Google)
+        } else if model.starts_with("gpt")
+            || model.starts_with("o1")
+            || model.starts_with("o3")
+            || model.starts_with("o4")
+        {
+            Some(Provider::OpenAI)
+        } else if model.starts_with("grok") {
+            Some(Provider::Xai)
+        } else {
+            None
+        }
+    }
+}
+
+/// Reorder the provider chain so entries from the session model's family come first.
+/// Relative order within each group is preserved; unknown models leave the chain as-is.
+pub fn prioritize_session_provider(
+    providers: &[LlmProviderConfig],
+    session_model: &str,
+) -> Vec<LlmProviderConfig> {
+    let Some(preferred) = Provider::for_model(session_model) else {
+        return providers.to_vec();
+    };
+
+    let (mut matching, rest): (Vec<_>, Vec<_>) = providers
+        .iter()
+        .cloned()
+        .partition(|p| p.name.parse::<Provider>().ok() == Some(preferred));
+    matching.extend(rest);
+    matching
+}
+
 pub struct ProviderFactory;
 
 impl ProviderFactory {
@@ -71,113 +122,252 @@ impl ProviderFactory {
         )))
     }
 
-    /// Create a single provider from config
+    /// Create a single provider from config.
+    /// Fallback `models` produce a provider that moves to the next model on HTTP 503.
     pub fn create_single(config: &LlmProviderConfig) -> Result<Box<dyn LlmProvider>> {
+        Self::create_single_in(config, &HttpContext::new())
+    }
+
+    /// Like `create_single`, sending requests through the shared `http` client
+    pub fn create_single_in(
+        config: &LlmProviderConfig,
+        http: &HttpContext,
+    ) -> Result<Box<dyn LlmProvider>> {
+        if config.models.iter().all(|m| m == &config.model) {
+            return Self::create_for_model(config, http);
+        }
+
+        let mut model_ids = vec![config.model.clone()];
+        for model in &config.models {
+            if !model_ids.contains(model) {
+                model_ids.push(model.clone());
+            }
+        }
+
+        let mut models = Vec::with_capacity(model_ids.len());
+        for model in model_ids {
+            let model_config = LlmProviderConfig {
+                model: model.clone(),
+                models: Vec::new(),
+                ..config.clone()
+            };
+            models.push((model, Self::create_for_model(&model_config, http)?));
+        }
+        Ok(Box::new(ModelFallbackProvider::new(models)))
+    }
+
+    /// Create a provider for the config's primary model.
+    /// Several API keys produce a provider that rotates keys on rate limits.
+    fn create_for_model(
+        config: &LlmProviderConfig,
+        http: &HttpContext,
+    ) -> Result<Box<dyn LlmProvider>> {
         let timeout = Duration::from_secs(config.timeout);
         let provider: Provider = config.name.parse()?;
 
+        if provider == Provider::Ollama {
+            let base_url = config
+                .base_url
+                .clone()
+                .unwrap_or_else(|| "http://localhost:11434".to_string());
+            return Ok(Box::new(
+                OllamaProvider::with_base_url(base_url, config.model.clone(), timeout)
+                    .with_http(http.clone()),
+            ));
+        }
+
+        if config.auth == LlmAuth::Bedrock {
+            if provider != Provider::Anthropic {
+                return Err(VoiceError::Config(format!(
+                    "auth: bedrock is only supported for anthropic, not {}",
+                    config.name
+                )));
+            }
+            let region = config
+                .region
+                .clone()
+                .or_else(|| std::env::var("AWS_REGION").ok())
+                .ok_or_else(|| {
+                    VoiceError::Config("auth: bedrock needs `region` or AWS_REGION".into())
+                })?;
+            let bedrock = match &config.base_url {
+                Some(endpoint) => BedrockProvider::with_endpoint(
+                    AwsCredentials::from_env(),
+                    region,
+                    config.model.clone(),
+                    endpoint.clone(),
+                    timeout,
+                ),
+                None => BedrockProvider::new(
+                    AwsCredentials::from_env(),
+                    region,
+                    config.model.clone(),
+                    timeout,
+                ),
+            };
+            return Ok(Box::new(bedrock.with_http(http.clone())));
+        }
+
+        let mut keys = config.get_api_keys();
+        if keys.is_empty() {
+            let (label, env_name) = match provider {
+                Provider::Google => ("Google", "google"),
+                Provider::Anthropic => ("Anthropic", "anthropic"),
+                Provider::OpenAI => ("OpenAI", "openai"),
+                Provider::Xai => ("xAI", "xai"),
+                Provider::Ollama => unreachable!(),
+            };
+            return Err(VoiceError::Config(format!(
+                "No API key for {}. Set in config or env var {}",
+                label,
+                LlmProviderConfig::env_var_name(env_name)
+            )));
+        }
+
+        if keys.len() == 1 {
+            return Ok(Self::create_keyed(
+                provider,
+                config,
+                keys.remove(0),
+                timeout,
+                http,
+            ));
+        }
+
+        let providers = keys
+            .into_iter()
+            .map(|key| Self::create_keyed(provider, config, key, timeout, http))
+            .collect();
+        Ok(Box::new(KeyRotatingProvider::new(
+            providers,
+            KeyRotatingProvider::index_path_for(&config.name),
+        )))
+    }
+
+    /// Create an API-key based provider for one key
+    fn create_keyed(
+        provider: Provider,
+        config: &LlmProviderConfig,
+        api_key: String,
+        timeout: Duration,
+        http: &HttpContext,
+    ) -> Box<dyn LlmProvider> {
+        let model = config.model.clone();
         match provider {
             Provider::Google => {
-                let api_key = config.get_api_key().ok_or_else(|| {
-                    VoiceError::Config(format!(
-                        "No API key for Google. Set in config or env var {}",
-                        LlmProviderConfig::env_var_name("google")
-                    ))
-                })?;
                 let base_url = config.base_url.clone().unwrap_or_else(|| {
                     "https://generativelanguage.googleapis.com/v1beta".to_string()
                 });
-                Ok(Box::new(GeminiProvider::with_base_url(
-                    api_key,
-                    config.model.clone(),
-                    base_url,
-                    timeout,
-                )))
+                Box::new(
+                    GeminiProvider::with_base_url(api_key, model, base_url, timeout)
+                        .with_http(http.clone()),
+                )
             }
             Provider::Anthropic => {
-                let api_key = config.get_api_key().ok_or_else(|| {
-                    VoiceError::Config(format!(
-                        "No API key for Anthropic. Set in config or env var {}",
-                        LlmProviderConfig::env_var_name("anthropic")
-                    ))
-                })?;
                 let base_url = config
                     .base_url
                     .clone()
                     .unwrap_or_else(|| "https://api.anthropic.com/v1".to_string());
-                Ok(Box::new(AnthropicProvider::with_base_url(
-                    api_key,
-                    config.model.clone(),
-                    base_url,
-                    timeout,
-                )))
+                Box::new(
+                    AnthropicProvider::with_base_url(api_key, model, base_url, timeout)
+                        .with_http(http.clone()),
+                )
             }
-            Provider::OpenAI => {
-                let api_key = config.get_api_key().ok_or_else(|| {
-                    VoiceError::Config(format!(
-                        "No API key for OpenAI. Set in config or env var {}",
-                        LlmProviderConfig::env_var_name("openai")
-                    ))
-                })?;
+            Provider::OpenAI | Provider::Xai => {
+                let default_base = if provider == Provider::Xai {
+                    "https://api.x.ai/v1"
+                } else {
+                    "https://api.openai.com/v1"
+                };
                 let base_url = config
                     .base_url
                     .clone()
-                    .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
-                Ok(Box::new(OpenAIProvider::with_base_url(
-                    api_key,
-                    config.model.clone(),
-                    base_url,
-                    timeout,
-                )))
+                    .unwrap_or_else(|| default_base.to_string());
+                Box::new(
+                    OpenAIProvider::with_base_url(api_key, model, base_url, timeout)
+                        .with_http(http.clone()),
+                )
             }
-            Provider::Ollama => {
-                let base_url = config
-                    .base_url
-                    .clone()
-                    .unwrap_or_else(|| "http://localhost:11434".to_string());
-                Ok(Box::new(OllamaProvider::with_base_url(
-                    base_url,
-                    config.model.clone(),
-                    timeout,
-                )))
+            Provider::Ollama => unreachable!("Ollama does not use API keys"),
+        }
+    }
+
+    /// Resolve a `<prefix>-latest` model alias to the newest snapshot the
+    /// provider lists (cached for a day). Other model names, and aliases that
+    /// can't be resolved, are returned unchanged.
+    pub async fn resolve_model_alias(config: &LlmProviderConfig, http: &HttpContext) -> String {
+        let Some(prefix) = latest_alias_prefix(&config.model) else {
+            return config.model.clone();
+        };
+
+        let resolved = match (
+            Self::create_for_model(config, http),
+            ModelsCache::from_home(),
+        ) {
+            (Ok(provider), Ok(cache)) => {
+                cache
+                    .resolve_latest(&config.name, provider.as_ref(), prefix)
+                    .await
             }
-            Provider::Xai => {
-                let api_key = config.get_api_key().ok_or_else(|| {
-                    VoiceError::Config(format!(
-                        "No API key for xAI. Set in config or env var {}",
-                        LlmProviderConfig::env_var_name("xai")
-                    ))
-                })?;
-                let base_url = config
-                    .base_url
-                    .clone()
-                    .unwrap_or_else(|| "https://api.x.ai/v1".to_string());
-                Ok(Box::new(OpenAIProvider::with_base_url(
-                    api_key,
-                    config.model.clone(),
-                    base_url,
-                    timeout,
-                )))
+            (Err(e), _) => Err(crate::error::LlmError::Request(e.to_string())),
+            (_, Err(e)) => Err(e),
+        };
+        match resolved {
+            Ok(model) => {
+                tracing::info!("Resolved {} to {}", config.model, model);
+                model
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Could not resolve {}, using it as given: {}",
+                    config.model,
+                    e
+                );
+                config.model.clone()
             }
         }
     }
 
     /// Create a provider by name (for CLI override)
+    #[allow(dead_code)] // Used in tests
     pub fn create_by_name(
         name: &str,
         model: &str,
         timeout: Duration,
         api_key: Option<&str>,
     ) -> Result<Box<dyn LlmProvider>> {
-        let config = LlmProviderConfig {
+        Self::create_by_name_in(name, model, timeout, api_key, &HttpContext::new())
+    }
+
+    /// Like `create_by_name`, sending requests through the shared `http` client
+    pub fn create_by_name_in(
+        name: &str,
+        model: &str,
+        timeout: Duration,
+        api_key: Option<&str>,
+        http: &HttpContext,
+    ) -> Result<Box<dyn LlmProvider>> {
+        Self::create_single_in(&Self::named_config(name, model, timeout, api_key), http)
+    }
+
+    /// Provider config for a CLI-selected provider and model
+    pub fn named_config(
+        name: &str,
+        model: &str,
+        timeout: Duration,
+        api_key: Option<&str>,
+    ) -> LlmProviderConfig {
+        LlmProviderConfig {
             name: name.to_string(),
             model: model.to_string(),
-            api_key: api_key.map(|s| s.to_string()),
+            api_key: api_key.map(ApiKey::from),
             base_url: None,
             timeout: timeout.as_secs(),
             disable_thinking: None,
-        };
-        Self::create_single(&config)
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: Vec::new(),
+        }
     }
 }
 
@@ -238,15 +428,61 @@ mod tests {
         assert!("unknown".parse::<Provider>().is_err());
     }
 
+    #[test]
+    fn test_provider_for_model() {
+        assert_eq!(
+            Provider::for_model("claude-opus-4-1-20250805"),
+            Some(Provider::Anthropic)
+        );
+        assert_eq!(
+            Provider::for_model("gemini/gemini-2.5-pro"),
+            Some(Provider::Google)
+        );
+        assert_eq!(Provider::for_model("gpt-5-nano"), Some(Provider::OpenAI));
+        assert_eq!(Provider::for_model("o3-mini"), Some(Provider::OpenAI));
+        assert_eq!(Provider::for_model("grok-4"), Some(Provider::Xai));
+        assert_eq!(Provider::for_model("<synthetic>"), None);
+    }
+
+    #[test]
+    fn test_prioritize_session_provider() {
+        let providers: Vec<LlmProviderConfig> = ["google", "openai", "claude", "ollama"]
+            .iter()
+            .map(|name| LlmProviderConfig {
+                name: name.to_string(),
+                model: "m".to_string(),
+                api_key: None,
+                base_url: None,
+                timeout: 10,
+                disable_thinking: None,
+                auth: LlmAuth::ApiKey,
+                region: None,
+                models: Vec::new(),
+            })
+            .collect();
+
+        let ordered = prioritize_session_provider(&providers, "claude-sonnet-4-5");
+        let names: Vec<_> = ordered.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["claude", "google", "openai", "ollama"]);
+
+        // Unknown model family keeps config order
+        let ordered = prioritize_session_provider(&providers, "mystery-model");
+        let names: Vec<_> = ordered.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["google", "openai", "claude", "ollama"]);
+    }
+
     #[test]
     fn test_create_from_config_with_api_key() {
         let providers = vec![LlmProviderConfig {
             name: "google".to_string(),
             model: "gemini-2.5-flash".to_string(),
-            api_key: Some("test-key".to_string()),
+            api_key: Some("test-key".into()),
             base_url: None,
             timeout: 10,
             disable_thinking: None,
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: Vec::new(),
         }];
 
         let result = ProviderFactory::create_from_config(&providers);
@@ -265,6 +501,9 @@ mod tests {
                 base_url: None,
                 timeout: 10,
                 disable_thinking: None,
+                auth: LlmAuth::ApiKey,
+                region: None,
+                models: Vec::new(),
             },
             LlmProviderConfig {
                 name: "ollama".to_string(),
@@ -273,6 +512,9 @@ mod tests {
                 base_url: None,
                 timeout: 10,
                 disable_thinking: None,
+                auth: LlmAuth::ApiKey,
+                region: None,
+                models: Vec::new(),
             },
         ];
 
@@ -336,4 +578,74 @@ mod tests {
         let err = result.err().unwrap();
         assert!(err.to_string().contains("No API key"));
     }
+
+    #[tokio::test]
+    async fn test_create_single_google_uses_base_url() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock(
+                "POST",
+                "/proxy/v1beta/models/gemini-2.5-flash:generateContent",
+            )
+            .match_query(mockito::Matcher::UrlEncoded(
+                "key".into(),
+                "test-key".into(),
+            ))
+            .with_status(200)
+            .with_body(r#"{"candidates":[{"content":{"parts":[{"text":"Done."}]}}]}"#)
+            .create_async()
+            .await;
+
+        let config = LlmProviderConfig {
+            name: "google".to_string(),
+            model: "gemini-2.5-flash".to_string(),
+            api_key: Some("test-key".into()),
+            base_url: Some(format!("{}/proxy/v1beta", server.url())),
+            timeout: 5,
+            disable_thinking: None,
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: Vec::new(),
+        };
+        let provider = ProviderFactory::create_single(&config).unwrap();
+        let request = crate::llm::GenerationRequest {
+            system_message: None,
+            prompt: "Summarize".to_string(),
+            max_tokens: 50,
+            temperature: 0.3,
+            disable_thinking: false,
+            stop: None,
+            reasoning_effort: None,
+            ollama_system_mode: Default::default(),
+        };
+
+        let response = provider.generate(&request).await.unwrap();
+        assert_eq!(response.text, "Done.");
+        mock.assert_async().await;
+    }
+
+    #[test]
+    fn test_create_single_bedrock_auth() {
+        let config = LlmProviderConfig {
+            name: "anthropic".to_string(),
+            model: "claude-3-5-haiku-20241022".to_string(),
+            api_key: None,
+            base_url: None,
+            timeout: 10,
+            disable_thinking: None,
+            auth: LlmAuth::Bedrock,
+            region: Some("us-west-2".to_string()),
+            models: Vec::new(),
+        };
+        let provider = ProviderFactory::create_single(&config).unwrap();
+        assert_eq!(provider.name(), "bedrock");
+
+        // Bedrock auth is Anthropic-only
+        let openai = LlmProviderConfig {
+            name: "openai".to_string(),
+            ..config
+        };
+        let err = ProviderFactory::create_single(&openai).err().unwrap();
+        assert!(err.to_string().contains("only supported for anthropic"));
+    }
 }
@@ -1,8 +1,10 @@
 // Notification queue - cross-process coordination for sequential playback
 
 use std::fs::{File, OpenOptions};
+use std::io::Read;
+use std::os::unix::fs::FileExt;
 use std::path::PathBuf;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use nix::fcntl::Flock;
 use nix::fcntl::FlockArg;
@@ -49,8 +51,12 @@ impl NotificationQueue {
 }
 
 /// RAII wrapper for queue lock
+///
+/// On release the lock file records when (ms since the epoch), so the next
+/// holder can keep `hooks.playback.gap_ms` of silence after it.
 pub struct QueueLock {
-    _flock: Flock<File>,
+    flock: Flock<File>,
+    waited: bool,
 }
 
 impl QueueLock {
@@ -71,10 +77,12 @@ impl QueueLock {
         queue.ensure_lock_dir()?;
 
         // Try to acquire lock with timeout
+        let mut waited = false;
         loop {
             // Open or create lock file
             let file = OpenOptions::new()
                 .create(true)
+                .read(true)
                 .write(true)
                 .truncate(false)
                 .open(&queue.lock_file_path)
@@ -87,10 +95,11 @@ impl QueueLock {
                     let elapsed = start_time.elapsed();
                     tracing::info!("Queue lock acquired after {:?}", elapsed);
 
-                    return Ok(QueueLock { _flock: flock });
+                    return Ok(QueueLock { flock, waited });
                 }
                 Err((_, nix::errno::Errno::EWOULDBLOCK)) => {
                     // Lock is held by another process
+                    waited = true;
                     let elapsed = start_time.elapsed();
 
                     if elapsed >= queue.timeout {
@@ -115,13 +124,123 @@ impl QueueLock {
     }
 }
 
+impl QueueLock {
+    /// True when another playback held the lock on arrival, i.e. this one
+    /// was queued behind it
+    pub fn waited(&self) -> bool {
+        self.waited
+    }
+
+    /// When the previous holder released the lock, if it recorded it
+    fn previous_release_ms(&self) -> Option<u64> {
+        let mut contents = String::new();
+        (&*self.flock).read_to_string(&mut contents).ok()?;
+        contents.trim().parse().ok()
+    }
+
+    /// How much of `gap` is still to wait since the previous playback ended
+    pub fn gap_remaining(&self, gap: Duration) -> Duration {
+        let Some(released) = self.previous_release_ms() else {
+            return Duration::ZERO;
+        };
+        let since = Duration::from_millis(now_ms().saturating_sub(released));
+        gap.saturating_sub(since)
+    }
+
+    /// Wait out the rest of `gap` after the previous playback
+    pub async fn keep_gap(&self, gap: Duration) {
+        let remaining = self.gap_remaining(gap);
+        if !remaining.is_zero() {
+            tracing::debug!("Keeping {:?} gap after the previous playback", remaining);
+            tokio::time::sleep(remaining).await;
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
 impl Drop for QueueLock {
     fn drop(&mut self) {
-        // Release lock (Flock automatically releases on drop)
+        // Record the release time for the next holder's gap, then release
+        // (Flock automatically releases on drop)
+        let file: &File = &self.flock;
+        let recorded = file
+            .set_len(0)
+            .and_then(|_| file.write_all_at(now_ms().to_string().as_bytes(), 0));
+        if let Err(e) = recorded {
+            tracing::debug!("Failed to record queue lock release time: {}", e);
+        }
         tracing::debug!("Queue lock released");
     }
 }
 
+/// Shared "latest notification" record used to coalesce bursts.
+///
+/// Each notification registers a ticket on arrival; once it holds the queue
+/// lock it checks whether a newer ticket exists and, if so, stays silent so
+/// only the most recent notification of a burst is spoken.
+pub struct NotificationSequence {
+    path: PathBuf,
+}
+
+impl NotificationSequence {
+    /// Sequence file at ~/.sumvox/latest-notification
+    pub fn from_home() -> Result<Self> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| VoiceError::Config("Cannot find home directory".into()))?;
+        Ok(Self::at(home.join(".sumvox").join("latest-notification")))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Record a new latest notification and return its ticket
+    pub fn register(&self) -> Result<u64> {
+        // Nanosecond timestamps order tickets across processes; bump past the
+        // current latest in case two arrive within the clock's resolution
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        let ticket = now.max(self.latest().map_or(0, |l| l + 1));
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write-then-rename so readers never see a partial ticket
+        let tmp = self
+            .path
+            .with_extension(format!("tmp-{}", std::process::id()));
+        std::fs::write(&tmp, ticket.to_string())?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(ticket)
+    }
+
+    /// Ticket of the most recently registered notification, if any
+    pub fn latest(&self) -> Option<u64> {
+        std::fs::read_to_string(&self.path)
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// True when a newer notification has arrived since `ticket`
+    pub fn is_superseded(&self, ticket: u64) -> bool {
+        superseded(ticket, self.latest())
+    }
+}
+
+fn superseded(ticket: u64, latest: Option<u64>) -> bool {
+    latest.is_some_and(|latest| latest > ticket)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +292,53 @@ mod tests {
         assert!(lock2.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_lock_reports_waiting_behind_another_playback() {
+        let temp_dir = tempdir().unwrap();
+        let mut queue = NotificationQueue::new(Some(Duration::from_secs(5))).unwrap();
+        queue.lock_file_path = temp_dir.path().join("test.lock");
+
+        let first = QueueLock::acquire(&queue).await.unwrap();
+        assert!(!first.waited());
+
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            drop(first);
+        });
+        let second = QueueLock::acquire(&queue).await.unwrap();
+        assert!(second.waited());
+        release.await.unwrap();
+        drop(second);
+
+        let third = QueueLock::acquire(&queue).await.unwrap();
+        assert!(!third.waited());
+    }
+
+    #[tokio::test]
+    async fn test_gap_is_kept_between_serialized_playbacks() {
+        let temp_dir = tempdir().unwrap();
+        let mut queue = NotificationQueue::new(Some(Duration::from_secs(5))).unwrap();
+        queue.lock_file_path = temp_dir.path().join("test.lock");
+        let gap = Duration::from_millis(300);
+
+        // Nothing played before: no gap to keep
+        let first = QueueLock::acquire(&queue).await.unwrap();
+        assert_eq!(first.gap_remaining(gap), Duration::ZERO);
+        drop(first);
+
+        // The next playback right after waits out the gap
+        let second = QueueLock::acquire(&queue).await.unwrap();
+        let start = Instant::now();
+        second.keep_gap(gap).await;
+        assert!(start.elapsed() >= Duration::from_millis(250));
+        drop(second);
+
+        // Long after the previous playback, there's nothing left to wait
+        std::fs::write(&queue.lock_file_path, (now_ms() - 1000).to_string()).unwrap();
+        let third = QueueLock::acquire(&queue).await.unwrap();
+        assert_eq!(third.gap_remaining(gap), Duration::ZERO);
+    }
+
     #[test]
     fn test_ensure_lock_dir_creates_directory() {
         let temp_dir = tempdir().unwrap();
@@ -185,4 +351,28 @@ mod tests {
 
         assert!(lock_path.parent().unwrap().exists());
     }
+
+    #[test]
+    fn test_superseded_logic() {
+        assert!(!superseded(5, None));
+        assert!(!superseded(5, Some(5)));
+        assert!(!superseded(5, Some(4)));
+        assert!(superseded(5, Some(6)));
+    }
+
+    #[test]
+    fn test_newer_notification_supersedes_older() {
+        let temp_dir = tempdir().unwrap();
+        let sequence = NotificationSequence::at(temp_dir.path().join("latest-notification"));
+        assert_eq!(sequence.latest(), None);
+
+        let first = sequence.register().unwrap();
+        assert!(!sequence.is_superseded(first));
+
+        let second = sequence.register().unwrap();
+        assert!(second > first);
+        assert!(sequence.is_superseded(first));
+        assert!(!sequence.is_superseded(second));
+        assert_eq!(sequence.latest(), Some(second));
+    }
 }
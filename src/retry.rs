@@ -0,0 +1,170 @@
+// Retry with exponential backoff, shared by LLM providers and cloud TTS
+// A call is retried only when the predicate says its error is transient
+// (rate limits, overloaded models, 5xx). Anything else returns at once so
+// provider fallback can move on without waiting.
+
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+
+/// How many times to try and how long to wait in between
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first (at least 1)
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles for each one after
+    pub base_delay: Duration,
+    /// Upper bound on any single wait
+    pub max_delay: Duration,
+    /// Fraction of each wait that is randomized (0.0 - 1.0), so callers
+    /// hitting the same limit don't retry in lockstep
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    pub const fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+            max_delay: Duration::from_secs(10),
+            jitter: 0.0,
+        }
+    }
+
+    pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub const fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Wait before retry number `retry` (1 = first retry), without jitter
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// `delay` with up to `jitter` of it taken off at random
+    fn jittered_delay(&self, retry: u32) -> Duration {
+        let delay = self.delay(retry);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return delay;
+        }
+        delay.mul_f64(1.0 - rand::thread_rng().gen_range(0.0..=jitter))
+    }
+}
+
+/// Run `op` until it succeeds, fails with an error `is_retryable` rejects,
+/// or `policy.max_attempts` is used up. Returns the last error.
+pub async fn retry_async<T, E, F, Fut, P>(
+    mut op: F,
+    policy: &RetryPolicy,
+    is_retryable: P,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
+    E: std::fmt::Display,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < policy.max_attempts && is_retryable(&e) => {
+                let delay = policy.jittered_delay(attempt);
+                tracing::debug!(
+                    "Attempt {}/{} failed: {}, retrying in {:?}",
+                    attempt,
+                    policy.max_attempts,
+                    e,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    const FAST: RetryPolicy = RetryPolicy::new(3, Duration::ZERO);
+
+    #[test]
+    fn test_delay_doubles_up_to_max() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(350));
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(350));
+        assert_eq!(policy.delay(40), Duration::from_millis(350));
+
+        let jittered = policy.with_jitter(0.5);
+        for _ in 0..20 {
+            let delay = jittered.jittered_delay(2);
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success_or_attempts_run_out() {
+        let calls = Cell::new(0);
+        let result: Result<u32, String> = retry_async(
+            || {
+                calls.set(calls.get() + 1);
+                let n = calls.get();
+                async move {
+                    if n < 3 {
+                        Err("busy".into())
+                    } else {
+                        Ok(n)
+                    }
+                }
+            },
+            &FAST,
+            |_| true,
+        )
+        .await;
+        assert_eq!(result, Ok(3));
+
+        calls.set(0);
+        let result: Result<(), String> = retry_async(
+            || {
+                calls.set(calls.get() + 1);
+                async { Err("busy".into()) }
+            },
+            &FAST,
+            |_| true,
+        )
+        .await;
+        assert_eq!(result, Err("busy".into()));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_returns_immediately() {
+        let calls = Cell::new(0);
+        let result: Result<(), String> = retry_async(
+            || {
+                calls.set(calls.get() + 1);
+                let n = calls.get();
+                async move { Err(if n == 1 { "busy" } else { "bad request" }.to_string()) }
+            },
+            &FAST,
+            |e| e == "busy",
+        )
+        .await;
+        assert_eq!(result, Err("bad request".into()));
+        assert_eq!(calls.get(), 2);
+    }
+}
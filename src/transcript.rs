@@ -1,34 +1,121 @@
 // Transcript JSONL reader for Claude Code
+// Turn extraction goes through `TranscriptParser`, so other tools' transcript
+// formats can plug in next to `ClaudeCodeParser`.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Read;
 use std::path::Path;
-use tokio::fs::File;
-use tokio::io::{AsyncBufReadExt, BufReader};
 
+use crate::config::TurnBoundary;
 use crate::error::{Result, VoiceError};
 
+/// gzip magic bytes
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Read a transcript as text, transparently decompressing gzip (`.jsonl.gz`).
+/// Compression is detected from the magic bytes, so the extension doesn't matter.
+async fn read_transcript(path: &Path) -> Result<String> {
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| VoiceError::Transcript(format!("Failed to open transcript file: {}", e)))?;
+
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return String::from_utf8(bytes)
+            .map_err(|e| VoiceError::Transcript(format!("Transcript is not UTF-8: {}", e)));
+    }
+
+    let mut text = String::new();
+    flate2::read::MultiGzDecoder::new(bytes.as_slice())
+        .read_to_string(&mut text)
+        .map_err(|e| {
+            VoiceError::Transcript(format!("Failed to decompress transcript file: {}", e))
+        })?;
+    Ok(text)
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TranscriptEntry {
     #[serde(rename = "type")]
     pub entry_type: String,
     pub message: Option<Message>,
     pub timestamp: Option<String>,
+    /// Session summary text (`type: "summary"` entries)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// System entry payload (`type: "system"` entries); kept raw since its shape varies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<serde_json::Value>,
+    /// System entry subtype (e.g. "compact_boundary")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtype: Option<String>,
+}
+
+/// Kind of a transcript entry, derived from its `type` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    User,
+    Assistant,
+    /// Legacy/test format: role lives in `message.role`
+    Message,
+    /// Claude Code's own session summary
+    Summary,
+    /// System notices (compaction boundaries, hook output, etc.)
+    System,
+    Other,
+}
+
+impl TranscriptEntry {
+    pub fn kind(&self) -> EntryKind {
+        match self.entry_type.as_str() {
+            "user" => EntryKind::User,
+            "assistant" => EntryKind::Assistant,
+            "message" => EntryKind::Message,
+            "summary" => EntryKind::Summary,
+            "system" => EntryKind::System,
+            _ => EntryKind::Other,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Message {
     pub role: String,
     pub content: MessageContent,
+    /// Model that produced an assistant message (e.g. "claude-sonnet-4-5-20250929")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
 }
 
-/// Message content can be either a string or an array of ContentBlocks
+/// Message content can be either a string or an array of ContentBlocks.
+/// A single block object (emitted by some Claude Code versions) is read as a
+/// one-block array.
 #[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(untagged)]
+#[serde(untagged, from = "RawMessageContent")]
 pub enum MessageContent {
     Text(String),
     Blocks(Vec<ContentBlock>),
 }
 
+/// Every shape `content` comes in on the wire
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawMessageContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+    Block(ContentBlock),
+}
+
+impl From<RawMessageContent> for MessageContent {
+    fn from(raw: RawMessageContent) -> Self {
+        match raw {
+            RawMessageContent::Text(text) => MessageContent::Text(text),
+            RawMessageContent::Blocks(blocks) => MessageContent::Blocks(blocks),
+            RawMessageContent::Block(block) => MessageContent::Blocks(vec![block]),
+        }
+    }
+}
+
 impl Message {
     /// Extract text content from message, handling both string and array formats
     pub fn extract_texts(&self) -> Vec<String> {
@@ -78,65 +165,229 @@ pub enum ContentBlock {
         tool_use_id: String,
         content: serde_json::Value,
     },
+    /// Extended thinking output (never spoken)
+    #[serde(rename = "thinking")]
+    Thinking {
+        #[serde(default)]
+        thinking: String,
+    },
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking,
     #[serde(other)]
     Other,
 }
 
-pub struct TranscriptReader;
+impl ContentBlock {
+    /// Compact description of a file change in this block: an Edit/Write
+    /// tool call, or the diff lines of a tool result that printed one
+    pub fn diff_excerpt(&self) -> Option<String> {
+        match self {
+            ContentBlock::ToolUse { name, input } => {
+                let path = input.get("file_path")?.as_str()?;
+                let field = |key: &str| input.get(key).and_then(|v| v.as_str());
+                if let (Some(old), Some(new)) = (field("old_string"), field("new_string")) {
+                    let mut excerpt = format!("{} {}", name, path);
+                    excerpt.extend(old.lines().map(|l| format!("\n- {}", l)));
+                    excerpt.extend(new.lines().map(|l| format!("\n+ {}", l)));
+                    Some(excerpt)
+                } else if let Some(edits) = input.get("edits").and_then(|v| v.as_array()) {
+                    Some(format!("{} {} ({} edits)", name, path, edits.len()))
+                } else {
+                    let content = field("content")?;
+                    Some(format!(
+                        "{} {} ({} lines)",
+                        name,
+                        path,
+                        content.lines().count()
+                    ))
+                }
+            }
+            ContentBlock::ToolResult { content, .. } => {
+                let text = match content {
+                    serde_json::Value::String(text) => text.clone(),
+                    serde_json::Value::Array(parts) => parts
+                        .iter()
+                        .filter_map(|p| p.get("text").and_then(|t| t.as_str()))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    _ => return None,
+                };
+                diff_lines(&text)
+            }
+            _ => None,
+        }
+    }
+}
 
-impl TranscriptReader {
-    /// Read transcript JSONL file and extract assistant text blocks
-    pub async fn read_assistant_texts(path: impl AsRef<Path>, limit: usize) -> Result<Vec<String>> {
-        let file = File::open(path.as_ref()).await.map_err(|e| {
-            VoiceError::Transcript(format!("Failed to open transcript file: {}", e))
-        })?;
+/// The header and +/- lines of a unified diff in `text`, or None when
+/// `text` doesn't contain one
+fn diff_lines(text: &str) -> Option<String> {
+    if !text
+        .lines()
+        .any(|l| l.starts_with("@@ ") || l.starts_with("diff --git "))
+    {
+        return None;
+    }
+    let kept: Vec<&str> = text
+        .lines()
+        .filter(|l| {
+            l.starts_with("+++ ")
+                || (l.starts_with('+') && !l.starts_with("++"))
+                || (l.starts_with('-') && !l.starts_with("--"))
+        })
+        .collect();
+    (!kept.is_empty()).then(|| kept.join("\n"))
+}
 
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
-        let mut texts = Vec::new();
+/// How recent turns are picked out of a transcript
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TurnOptions {
+    /// Which messages start a turn
+    pub boundary: TurnBoundary,
+    /// Skip trailing turns without assistant text (thinking or tool calls only)
+    pub skip_empty: bool,
+}
 
-        while let Some(line) = lines.next_line().await? {
-            if line.trim().is_empty() {
-                continue;
-            }
+/// One exchange in a transcript
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Turn {
+    /// Human message that opened the turn, when one did
+    pub user: Option<String>,
+    /// Assistant text blocks, oldest first
+    pub assistant: Vec<String>,
+}
 
-            match serde_json::from_str::<TranscriptEntry>(&line) {
-                Ok(entry) => {
-                    // Support both formats:
-                    // 1. Test format: {"type":"message","message":{"role":"assistant",...}}
-                    // 2. Claude Code format: {"type":"assistant","message":{"role":"assistant",...}}
-                    let is_assistant = entry.entry_type == "assistant"
-                        || (entry.entry_type == "message"
-                            && entry
-                                .message
-                                .as_ref()
-                                .is_some_and(|m| m.role == "assistant"));
-
-                    if is_assistant {
-                        if let Some(message) = entry.message {
-                            for text in message.extract_texts() {
-                                texts.push(text);
-                                if texts.len() >= limit {
-                                    return Ok(texts);
-                                }
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    // Skip malformed lines (different message formats, progress entries, etc.)
-                    tracing::debug!("Skipping non-message JSONL line: {}", e);
-                    continue;
-                }
+/// Splits one tool's transcript format into turns, keeping readers
+/// independent of its schema
+pub trait TranscriptParser: Send + Sync {
+    /// Turns in `transcript`, oldest first. Text before the first turn start
+    /// belongs to no turn; a transcript without turn starts yields none.
+    fn extract_turns(&self, transcript: &str, options: TurnOptions) -> Vec<Turn>;
+
+    /// Every assistant text block in `transcript`, oldest first
+    fn assistant_texts(&self, transcript: &str) -> Vec<String>;
+}
+
+/// Claude Code's JSONL transcripts (one entry per line)
+pub struct ClaudeCodeParser;
+
+/// What the reader sees in a transcript (`sumvox transcript-stats`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TranscriptStats {
+    /// Non-empty lines
+    pub entries: usize,
+    /// Lines that aren't valid transcript JSON
+    pub unparsed: usize,
+    /// Human user messages
+    pub user: usize,
+    pub assistant: usize,
+    /// tool_use blocks in assistant messages
+    pub tool_calls: usize,
+    /// User entries carrying only tool results
+    pub tool_results: usize,
+    /// Line index and opening text of each turn start
+    pub turn_starts: Vec<(usize, String)>,
+    /// What `read_last_n_turns` extracts for N = 1, 2, 3
+    pub extracted: Vec<Vec<String>>,
+}
+
+/// Longest text preview in the stats report, in characters
+const STATS_PREVIEW_CHARS: usize = 60;
+
+fn preview(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= STATS_PREVIEW_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(STATS_PREVIEW_CHARS).collect();
+    format!("{}...", cut)
+}
+
+impl fmt::Display for TranscriptStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Entries: {} ({} unparsed)", self.entries, self.unparsed)?;
+        writeln!(
+            f,
+            "User: {}  Assistant: {}  Tool calls: {}  Tool results: {}",
+            self.user, self.assistant, self.tool_calls, self.tool_results
+        )?;
+        writeln!(f)?;
+        writeln!(f, "Turn starts ({}):", self.turn_starts.len())?;
+        for (idx, text) in &self.turn_starts {
+            writeln!(f, "  [{}] {}", idx, text)?;
+        }
+        for (i, texts) in self.extracted.iter().enumerate() {
+            writeln!(f)?;
+            writeln!(f, "Last {} turn(s), {} text(s):", i + 1, texts.len())?;
+            for text in texts {
+                writeln!(f, "  - {}", preview(text))?;
             }
         }
+        Ok(())
+    }
+}
+
+pub struct TranscriptReader;
 
+impl TranscriptReader {
+    /// Read the first `limit` assistant text blocks of the transcript
+    pub async fn read_assistant_texts(
+        parser: &dyn TranscriptParser,
+        path: impl AsRef<Path>,
+        limit: usize,
+    ) -> Result<Vec<String>> {
+        let content = read_transcript(path.as_ref()).await?;
+        let mut texts = parser.assistant_texts(&content);
+        texts.truncate(limit);
         Ok(texts)
     }
 
+    /// Detect the model that produced the most recent assistant message.
+    ///
+    /// Claude Code stamps assistant messages with a `model` field. Placeholder ids
+    /// such as `<synthetic>` (locally generated messages) are skipped.
+    /// Returns None when the file can't be read or no model is recorded.
+    pub async fn detect_session_model(path: impl AsRef<Path>) -> Option<String> {
+        let content = read_transcript(path.as_ref()).await.ok()?;
+
+        content
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<TranscriptEntry>(line).ok())
+            .filter_map(|entry| entry.message)
+            .filter(|m| m.role == "assistant")
+            .filter_map(|m| m.model)
+            .find(|model| !model.starts_with('<'))
+    }
+
+    /// Read the native session summary (`type: "summary"` entry) of the
+    /// latest turn. Only entries after the last user turn start count: an
+    /// older one describes earlier work. Returns Ok(None) when there is no
+    /// such non-empty summary.
+    pub async fn read_native_summary(path: impl AsRef<Path>) -> Result<Option<String>> {
+        let lines = Self::read_nonempty_lines(path.as_ref()).await?;
+        let start = Self::turn_starts(&lines, TurnBoundary::User)
+            .last()
+            .copied()
+            .unwrap_or(0);
+
+        Ok(lines[start..]
+            .iter()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<TranscriptEntry>(line).ok())
+            .filter(|entry| entry.kind() == EntryKind::Summary)
+            .filter_map(|entry| entry.summary)
+            .map(|s| s.trim().to_string())
+            .find(|s| !s.is_empty()))
+    }
+
     /// Read last N assistant text blocks from transcript
-    pub async fn read_last_n_texts(path: impl AsRef<Path>, n: usize) -> Result<Vec<String>> {
-        let all_texts = Self::read_assistant_texts(path, usize::MAX).await?;
+    pub async fn read_last_n_texts(
+        parser: &dyn TranscriptParser,
+        path: impl AsRef<Path>,
+        n: usize,
+    ) -> Result<Vec<String>> {
+        let all_texts = Self::read_assistant_texts(parser, path, usize::MAX).await?;
         let start = all_texts.len().saturating_sub(n);
         Ok(all_texts[start..].to_vec())
     }
@@ -145,9 +396,12 @@ impl TranscriptReader {
     ///
     /// A turn is defined from a user message to the next user message (or EOF).
     /// This ensures we only summarize the most recent interaction, not historical steps.
+    /// With `TurnBoundary::Assistant`, each assistant message with text starts
+    /// a turn instead, so a turn is one assistant response.
     ///
     /// # Arguments
-    /// * `path` - Path to the transcript JSONL file
+    /// * `parser` - Turn extraction for the transcript's format
+    /// * `path` - Path to the transcript file
     /// * `n` - Number of turns to read (must be >= 1)
     ///
     /// # Fallback
@@ -163,65 +417,276 @@ impl TranscriptReader {
     /// assistant: "Running tests..."
     /// assistant: "Tests passed"      <- Turn 2 ends (EOF)
     ///
-    /// read_last_n_turns(path, 1) -> ["Running tests...", "Tests passed"]
-    /// read_last_n_turns(path, 2) -> ["Here's the code...", "Function done", "Running tests...", "Tests passed"]
+    /// read_last_n_turns(&ClaudeCodeParser, path, 1, TurnOptions::default()) -> ["Running tests...", "Tests passed"]
+    /// read_last_n_turns(&ClaudeCodeParser, path, 2, TurnOptions::default()) -> ["Here's the code...", "Function done", "Running tests...", "Tests passed"]
     /// ```
-    pub async fn read_last_n_turns(path: impl AsRef<Path>, n: usize) -> Result<Vec<String>> {
+    ///
+    /// With `options.skip_empty`, trailing turns without any assistant text
+    /// (only thinking or tool calls) are skipped first.
+    pub async fn read_last_n_turns(
+        parser: &dyn TranscriptParser,
+        path: impl AsRef<Path>,
+        n: usize,
+        options: TurnOptions,
+    ) -> Result<Vec<String>> {
         let n = n.max(1); // Ensure at least 1 turn
 
-        // Read all lines into memory (transcript files are typically small)
-        let file = File::open(path.as_ref()).await.map_err(|e| {
-            VoiceError::Transcript(format!("Failed to open transcript file: {}", e))
-        })?;
+        // Read the whole file (transcript files are typically small)
+        let content = read_transcript(path.as_ref()).await?;
+        let turns = parser.extract_turns(&content, options);
+
+        // Fallback: No user messages found, read last 1 text block
+        if turns.is_empty() {
+            tracing::debug!("No user messages found in transcript, fallback to last 1 text block");
+            return Self::read_last_n_texts(parser, path, 1).await;
+        }
+
+        // Not enough turns: start from the first one
+        let start = turns.len().saturating_sub(n);
+        Ok(turns
+            .into_iter()
+            .skip(start)
+            .flat_map(|turn| turn.assistant)
+            .collect())
+    }
+
+    /// Read assistant texts from as many recent turns as fit `char_budget`.
+    ///
+    /// The latest turn is always included. Earlier turns are added one at a
+    /// time while the total text stays within `char_budget` characters, up to
+    /// `max_turns` turns, so short exchanges bring in more history than long ones.
+    /// Falls back and skips empty turns like `read_last_n_turns`.
+    pub async fn read_turns_until_budget(
+        parser: &dyn TranscriptParser,
+        path: impl AsRef<Path>,
+        char_budget: usize,
+        max_turns: usize,
+        options: TurnOptions,
+    ) -> Result<Vec<String>> {
+        let content = read_transcript(path.as_ref()).await?;
+        let turns = parser.extract_turns(&content, options);
+
+        if turns.is_empty() {
+            tracing::debug!("No user messages found in transcript, fallback to last 1 text block");
+            return Self::read_last_n_texts(parser, path, 1).await;
+        }
+
+        let text_chars =
+            |texts: &[String]| -> usize { texts.iter().map(|t| t.chars().count()).sum() };
+
+        let max_turns = max_turns.clamp(1, turns.len());
+        let mut texts: Vec<String> = Vec::new();
+        for (taken, turn) in turns.into_iter().rev().take(max_turns).enumerate() {
+            if taken > 0 && text_chars(&texts) + text_chars(&turn.assistant) > char_budget {
+                break;
+            }
+            texts.splice(0..0, turn.assistant);
+        }
+
+        tracing::debug!(
+            "Auto turns: {} text blocks, {} chars (budget {})",
+            texts.len(),
+            text_chars(&texts),
+            char_budget
+        );
+        Ok(texts)
+    }
+
+    /// File changes made by tools in the latest turn (see
+    /// `ContentBlock::diff_excerpt`), oldest first, cut to `max_chars` in total.
+    pub async fn read_last_turn_changes(
+        path: impl AsRef<Path>,
+        max_chars: usize,
+    ) -> Result<Vec<String>> {
+        let lines = Self::read_nonempty_lines(path.as_ref()).await?;
+        let start = Self::turn_starts(&lines, TurnBoundary::User)
+            .last()
+            .copied()
+            .unwrap_or(0);
+
+        let mut changes = Vec::new();
+        let mut remaining = max_chars;
+        for line in &lines[start..] {
+            let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) else {
+                continue;
+            };
+            let Some(MessageContent::Blocks(blocks)) = entry.message.map(|m| m.content) else {
+                continue;
+            };
+            for excerpt in blocks.iter().filter_map(ContentBlock::diff_excerpt) {
+                if remaining == 0 {
+                    return Ok(changes);
+                }
+                let excerpt: String = excerpt.chars().take(remaining).collect();
+                remaining -= excerpt.chars().count();
+                changes.push(excerpt);
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Text of the latest human-authored user message (tool results skipped),
+    /// or None when the transcript has none
+    pub async fn read_last_user_prompt(path: impl AsRef<Path>) -> Result<Option<String>> {
+        let lines = Self::read_nonempty_lines(path.as_ref()).await?;
+        Ok(Self::turn_starts(&lines, TurnBoundary::User)
+            .last()
+            .and_then(|&start| Self::human_text(&lines[start])))
+    }
 
-        let reader = BufReader::new(file);
-        let mut lines_vec = Vec::new();
-        let mut lines = reader.lines();
+    /// Entry counts, turn boundaries and what the last 1-3 turns extract to,
+    /// for troubleshooting why a summary picked certain text
+    pub async fn stats(path: impl AsRef<Path>, options: TurnOptions) -> Result<TranscriptStats> {
+        let path = path.as_ref();
+        let lines = Self::read_nonempty_lines(path).await?;
+        let mut stats = TranscriptStats {
+            entries: lines.len(),
+            ..Default::default()
+        };
 
-        while let Some(line) = lines.next_line().await? {
-            if !line.trim().is_empty() {
-                lines_vec.push(line);
+        for line in &lines {
+            let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) else {
+                stats.unparsed += 1;
+                continue;
+            };
+            let Some(message) = entry.message else {
+                continue;
+            };
+            match message.role.as_str() {
+                "user" if message.is_human_text() => stats.user += 1,
+                "user" => stats.tool_results += 1,
+                "assistant" => {
+                    stats.assistant += 1;
+                    if let MessageContent::Blocks(blocks) = &message.content {
+                        stats.tool_calls += blocks
+                            .iter()
+                            .filter(|b| matches!(b, ContentBlock::ToolUse { .. }))
+                            .count();
+                    }
+                }
+                _ => {}
             }
         }
 
-        // Find human user message indices (turn boundaries).
-        // In Claude Code transcripts, tool_result entries also have type="user"
-        // and role="user", but they should NOT be treated as turn boundaries.
-        // Only real human input (text content) marks a new turn.
-        let mut user_indices = Vec::new();
-        for (idx, line) in lines_vec.iter().enumerate() {
+        stats.turn_starts = Self::turn_starts(&lines, options.boundary)
+            .into_iter()
+            .map(|idx| {
+                let text = serde_json::from_str::<TranscriptEntry>(&lines[idx])
+                    .ok()
+                    .and_then(|e| e.message)
+                    .map(|m| m.extract_texts().join(" "))
+                    .unwrap_or_default();
+                (idx, preview(&text))
+            })
+            .collect();
+
+        for n in 1..=3 {
+            stats
+                .extracted
+                .push(Self::read_last_n_turns(&ClaudeCodeParser, path, n, options).await?);
+        }
+        Ok(stats)
+    }
+
+    /// Indices of lines that start a turn: human user messages, or with
+    /// `TurnBoundary::Assistant`, assistant messages that have text.
+    ///
+    /// In Claude Code transcripts, tool_result entries also have type="user"
+    /// and role="user", but they should NOT be treated as turn boundaries.
+    /// Only real human input (text content) marks a new turn.
+    fn turn_starts(lines: &[String], boundary: TurnBoundary) -> Vec<usize> {
+        let (role, entry_type) = match boundary {
+            TurnBoundary::User => ("user", "user"),
+            TurnBoundary::Assistant => ("assistant", "assistant"),
+        };
+        let mut indices = Vec::new();
+        for (idx, line) in lines.iter().enumerate() {
             if let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) {
-                let is_user = entry.entry_type == "user"
+                let is_role = entry.entry_type == entry_type
                     || (entry.entry_type == "message"
-                        && entry.message.as_ref().is_some_and(|m| m.role == "user"));
+                        && entry.message.as_ref().is_some_and(|m| m.role == role));
 
-                if is_user {
+                if is_role {
                     if let Some(ref message) = entry.message {
-                        if message.is_human_text() {
-                            user_indices.push(idx);
+                        let starts_turn = match boundary {
+                            TurnBoundary::User => message.is_human_text(),
+                            TurnBoundary::Assistant => {
+                                message.extract_texts().iter().any(|t| !t.trim().is_empty())
+                            }
+                        };
+                        if starts_turn {
+                            indices.push(idx);
                         }
                     }
                 }
             }
         }
+        indices
+    }
 
-        // Fallback: No user messages found, read last 1 text block
-        if user_indices.is_empty() {
-            tracing::debug!("No user messages found in transcript, fallback to last 1 text block");
-            return Self::read_last_n_texts(path, 1).await;
+    /// Cut trailing turns whose assistant messages have no meaningful text
+    /// (thinking or tool calls only) off `lines`, so the last remaining
+    /// turn is the latest substantive one. Keeps everything when no turn
+    /// has text.
+    fn drop_empty_turns(lines: &mut Vec<String>, turn_starts: &mut Vec<usize>) {
+        let turn_end = |i: usize| turn_starts.get(i + 1).copied().unwrap_or(lines.len());
+        let Some(last_substantive) = (0..turn_starts.len()).rev().find(|&i| {
+            Self::assistant_texts_in(&lines[turn_starts[i]..turn_end(i)])
+                .iter()
+                .any(|t| !t.trim().is_empty())
+        }) else {
+            return;
+        };
+        let end = turn_end(last_substantive);
+        if end < lines.len() {
+            tracing::debug!(
+                "Skipping {} trailing turn(s) without assistant text",
+                turn_starts.len() - last_substantive - 1
+            );
+            lines.truncate(end);
+            turn_starts.truncate(last_substantive + 1);
         }
+    }
 
-        // Calculate start index: position of the Nth-last user message
-        let start_idx = if user_indices.len() >= n {
-            user_indices[user_indices.len() - n]
-        } else {
-            // Not enough turns, start from the first user message
-            user_indices[0]
-        };
+    /// Read assistant texts after the first `skip` non-empty lines.
+    ///
+    /// Returns the texts and the transcript's current non-empty line count, which
+    /// the caller stores as the next starting point. When `skip` is past EOF (the
+    /// transcript was rewritten), no texts are returned.
+    pub async fn read_texts_after(
+        path: impl AsRef<Path>,
+        skip: usize,
+    ) -> Result<(Vec<String>, usize)> {
+        let lines = Self::read_nonempty_lines(path.as_ref()).await?;
+        let texts = lines
+            .get(skip..)
+            .map(Self::assistant_texts_in)
+            .unwrap_or_default();
+        Ok((texts, lines.len()))
+    }
+
+    /// Number of non-empty lines in the transcript
+    pub async fn line_count(path: impl AsRef<Path>) -> Result<usize> {
+        Ok(Self::read_nonempty_lines(path.as_ref()).await?.len())
+    }
+
+    async fn read_nonempty_lines(path: &Path) -> Result<Vec<String>> {
+        Ok(nonempty_lines(&read_transcript(path).await?))
+    }
 
-        // Extract assistant texts from start_idx to EOF
+    /// Text of `line` when it is a human-authored user message
+    fn human_text(line: &str) -> Option<String> {
+        serde_json::from_str::<TranscriptEntry>(line)
+            .ok()
+            .and_then(|entry| entry.message)
+            .filter(|message| message.role == "user" && message.is_human_text())
+            .map(|message| message.extract_texts().join("\n").trim().to_string())
+            .filter(|text| !text.is_empty())
+    }
+
+    fn assistant_texts_in(lines: &[String]) -> Vec<String> {
         let mut texts = Vec::new();
-        for line in &lines_vec[start_idx..] {
+        for line in lines {
             if let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) {
                 let is_assistant = entry.entry_type == "assistant"
                     || (entry.entry_type == "message"
@@ -237,11 +702,44 @@ impl TranscriptReader {
                 }
             }
         }
+        texts
+    }
+}
 
-        Ok(texts)
+impl TranscriptParser for ClaudeCodeParser {
+    fn extract_turns(&self, transcript: &str, options: TurnOptions) -> Vec<Turn> {
+        let mut lines = nonempty_lines(transcript);
+        let mut starts = TranscriptReader::turn_starts(&lines, options.boundary);
+        if options.skip_empty {
+            TranscriptReader::drop_empty_turns(&mut lines, &mut starts);
+        }
+
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let end = starts.get(i + 1).copied().unwrap_or(lines.len());
+                Turn {
+                    user: TranscriptReader::human_text(&lines[start]),
+                    assistant: TranscriptReader::assistant_texts_in(&lines[start..end]),
+                }
+            })
+            .collect()
+    }
+
+    fn assistant_texts(&self, transcript: &str) -> Vec<String> {
+        TranscriptReader::assistant_texts_in(&nonempty_lines(transcript))
     }
 }
 
+fn nonempty_lines(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,7 +760,7 @@ mod tests {
         temp_file.write_all(jsonl_content.as_bytes()).unwrap();
         let path = temp_file.path();
 
-        let texts = TranscriptReader::read_assistant_texts(path, 10)
+        let texts = TranscriptReader::read_assistant_texts(&ClaudeCodeParser, path, 10)
             .await
             .unwrap();
 
@@ -272,6 +770,34 @@ mod tests {
         assert_eq!(texts[2], "Third response");
     }
 
+    #[tokio::test]
+    async fn test_read_texts_after() {
+        let jsonl_content = r#"{"type":"user","message":{"role":"user","content":"Add a parser"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Parser added"}]}}
+
+{"type":"user","message":{"role":"user","content":"Now tests"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Tests pass"}]}}
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+        let path = temp_file.path();
+
+        // Blank lines are not counted
+        let (texts, count) = TranscriptReader::read_texts_after(path, 2).await.unwrap();
+        assert_eq!(texts, vec!["Tests pass"]);
+        assert_eq!(count, 4);
+        assert_eq!(TranscriptReader::line_count(path).await.unwrap(), 4);
+
+        let (texts, _) = TranscriptReader::read_texts_after(path, 0).await.unwrap();
+        assert_eq!(texts, vec!["Parser added", "Tests pass"]);
+
+        // Marker past EOF: transcript was rewritten
+        let (texts, count) = TranscriptReader::read_texts_after(path, 9).await.unwrap();
+        assert!(texts.is_empty());
+        assert_eq!(count, 4);
+    }
+
     #[tokio::test]
     async fn test_read_last_n_texts() {
         let jsonl_content = r#"{"type":"message","message":{"role":"assistant","content":[{"type":"text","text":"Text 1"}]},"timestamp":"2025-01-22T10:00:01Z"}
@@ -285,7 +811,9 @@ mod tests {
         temp_file.write_all(jsonl_content.as_bytes()).unwrap();
         let path = temp_file.path();
 
-        let texts = TranscriptReader::read_last_n_texts(path, 3).await.unwrap();
+        let texts = TranscriptReader::read_last_n_texts(&ClaudeCodeParser, path, 3)
+            .await
+            .unwrap();
 
         assert_eq!(texts.len(), 3);
         assert_eq!(texts[0], "Text 3");
@@ -304,7 +832,7 @@ mod tests {
         temp_file.write_all(jsonl_content.as_bytes()).unwrap();
         let path = temp_file.path();
 
-        let texts = TranscriptReader::read_assistant_texts(path, 2)
+        let texts = TranscriptReader::read_assistant_texts(&ClaudeCodeParser, path, 2)
             .await
             .unwrap();
 
@@ -318,7 +846,7 @@ mod tests {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path();
 
-        let texts = TranscriptReader::read_assistant_texts(path, 10)
+        let texts = TranscriptReader::read_assistant_texts(&ClaudeCodeParser, path, 10)
             .await
             .unwrap();
 
@@ -336,7 +864,7 @@ invalid json line
         temp_file.write_all(jsonl_content.as_bytes()).unwrap();
         let path = temp_file.path();
 
-        let texts = TranscriptReader::read_assistant_texts(path, 10)
+        let texts = TranscriptReader::read_assistant_texts(&ClaudeCodeParser, path, 10)
             .await
             .unwrap();
 
@@ -346,6 +874,148 @@ invalid json line
         assert_eq!(texts[1], "Also valid");
     }
 
+    #[tokio::test]
+    async fn test_read_last_turn_changes() {
+        let jsonl_content = r#"{"type":"user","message":{"role":"user","content":"Rename the flag"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Edit","input":{"file_path":"old.rs","old_string":"a","new_string":"b"}}]}}
+{"type":"user","message":{"role":"user","content":"Now fix the parser"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Edit","input":{"file_path":"src/parser.rs","old_string":"let n = 0;","new_string":"let n = 1;"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"The file src/parser.rs has been updated."}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"git diff"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_2","content":[{"type":"text","text":"diff --git a/README.md b/README.md\nindex 1..2 100644\n--- a/README.md\n+++ b/README.md\n@@ -1,2 +1,2 @@\n # Parser\n-Counts from zero\n+Counts from one"}]}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Write","input":{"file_path":"notes.md","content":"one\ntwo\n"}}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Done."}]}}
+"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+
+        let changes = TranscriptReader::read_last_turn_changes(temp_file.path(), 10_000)
+            .await
+            .unwrap();
+
+        // Only the latest turn; plain tool output without a diff is skipped
+        assert_eq!(
+            changes,
+            [
+                "Edit src/parser.rs\n- let n = 0;\n+ let n = 1;",
+                "+++ b/README.md\n-Counts from zero\n+Counts from one",
+                "Write notes.md (2 lines)",
+            ]
+        );
+
+        let cut = TranscriptReader::read_last_turn_changes(temp_file.path(), 20)
+            .await
+            .unwrap();
+        assert_eq!(cut, ["Edit src/parser.rs\n-"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_last_user_prompt_skips_tool_results() {
+        let jsonl_content = r#"{"type":"user","message":{"role":"user","content":"Rename the flag"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Renamed."}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"text","text":"Now fix the parser"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"ok"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Fixed."}]}}
+"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+
+        let prompt = TranscriptReader::read_last_user_prompt(temp_file.path())
+            .await
+            .unwrap();
+        assert_eq!(prompt.as_deref(), Some("Now fix the parser"));
+
+        let mut no_user = NamedTempFile::new().unwrap();
+        no_user
+            .write_all(br#"{"type":"assistant","message":{"role":"assistant","content":"Hi"}}"#)
+            .unwrap();
+        let prompt = TranscriptReader::read_last_user_prompt(no_user.path())
+            .await
+            .unwrap();
+        assert_eq!(prompt, None);
+    }
+
+    /// Transcript with one user line and one assistant reply per entry of `replies`
+    fn turns_transcript(replies: &[String]) -> NamedTempFile {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        for (i, reply) in replies.iter().enumerate() {
+            writeln!(
+                temp_file,
+                r#"{{"type":"user","message":{{"role":"user","content":"Step {}"}}}}"#,
+                i
+            )
+            .unwrap();
+            writeln!(
+                temp_file,
+                r#"{{"type":"assistant","message":{{"role":"assistant","content":[{{"type":"text","text":"{}"}}]}}}}"#,
+                reply
+            )
+            .unwrap();
+        }
+        temp_file
+    }
+
+    #[tokio::test]
+    async fn test_budget_expands_over_tiny_turns() {
+        let replies: Vec<String> = (0..6).map(|i| format!("Done {}", i)).collect();
+        let file = turns_transcript(&replies);
+
+        // Plenty of budget: limited only by max_turns
+        let texts = TranscriptReader::read_turns_until_budget(
+            &ClaudeCodeParser,
+            file.path(),
+            1000,
+            4,
+            TurnOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(texts, ["Done 2", "Done 3", "Done 4", "Done 5"]);
+
+        // Budget of 13 chars fits two 6-char replies, not three
+        let texts = TranscriptReader::read_turns_until_budget(
+            &ClaudeCodeParser,
+            file.path(),
+            13,
+            10,
+            TurnOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(texts, ["Done 4", "Done 5"]);
+    }
+
+    #[tokio::test]
+    async fn test_budget_stops_at_large_turns() {
+        let replies = vec!["a".repeat(300), "b".repeat(300), "c".repeat(300)];
+        let file = turns_transcript(&replies);
+
+        // Two turns would be 600 chars: only the latest fits
+        let texts = TranscriptReader::read_turns_until_budget(
+            &ClaudeCodeParser,
+            file.path(),
+            500,
+            10,
+            TurnOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(texts, ["c".repeat(300)]);
+
+        // The latest turn is kept even when it alone exceeds the budget
+        let texts = TranscriptReader::read_turns_until_budget(
+            &ClaudeCodeParser,
+            file.path(),
+            100,
+            10,
+            TurnOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(texts, ["c".repeat(300)]);
+    }
+
     #[tokio::test]
     async fn test_read_last_one_turn() {
         let jsonl_content = r#"{"type":"conversation_start","timestamp":"2025-01-22T10:00:00Z"}
@@ -361,7 +1031,10 @@ invalid json line
         temp_file.write_all(jsonl_content.as_bytes()).unwrap();
         let path = temp_file.path();
 
-        let texts = TranscriptReader::read_last_n_turns(path, 1).await.unwrap();
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 1, TurnOptions::default())
+                .await
+                .unwrap();
 
         // Should only get the last turn (after "Run tests")
         assert_eq!(texts.len(), 2);
@@ -384,19 +1057,78 @@ invalid json line
         let path = temp_file.path();
 
         // Read last 2 turns
-        let texts = TranscriptReader::read_last_n_turns(path, 2).await.unwrap();
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 2, TurnOptions::default())
+                .await
+                .unwrap();
         assert_eq!(texts.len(), 2);
         assert_eq!(texts[0], "Response 2");
         assert_eq!(texts[1], "Response 3");
 
         // Read all 3 turns
-        let texts = TranscriptReader::read_last_n_turns(path, 3).await.unwrap();
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 3, TurnOptions::default())
+                .await
+                .unwrap();
         assert_eq!(texts.len(), 3);
         assert_eq!(texts[0], "Response 1");
         assert_eq!(texts[1], "Response 2");
         assert_eq!(texts[2], "Response 3");
     }
 
+    #[tokio::test]
+    async fn test_gzip_transcript_reads_same_turns() {
+        let jsonl_content = r#"{"type":"user","message":{"role":"user","content":"Add a parser"}}
+{"type":"assistant","message":{"role":"assistant","model":"claude-sonnet-4-5","content":[{"type":"text","text":"Parser added"}]}}
+{"type":"user","message":{"role":"user","content":"Now tests"}}
+{"type":"assistant","message":{"role":"assistant","model":"claude-sonnet-4-5","content":[{"type":"text","text":"Tests pass"}]}}
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("session.jsonl");
+        let compressed = dir.path().join("session.jsonl.gz");
+        std::fs::write(&plain, jsonl_content).unwrap();
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(jsonl_content.as_bytes()).unwrap();
+        std::fs::write(&compressed, encoder.finish().unwrap()).unwrap();
+
+        for turns in [1, 2] {
+            assert_eq!(
+                TranscriptReader::read_last_n_turns(
+                    &ClaudeCodeParser,
+                    &compressed,
+                    turns,
+                    TurnOptions::default()
+                )
+                .await
+                .unwrap(),
+                TranscriptReader::read_last_n_turns(
+                    &ClaudeCodeParser,
+                    &plain,
+                    turns,
+                    TurnOptions::default()
+                )
+                .await
+                .unwrap()
+            );
+        }
+        assert_eq!(
+            TranscriptReader::read_last_n_turns(
+                &ClaudeCodeParser,
+                &compressed,
+                1,
+                TurnOptions::default()
+            )
+            .await
+            .unwrap(),
+            vec!["Tests pass"]
+        );
+        assert_eq!(
+            TranscriptReader::detect_session_model(&compressed).await,
+            Some("claude-sonnet-4-5".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn test_read_last_n_turns_with_tools() {
         let jsonl_content = r#"{"type":"message","message":{"role":"user","content":[{"type":"text","text":"Do something"}]}}
@@ -409,7 +1141,10 @@ invalid json line
         temp_file.write_all(jsonl_content.as_bytes()).unwrap();
         let path = temp_file.path();
 
-        let texts = TranscriptReader::read_last_n_turns(path, 1).await.unwrap();
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 1, TurnOptions::default())
+                .await
+                .unwrap();
 
         // Should only extract text blocks, not tool_use
         assert_eq!(texts.len(), 2);
@@ -429,7 +1164,10 @@ invalid json line
         let path = temp_file.path();
 
         // Fallback: should return last 1 text block
-        let texts = TranscriptReader::read_last_n_turns(path, 1).await.unwrap();
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 1, TurnOptions::default())
+                .await
+                .unwrap();
         assert_eq!(texts.len(), 1);
         assert_eq!(texts[0], "Text 3");
     }
@@ -445,7 +1183,10 @@ invalid json line
         let path = temp_file.path();
 
         // Request 5 turns but only 1 exists - should return all texts from turn 1
-        let texts = TranscriptReader::read_last_n_turns(path, 5).await.unwrap();
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 5, TurnOptions::default())
+                .await
+                .unwrap();
         assert_eq!(texts.len(), 1);
         assert_eq!(texts[0], "Response");
     }
@@ -467,7 +1208,10 @@ invalid json line
         temp_file.write_all(jsonl_content.as_bytes()).unwrap();
         let path = temp_file.path();
 
-        let texts = TranscriptReader::read_last_n_turns(path, 1).await.unwrap();
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 1, TurnOptions::default())
+                .await
+                .unwrap();
 
         // Should get ALL assistant texts from the turn, not just the ones after
         // the last tool_result. tool_result entries should not split the turn.
@@ -476,6 +1220,268 @@ invalid json line
         assert_eq!(texts[1], "Bug fixed successfully");
     }
 
+    #[tokio::test]
+    async fn test_skip_empty_turns_walks_back_to_substantive_turn() {
+        // The final turn has only thinking, a tool call and blank text
+        let jsonl_content = r#"{"type":"user","message":{"role":"user","content":"Fix the parser"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Parser fixed, all tests pass"}]}}
+{"type":"user","message":{"role":"user","content":"Thanks"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"Nothing left to do"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"git status"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"clean"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"  "}]}}
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+        let path = temp_file.path();
+
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 1, TurnOptions::default())
+                .await
+                .unwrap();
+        assert_eq!(texts, ["  "]);
+
+        let skip_empty = TurnOptions {
+            skip_empty: true,
+            ..Default::default()
+        };
+        let texts = TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 1, skip_empty)
+            .await
+            .unwrap();
+        assert_eq!(texts, ["Parser fixed, all tests pass"]);
+
+        let texts =
+            TranscriptReader::read_turns_until_budget(&ClaudeCodeParser, path, 1000, 1, skip_empty)
+                .await
+                .unwrap();
+        assert_eq!(texts, ["Parser fixed, all tests pass"]);
+    }
+
+    #[tokio::test]
+    async fn test_turn_boundary_user_vs_assistant() {
+        let jsonl_content = r#"{"type":"user","message":{"role":"user","content":"Write a function"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Here's the code"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Function done"}]}}
+{"type":"user","message":{"role":"user","content":"Run tests"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Running tests"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"ok"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Tests passed"}]}}
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+        let path = temp_file.path();
+        let by_assistant = TurnOptions {
+            boundary: TurnBoundary::Assistant,
+            ..Default::default()
+        };
+
+        // One turn: the whole last exchange, or only the last response
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 1, TurnOptions::default())
+                .await
+                .unwrap();
+        assert_eq!(texts, ["Running tests", "Tests passed"]);
+        let texts = TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 1, by_assistant)
+            .await
+            .unwrap();
+        assert_eq!(texts, ["Tests passed"]);
+
+        // Three turns: everything under user boundaries, the last three
+        // responses under assistant ones
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 3, TurnOptions::default())
+                .await
+                .unwrap();
+        assert_eq!(texts.len(), 4);
+        let texts = TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 3, by_assistant)
+            .await
+            .unwrap();
+        assert_eq!(texts, ["Function done", "Running tests", "Tests passed"]);
+
+        // Auto mode counts turns the same way
+        let texts = TranscriptReader::read_turns_until_budget(
+            &ClaudeCodeParser,
+            path,
+            1000,
+            2,
+            by_assistant,
+        )
+        .await
+        .unwrap();
+        assert_eq!(texts, ["Running tests", "Tests passed"]);
+    }
+
+    #[tokio::test]
+    async fn test_stats_counts_entries_and_previews_extraction() {
+        let jsonl_content = r#"{"type":"summary","summary":"Earlier work"}
+{"type":"user","message":{"role":"user","content":"Fix the parser"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Looking at it"},{"type":"tool_use","name":"Read","input":{"file_path":"src/parser.rs"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"fn parse() {}"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Parser fixed"}]}}
+not json
+{"type":"user","message":{"role":"user","content":"Now add tests"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Write","input":{"file_path":"tests/parser.rs","content":"fn it_parses() {}"}}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Tests added"}]}}
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+
+        let stats = TranscriptReader::stats(temp_file.path(), TurnOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(stats.entries, 9);
+        assert_eq!(stats.unparsed, 1);
+        assert_eq!(stats.user, 2);
+        assert_eq!(stats.assistant, 4);
+        assert_eq!(stats.tool_calls, 2);
+        assert_eq!(stats.tool_results, 1);
+        assert_eq!(
+            stats.turn_starts,
+            [
+                (1, "Fix the parser".to_string()),
+                (6, "Now add tests".to_string())
+            ]
+        );
+        assert_eq!(stats.extracted[0], ["Tests added"]);
+        assert_eq!(
+            stats.extracted[1],
+            ["Looking at it", "Parser fixed", "Tests added"]
+        );
+        // Only two turns: asking for three reads the same as two
+        assert_eq!(stats.extracted[2], stats.extracted[1]);
+
+        let report = stats.to_string();
+        assert!(report.contains("Turn starts (2):\n  [1] Fix the parser\n  [6] Now add tests"));
+        assert!(report.contains("Last 1 turn(s), 1 text(s):\n  - Tests added"));
+    }
+
+    #[test]
+    fn test_stats_preview_truncates_long_text() {
+        assert_eq!(preview("short\n  text"), "short text");
+        let long = "word ".repeat(30);
+        let shown = preview(&long);
+        assert!(shown.ends_with("..."));
+        assert_eq!(shown.chars().count(), STATS_PREVIEW_CHARS + 3);
+    }
+
+    /// `read_last_n_turns` as it was before turn extraction moved behind
+    /// `TranscriptParser`: turn start indices over the raw lines
+    fn index_based_last_n_turns(content: &str, n: usize, options: TurnOptions) -> Vec<String> {
+        let mut lines = nonempty_lines(content);
+        let mut starts = TranscriptReader::turn_starts(&lines, options.boundary);
+        if options.skip_empty {
+            TranscriptReader::drop_empty_turns(&mut lines, &mut starts);
+        }
+        match starts.len().checked_sub(n.max(1)) {
+            _ if starts.is_empty() => {
+                let texts = TranscriptReader::assistant_texts_in(&lines);
+                texts.last().cloned().into_iter().collect()
+            }
+            Some(i) => TranscriptReader::assistant_texts_in(&lines[starts[i]..]),
+            None => TranscriptReader::assistant_texts_in(&lines[starts[0]..]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claude_code_parser_matches_index_based_turns() {
+        let transcripts = [
+            // Claude Code entries with tool calls, tool results and thinking
+            r#"{"type":"user","message":{"role":"user","content":"Write a parser"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Here's the parser"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Write","input":{"file_path":"parser.rs","content":"fn parse() {}"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"ok"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Parser written"}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"text","text":"Run the tests"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Running tests"},{"type":"text","text":"All green"}]}}
+{"type":"user","message":{"role":"user","content":"Commit it"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"Commit now"}]}}
+"#,
+            // Legacy "message" entries, with text before the first user turn
+            r#"{"type":"message","message":{"role":"assistant","content":[{"type":"text","text":"Welcome"}]}}
+{"type":"message","message":{"role":"user","content":[{"type":"text","text":"Hi"}]}}
+{"type":"message","message":{"role":"assistant","content":[{"type":"text","text":"Hello"}]}}
+not json
+{"type":"message","message":{"role":"user","content":[{"type":"text","text":"Bye"}]}}
+{"type":"message","message":{"role":"assistant","content":[{"type":"text","text":"Goodbye"}]}}
+"#,
+            // No user messages at all
+            r#"{"type":"message","message":{"role":"assistant","content":[{"type":"text","text":"Text 1"}]}}
+{"type":"message","message":{"role":"assistant","content":[{"type":"text","text":"Text 2"}]}}
+"#,
+            "",
+        ];
+        let options = [
+            TurnOptions::default(),
+            TurnOptions {
+                skip_empty: true,
+                ..TurnOptions::default()
+            },
+            TurnOptions {
+                boundary: TurnBoundary::Assistant,
+                ..TurnOptions::default()
+            },
+            TurnOptions {
+                boundary: TurnBoundary::Assistant,
+                skip_empty: true,
+            },
+        ];
+
+        for content in transcripts {
+            let mut file = NamedTempFile::new().unwrap();
+            file.write_all(content.as_bytes()).unwrap();
+            for options in options {
+                for n in 0..=4 {
+                    let texts = TranscriptReader::read_last_n_turns(
+                        &ClaudeCodeParser,
+                        file.path(),
+                        n,
+                        options,
+                    )
+                    .await
+                    .unwrap();
+                    assert_eq!(
+                        texts,
+                        index_based_last_n_turns(content, n, options),
+                        "n={} options={:?} transcript={:?}",
+                        n,
+                        options,
+                        content
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_claude_code_parser_turns_carry_user_prompt() {
+        let content = r#"{"type":"user","message":{"role":"user","content":"Run the tests"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"ok"}]}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"All green"}]}}
+"#;
+        let turns = ClaudeCodeParser.extract_turns(content, TurnOptions::default());
+        assert_eq!(
+            turns,
+            [Turn {
+                user: Some("Run the tests".to_string()),
+                assistant: vec!["All green".to_string()],
+            }]
+        );
+
+        let by_assistant = TurnOptions {
+            boundary: TurnBoundary::Assistant,
+            ..TurnOptions::default()
+        };
+        assert_eq!(
+            ClaudeCodeParser.extract_turns(content, by_assistant)[0].user,
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_read_last_n_turns_claude_code_format() {
         // Uses Claude Code's native format: type="user"/"assistant" (not "message")
@@ -493,16 +1499,161 @@ invalid json line
         let path = temp_file.path();
 
         // Last 1 turn should be "Deploy it" and all its assistant responses
-        let texts = TranscriptReader::read_last_n_turns(path, 1).await.unwrap();
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 1, TurnOptions::default())
+                .await
+                .unwrap();
         assert_eq!(texts.len(), 2);
         assert_eq!(texts[0], "Deploying now");
         assert_eq!(texts[1], "Deployment complete");
 
         // Last 2 turns should include both
-        let texts = TranscriptReader::read_last_n_turns(path, 2).await.unwrap();
+        let texts =
+            TranscriptReader::read_last_n_turns(&ClaudeCodeParser, path, 2, TurnOptions::default())
+                .await
+                .unwrap();
         assert_eq!(texts.len(), 3);
         assert_eq!(texts[0], "Here is the summary");
         assert_eq!(texts[1], "Deploying now");
         assert_eq!(texts[2], "Deployment complete");
     }
+
+    #[tokio::test]
+    async fn test_single_object_content() {
+        let jsonl_content = r#"{"type":"user","message":{"role":"user","content":{"type":"text","text":"Fix the build"}}}
+{"type":"assistant","message":{"role":"assistant","content":{"type":"text","text":"Build fixed"}}}
+{"type":"assistant","message":{"role":"assistant","content":{"type":"tool_use","name":"Bash","input":{"command":"make"}}}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"All green"}]}}
+"#;
+
+        let entry: TranscriptEntry =
+            serde_json::from_str(jsonl_content.lines().next().unwrap()).unwrap();
+        let message = entry.message.unwrap();
+        assert!(matches!(&message.content, MessageContent::Blocks(blocks) if blocks.len() == 1));
+        assert!(message.is_human_text());
+        assert_eq!(message.extract_texts(), vec!["Fix the build"]);
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+        let texts = TranscriptReader::read_last_n_turns(
+            &ClaudeCodeParser,
+            temp_file.path(),
+            1,
+            TurnOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(texts, vec!["Build fixed", "All green"]);
+    }
+
+    #[tokio::test]
+    async fn test_detect_session_model() {
+        let jsonl_content = r#"{"type":"user","message":{"role":"user","content":"Fix the bug"}}
+{"type":"assistant","message":{"role":"assistant","model":"claude-opus-4-1-20250805","content":[{"type":"text","text":"Looking"}]}}
+{"type":"assistant","message":{"role":"assistant","model":"claude-sonnet-4-5-20250929","content":[{"type":"text","text":"Fixed"}]}}
+{"type":"assistant","message":{"role":"assistant","model":"<synthetic>","content":[{"type":"text","text":"No response requested."}]}}
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+
+        let model = TranscriptReader::detect_session_model(temp_file.path()).await;
+        assert_eq!(model.as_deref(), Some("claude-sonnet-4-5-20250929"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_session_model_absent() {
+        let jsonl_content = r#"{"type":"message","message":{"role":"assistant","content":[{"type":"text","text":"No model field"}]}}
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+
+        assert_eq!(
+            TranscriptReader::detect_session_model(temp_file.path()).await,
+            None
+        );
+        assert_eq!(
+            TranscriptReader::detect_session_model("/nonexistent/transcript.jsonl").await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_summary_and_system_entries() {
+        let jsonl_content = r#"{"type":"summary","summary":"Refactored the config loader","leafUuid":"abc-123"}
+{"type":"system","subtype":"compact_boundary","content":"Conversation compacted","level":"info"}
+{"type":"user","message":{"role":"user","content":"Fix the bug"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"Let me look","signature":"sig"},{"type":"redacted_thinking","data":"xyz"},{"type":"text","text":"Fixed it"}]}}
+{"type":"summary","summary":"Fixed the login bug","leafUuid":"def-456"}
+"#;
+
+        let entries: Vec<TranscriptEntry> = jsonl_content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let kinds: Vec<_> = entries.iter().map(|e| e.kind()).collect();
+        assert_eq!(
+            kinds,
+            [
+                EntryKind::Summary,
+                EntryKind::System,
+                EntryKind::User,
+                EntryKind::Assistant,
+                EntryKind::Summary
+            ]
+        );
+        assert_eq!(entries[1].subtype.as_deref(), Some("compact_boundary"));
+
+        // Thinking blocks parse but are never treated as spoken text
+        let message = entries[3].message.as_ref().unwrap();
+        match &message.content {
+            MessageContent::Blocks(blocks) => {
+                assert!(
+                    matches!(&blocks[0], ContentBlock::Thinking { thinking } if thinking == "Let me look")
+                );
+                assert!(matches!(blocks[1], ContentBlock::RedactedThinking));
+            }
+            _ => panic!("Expected content blocks"),
+        }
+        assert_eq!(message.extract_texts(), vec!["Fixed it"]);
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+        let summary = TranscriptReader::read_native_summary(temp_file.path())
+            .await
+            .unwrap();
+        assert_eq!(summary.as_deref(), Some("Fixed the login bug"));
+    }
+
+    #[tokio::test]
+    async fn test_read_native_summary_absent() {
+        let jsonl_content = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Done"}]}}
+{"type":"summary","summary":"   "}
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+        let summary = TranscriptReader::read_native_summary(temp_file.path())
+            .await
+            .unwrap();
+        assert_eq!(summary, None);
+    }
+
+    #[tokio::test]
+    async fn test_read_native_summary_ignores_earlier_turns() {
+        let jsonl_content = r#"{"type":"user","message":{"role":"user","content":"Fix the bug"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Fixed it"}]}}
+{"type":"summary","summary":"Fixed the login bug","leafUuid":"def-456"}
+{"type":"user","message":{"role":"user","content":"Now add tests"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Added three tests"}]}}
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(jsonl_content.as_bytes()).unwrap();
+        let summary = TranscriptReader::read_native_summary(temp_file.path())
+            .await
+            .unwrap();
+        assert_eq!(summary, None);
+    }
 }
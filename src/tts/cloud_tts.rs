@@ -1,4 +1,4 @@
-// Google Cloud Text-to-Speech provider using service account authentication
+// Google Cloud Text-to-Speech provider (service account or API key authentication)
 // Supports LINEAR16 audio with volume control via afplay
 
 use async_trait::async_trait;
@@ -7,13 +7,17 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use super::TtsProvider;
+use super::{TtsCapabilities, TtsProvider};
 use crate::error::{Result, VoiceError};
+use crate::retry::{retry_async, RetryPolicy};
 use crate::tts::cloud_tts_auth::CloudTtsAuth;
 
 const API_ENDPOINT: &str = "https://texttospeech.googleapis.com/v1/text:synthesize";
 const COST_PER_CHAR: f64 = 0.000004; // $4 per 1M chars (Standard voices)
 const MAX_TEXT_BYTES: usize = 5000;
+const RETRY_POLICY: RetryPolicy = RetryPolicy::new(3, Duration::from_millis(250))
+    .with_max_delay(Duration::from_secs(2))
+    .with_jitter(0.25);
 // Gemini-TTS caps input `text` at 4000 bytes (prompt is billed separately).
 const MAX_TEXT_BYTES_GEMINI: usize = 4000;
 // Gemini-TTS is billed per audio token, not per character. Rough estimate:
@@ -25,6 +29,8 @@ const COST_PER_CHAR_GEMINI: f64 = 0.0000167;
 pub struct CloudTtsProvider {
     auth: CloudTtsAuth,
     service_account_json: String,
+    /// API key auth (`?key=`), used instead of the service account when set
+    api_key: Option<String>,
     voice: String,
     language_code: String,
     /// When set (e.g. "gemini-2.5-flash-tts"), switches to the Gemini-TTS
@@ -33,6 +39,9 @@ pub struct CloudTtsProvider {
     /// Optional Gemini-TTS style instruction, sent as `input.prompt`.
     style_prompt: Option<String>,
     volume: u32,
+    /// `audioConfig.speakingRate` (0.25-4.0, 1.0 = normal). Unset uses the
+    /// voice's own rate
+    speaking_rate: Option<f32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -45,7 +54,12 @@ struct TtsRequest {
 
 #[derive(Debug, Serialize)]
 struct TextInput {
+    /// Plain text input. Omitted when `ssml` is sent instead.
+    #[serde(skip_serializing_if = "String::is_empty")]
     text: String,
+    /// Authored SSML input, passed through untouched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssml: Option<String>,
     /// Gemini-TTS style instruction. Omitted from the wire for traditional voices.
     #[serde(skip_serializing_if = "Option::is_none")]
     prompt: Option<String>,
@@ -65,6 +79,8 @@ struct VoiceSelection {
 struct AudioConfig {
     #[serde(rename = "audioEncoding")]
     audio_encoding: String,
+    #[serde(rename = "speakingRate", skip_serializing_if = "Option::is_none")]
+    speaking_rate: Option<f32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -88,11 +104,42 @@ impl CloudTtsProvider {
         Self {
             auth: CloudTtsAuth::new(service_account_json.clone()),
             service_account_json,
+            api_key: None,
             voice,
             language_code: lang_code,
             model,
             style_prompt,
             volume,
+            speaking_rate: None,
+        }
+    }
+
+    /// Speak at `speaking_rate` times the normal rate, clamped to 0.25-4.0
+    pub fn with_speaking_rate(mut self, speaking_rate: Option<f32>) -> Self {
+        self.speaking_rate = speaking_rate.map(|r| r.clamp(0.25, 4.0));
+        self
+    }
+
+    /// Provider authenticating with a Cloud API key (Text-to-Speech API enabled)
+    /// instead of a service account.
+    pub fn with_api_key(
+        api_key: String,
+        voice: String,
+        language_code: Option<String>,
+        model: Option<String>,
+        style_prompt: Option<String>,
+        volume: u32,
+    ) -> Self {
+        Self {
+            api_key: Some(api_key),
+            ..Self::new(
+                String::new(),
+                voice,
+                language_code,
+                model,
+                style_prompt,
+                volume,
+            )
         }
     }
 
@@ -130,12 +177,19 @@ impl CloudTtsProvider {
         let mut chunks = Vec::new();
         let mut current = String::new();
 
-        for sentence in text.split_inclusive(&['.', '!', '?', '。', '!', '?']) {
-            if current.len() + sentence.len() > max_bytes && !current.is_empty() {
-                chunks.push(current.clone());
-                current.clear();
+        for sentence in super::split_sentences(text) {
+            // Latin sentences are rejoined with a space; CJK ones need none
+            let separator = if current.ends_with(|c: char| c.is_ascii()) {
+                " "
+            } else {
+                ""
+            };
+            if current.len() + separator.len() + sentence.len() > max_bytes && !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            } else {
+                current.push_str(separator);
             }
-            current.push_str(sentence);
+            current.push_str(&sentence);
         }
 
         if !current.is_empty() {
@@ -145,15 +199,10 @@ impl CloudTtsProvider {
         chunks
     }
 
-    /// Synthesize single text chunk
-    async fn synthesize_chunk(&self, text: &str) -> Result<Vec<u8>> {
-        let token = self.auth.get_token().await?;
-
-        let request = TtsRequest {
-            input: TextInput {
-                text: text.to_string(),
-                prompt: self.style_prompt.clone(),
-            },
+    /// Build a synthesis request for the given input
+    fn build_request(&self, input: TextInput) -> TtsRequest {
+        TtsRequest {
+            input,
             voice: VoiceSelection {
                 language_code: self.language_code.clone(),
                 name: self.voice.clone(),
@@ -161,25 +210,65 @@ impl CloudTtsProvider {
             },
             audio_config: AudioConfig {
                 audio_encoding: "LINEAR16".to_string(),
+                speaking_rate: self.speaking_rate,
             },
-        };
+        }
+    }
+
+    /// Synthesize single text chunk
+    async fn synthesize_chunk(&self, text: &str) -> Result<Vec<u8>> {
+        let request = self.build_request(TextInput {
+            text: text.to_string(),
+            ssml: None,
+            prompt: self.style_prompt.clone(),
+        });
+        self.synthesize(&request).await
+    }
 
+    /// Send a synthesis request and decode the returned audio, retrying
+    /// rate limits, server errors and dropped connections
+    async fn synthesize(&self, request: &TtsRequest) -> Result<Vec<u8>> {
         let client = Self::create_client()?;
-        let response = client
-            .post(API_ENDPOINT)
-            .bearer_auth(&token)
-            .json(&request)
+        retry_async(
+            || self.synthesize_once(&client, request),
+            &RETRY_POLICY,
+            |e: &SynthesisError| e.transient,
+        )
+        .await
+        .map_err(|e| e.error)
+    }
+
+    async fn synthesize_once(
+        &self,
+        client: &Client,
+        request: &TtsRequest,
+    ) -> std::result::Result<Vec<u8>, SynthesisError> {
+        let builder = match &self.api_key {
+            Some(key) => client.post(API_ENDPOINT).query(&[("key", key)]),
+            None => {
+                let token = self.auth.get_token().await?;
+                client.post(API_ENDPOINT).bearer_auth(&token)
+            }
+        };
+        let response = builder
+            .json(request)
             .send()
             .await
-            .map_err(|e| VoiceError::Voice(format!("Cloud TTS API request failed: {}", e)))?;
+            .map_err(|e| SynthesisError {
+                error: VoiceError::Voice(format!("Cloud TTS API request failed: {}", e)),
+                transient: e.is_connect() || e.is_timeout(),
+            })?;
 
         let status = response.status();
         if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(VoiceError::Voice(format!(
-                "Cloud TTS API error ({}): {}",
-                status, error_text
-            )));
+            return Err(SynthesisError {
+                error: VoiceError::Voice(format!(
+                    "Cloud TTS API error ({}): {}",
+                    status, error_text
+                )),
+                transient: is_transient_status(status),
+            });
         }
 
         let tts_response: TtsResponse = response
@@ -187,12 +276,14 @@ impl CloudTtsProvider {
             .await
             .map_err(|e| VoiceError::Voice(format!("Failed to parse Cloud TTS response: {}", e)))?;
 
-        // Decode base64 audio
-        let audio_data = base64::engine::general_purpose::STANDARD
-            .decode(&tts_response.audio_content)
-            .map_err(|e| VoiceError::Voice(format!("Failed to decode audio: {}", e)))?;
+        Ok(Self::decode_audio_content(&tts_response.audio_content)?)
+    }
 
-        Ok(audio_data)
+    /// Decode the base64 `audioContent` (LINEAR16 WAV) from a synthesis response
+    fn decode_audio_content(audio_content: &str) -> Result<Vec<u8>> {
+        base64::engine::general_purpose::STANDARD
+            .decode(audio_content)
+            .map_err(|e| VoiceError::Voice(format!("Failed to decode audio: {}", e)))
     }
 
     /// Play audio data using afplay
@@ -210,6 +301,43 @@ impl CloudTtsProvider {
     }
 }
 
+/// A failed synthesis attempt and whether trying again may succeed
+struct SynthesisError {
+    error: VoiceError,
+    transient: bool,
+}
+
+impl From<VoiceError> for SynthesisError {
+    fn from(error: VoiceError) -> Self {
+        Self {
+            error,
+            transient: false,
+        }
+    }
+}
+
+impl std::fmt::Display for SynthesisError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+/// Rate limited or a server-side failure
+fn is_transient_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// What SumVox can do with this provider
+pub const CAPABILITIES: TtsCapabilities = TtsCapabilities {
+    // Traditional voices only, not Gemini-TTS models
+    ssml: true,
+    // audioConfig.speakingRate
+    rate: true,
+    volume: true,
+    voices: true,
+    streaming: false,
+};
+
 #[async_trait]
 impl TtsProvider for CloudTtsProvider {
     fn name(&self) -> &str {
@@ -218,6 +346,10 @@ impl TtsProvider for CloudTtsProvider {
 
     fn is_available(&self) -> bool {
         !self.service_account_json.is_empty()
+            || self
+                .api_key
+                .as_ref()
+                .is_some_and(|k| !k.is_empty() && !k.starts_with("${"))
     }
 
     async fn speak(&self, text: &str) -> Result<bool> {
@@ -255,6 +387,49 @@ impl TtsProvider for CloudTtsProvider {
         Ok(true)
     }
 
+    fn supports_ssml(&self) -> bool {
+        // Gemini-TTS models take free-form text with a style prompt, not SSML
+        !self.is_gemini()
+    }
+
+    async fn speak_ssml(&self, ssml: &str) -> Result<bool> {
+        if !self.supports_ssml() {
+            return Err(VoiceError::Voice(
+                "Gemini-TTS models do not accept SSML input".into(),
+            ));
+        }
+        // SSML can't be split at sentence boundaries without breaking markup
+        if ssml.len() > MAX_TEXT_BYTES {
+            return Err(VoiceError::Voice(format!(
+                "SSML input is {} bytes, exceeding the {} byte limit",
+                ssml.len(),
+                MAX_TEXT_BYTES
+            )));
+        }
+
+        tracing::info!(
+            "Speaking SSML with Cloud TTS: voice={}, bytes={}",
+            self.voice,
+            ssml.len()
+        );
+
+        let request = self.build_request(TextInput {
+            text: String::new(),
+            ssml: Some(ssml.to_string()),
+            prompt: None,
+        });
+        let audio = self.synthesize(&request).await?;
+        self.play_audio(&audio)?;
+        Ok(true)
+    }
+
+    fn capabilities(&self) -> TtsCapabilities {
+        TtsCapabilities {
+            ssml: self.supports_ssml(),
+            ..CAPABILITIES
+        }
+    }
+
     fn estimate_cost(&self, char_count: usize) -> f64 {
         // Gemini-TTS bills per audio token; use a coarse per-char proxy.
         // Traditional voices keep the exact $4/1M-char rate.
@@ -318,6 +493,44 @@ mod tests {
         assert!(!p.is_available());
     }
 
+    #[test]
+    fn test_api_key_provider_request() {
+        let p = CloudTtsProvider::with_api_key(
+            "AIza-test".into(),
+            "en-US-Neural2-C".into(),
+            Some("en-US".into()),
+            None,
+            None,
+            100,
+        );
+        assert!(p.is_available());
+        assert!(p.supports_ssml());
+
+        let request = p.build_request(TextInput {
+            text: "Build finished".into(),
+            ssml: None,
+            prompt: None,
+        });
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "input": {"text": "Build finished"},
+                "voice": {"languageCode": "en-US", "name": "en-US-Neural2-C"},
+                "audioConfig": {"audioEncoding": "LINEAR16"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_audio_content() {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(b"RIFF\x24\x00\x00\x00WAVE");
+        let decoded = CloudTtsProvider::decode_audio_content(&encoded).unwrap();
+        assert_eq!(decoded, b"RIFF\x24\x00\x00\x00WAVE");
+
+        assert!(CloudTtsProvider::decode_audio_content("not base64!").is_err());
+    }
+
     #[tokio::test]
     async fn test_speak_empty_text() {
         let p = create_test_provider();
@@ -377,6 +590,18 @@ mod tests {
         assert!(chunks.len() >= 2);
     }
 
+    #[test]
+    fn test_split_text_keeps_abbreviations_and_cjk_sentences_whole() {
+        let latin = format!("{} e.g. this one. {}", "A".repeat(20), "B".repeat(20));
+        let chunks = CloudTtsProvider::split_text(&latin, 40);
+        assert_eq!(chunks[0], format!("{} e.g. this one.", "A".repeat(20)));
+
+        let cjk = "測試通過。".repeat(4);
+        let chunks = CloudTtsProvider::split_text(&cjk, 30);
+        assert!(chunks.iter().all(|c| c.ends_with('。') && c.len() <= 30));
+        assert_eq!(chunks.concat(), cjk);
+    }
+
     #[test]
     fn test_default_request_serialization_omits_gemini_fields() {
         // With no model/style_prompt set, the wire format must be byte-identical
@@ -384,6 +609,7 @@ mod tests {
         let request = TtsRequest {
             input: TextInput {
                 text: "hello".to_string(),
+                ssml: None,
                 prompt: None,
             },
             voice: VoiceSelection {
@@ -393,6 +619,7 @@ mod tests {
             },
             audio_config: AudioConfig {
                 audio_encoding: "LINEAR16".to_string(),
+                speaking_rate: None,
             },
         };
         let json = serde_json::to_string(&request).unwrap();
@@ -409,6 +636,7 @@ mod tests {
         let request = TtsRequest {
             input: TextInput {
                 text: "hello".to_string(),
+                ssml: None,
                 prompt: Some("Say it curiously.".to_string()),
             },
             voice: VoiceSelection {
@@ -418,6 +646,7 @@ mod tests {
             },
             audio_config: AudioConfig {
                 audio_encoding: "LINEAR16".to_string(),
+                speaking_rate: None,
             },
         };
         let json = serde_json::to_string(&request).unwrap();
@@ -425,6 +654,62 @@ mod tests {
         assert!(json.contains(r#""prompt":"Say it curiously.""#));
     }
 
+    #[test]
+    fn test_ssml_request_passes_markup_unmodified() {
+        let p = create_test_provider();
+        let ssml = r#"<speak>Deploy <say-as interpret-as="characters">k8s</say-as> now.</speak>"#;
+        let request = p.build_request(TextInput {
+            text: String::new(),
+            ssml: Some(ssml.to_string()),
+            prompt: None,
+        });
+        let val = serde_json::to_value(&request).unwrap();
+        assert_eq!(val["input"]["ssml"], ssml);
+        assert!(val["input"].get("text").is_none());
+        assert!(!val["input"]["ssml"]
+            .as_str()
+            .unwrap()
+            .contains("Read this aloud"));
+    }
+
+    #[test]
+    fn test_wpm_sets_speaking_rate() {
+        let request = create_test_provider().build_request(TextInput {
+            text: "hello".to_string(),
+            ssml: None,
+            prompt: None,
+        });
+        let val = serde_json::to_value(&request).unwrap();
+        assert!(val["audioConfig"].get("speakingRate").is_none());
+
+        let p = create_test_provider().with_speaking_rate(Some(crate::tts::wpm_to_speed(140)));
+        let request = p.build_request(TextInput {
+            text: "hello".to_string(),
+            ssml: None,
+            prompt: None,
+        });
+        let val = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            val["audioConfig"]["speakingRate"].as_f64().unwrap() as f32,
+            0.8
+        );
+    }
+
+    #[test]
+    fn test_ssml_supported_only_for_traditional_voices() {
+        assert!(create_test_provider().supports_ssml());
+        assert!(!create_gemini_provider().supports_ssml());
+        assert!(create_test_provider().capabilities().ssml);
+        assert!(!create_gemini_provider().capabilities().ssml);
+    }
+
+    #[tokio::test]
+    async fn test_speak_ssml_rejects_oversized_input() {
+        let p = create_test_provider();
+        let ssml = format!("<speak>{}</speak>", "a".repeat(MAX_TEXT_BYTES));
+        assert!(p.speak_ssml(&ssml).await.is_err());
+    }
+
     #[test]
     fn test_gemini_chunk_cap_is_4000() {
         let p = create_gemini_provider();
@@ -451,4 +736,13 @@ mod tests {
         let cost = p.estimate_cost(1_000_000);
         assert!((cost - 4.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_transient_statuses_are_retried() {
+        use reqwest::StatusCode;
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_transient_status(StatusCode::BAD_REQUEST));
+        assert!(!is_transient_status(StatusCode::FORBIDDEN));
+    }
 }
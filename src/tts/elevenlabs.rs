@@ -8,7 +8,9 @@ use serde::Serialize;
 use std::io::Write;
 use std::time::Duration;
 
-use super::TtsProvider;
+use super::{TtsCapabilities, TtsProvider};
+use crate::audio::pcm_stream::PcmSegmenter;
+use crate::audio::wav_header::create_wav_file;
 use crate::error::{Result, VoiceError};
 
 const ELEVENLABS_API_BASE: &str = "https://api.elevenlabs.io/v1/text-to-speech";
@@ -16,6 +18,15 @@ const ELEVENLABS_API_BASE: &str = "https://api.elevenlabs.io/v1/text-to-speech";
 /// Default output format — MP3 44.1kHz 128kbps (free tier)
 const DEFAULT_OUTPUT_FORMAT: &str = "mp3_44100_128";
 
+/// Streaming output format — raw 16-bit mono PCM, playable without a decoder
+const STREAM_OUTPUT_FORMAT: &str = "pcm_24000";
+const STREAM_SAMPLE_RATE: u32 = 24000;
+
+/// Streaming segment lengths: a short first segment to start playback fast,
+/// then longer ones to keep gaps between segments rare
+const STREAM_FIRST_SEGMENT_MS: u32 = 500;
+const STREAM_SEGMENT_MS: u32 = 4000;
+
 /// Maximum characters per request (ElevenLabs limit)
 const MAX_TEXT_LENGTH: usize = 5_000;
 
@@ -33,6 +44,7 @@ pub struct ElevenLabsProvider {
     stability: Option<f32>,
     style: Option<f32>,
     volume: u32,
+    stream: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -75,9 +87,16 @@ impl ElevenLabsProvider {
             stability: stability.map(|s| s.clamp(0.0, 1.0)),
             style: style.map(|s| s.clamp(0.0, 1.0)),
             volume,
+            stream: false,
         }
     }
 
+    /// Use the streaming endpoint and play audio while it is still arriving
+    pub fn with_streaming(mut self, stream: bool) -> Self {
+        self.stream = stream;
+        self
+    }
+
     fn create_client() -> Result<Client> {
         Client::builder()
             .no_proxy()
@@ -118,8 +137,67 @@ impl ElevenLabsProvider {
         let _ = std::fs::remove_file(&tmp_path);
         result
     }
+
+    /// Play PCM segments as they arrive: a blocking player task drains a channel
+    /// of sample-aligned segments while the download keeps filling it.
+    async fn play_stream(&self, mut response: reqwest::Response) -> Result<()> {
+        let (tx, rx) = std::sync::mpsc::channel::<Vec<u8>>();
+        let volume = self.volume;
+        let player = tokio::task::spawn_blocking(move || -> Result<()> {
+            for pcm in rx {
+                let wav = create_wav_file(&pcm, STREAM_SAMPLE_RATE, 1, 16);
+                crate::audio::afplay::play_with_afplay(&wav, volume, "sumvox_elevenlabs_stream")?;
+            }
+            Ok(())
+        });
+
+        let mut segmenter = PcmSegmenter::for_duration(
+            STREAM_SAMPLE_RATE,
+            STREAM_FIRST_SEGMENT_MS,
+            STREAM_SEGMENT_MS,
+        );
+        let mut received = 0usize;
+        let download = async {
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| VoiceError::Voice(format!("Failed to read audio stream: {}", e)))?
+            {
+                received += chunk.len();
+                for segment in segmenter.push(&chunk) {
+                    // A closed channel means the player failed; its error is reported below
+                    if tx.send(segment).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+            if let Some(tail) = segmenter.finish() {
+                let _ = tx.send(tail);
+            }
+            Ok::<(), VoiceError>(())
+        };
+        let download_result = download.await;
+        drop(tx);
+
+        tracing::debug!("Received {} bytes of streamed PCM audio", received);
+        player
+            .await
+            .map_err(|e| VoiceError::Voice(format!("Stream player task failed: {}", e)))??;
+        download_result
+    }
 }
 
+/// What SumVox can do with this provider
+pub const CAPABILITIES: TtsCapabilities = TtsCapabilities {
+    ssml: false,
+    // voice_settings.speed
+    rate: true,
+    volume: true,
+    voices: true,
+    // `stream: true`
+    streaming: true,
+};
+
 #[async_trait]
 impl TtsProvider for ElevenLabsProvider {
     fn name(&self) -> &str {
@@ -157,10 +235,17 @@ impl TtsProvider for ElevenLabsProvider {
             text.len()
         );
 
-        let url = format!(
-            "{}/{}?output_format={}",
-            ELEVENLABS_API_BASE, self.voice_id, self.output_format
-        );
+        let url = if self.stream {
+            format!(
+                "{}/{}/stream?output_format={}",
+                ELEVENLABS_API_BASE, self.voice_id, STREAM_OUTPUT_FORMAT
+            )
+        } else {
+            format!(
+                "{}/{}?output_format={}",
+                ELEVENLABS_API_BASE, self.voice_id, self.output_format
+            )
+        };
 
         let voice_settings =
             if self.speed.is_some() || self.stability.is_some() || self.style.is_some() {
@@ -200,6 +285,12 @@ impl TtsProvider for ElevenLabsProvider {
             )));
         }
 
+        if self.stream {
+            self.play_stream(response).await?;
+            tracing::debug!("Streamed voice playback completed");
+            return Ok(true);
+        }
+
         let audio_data = response
             .bytes()
             .await
@@ -213,6 +304,10 @@ impl TtsProvider for ElevenLabsProvider {
         Ok(true)
     }
 
+    fn capabilities(&self) -> TtsCapabilities {
+        CAPABILITIES
+    }
+
     fn estimate_cost(&self, char_count: usize) -> f64 {
         let per_char = if self.model_id.contains("multilingual") {
             COST_PER_CHAR_MULTILINGUAL
@@ -242,7 +337,11 @@ mod tests {
         assert_eq!(provider.voice_id, "21m00Tcm4TlvDq8ikWAM");
         assert_eq!(provider.model_id, "eleven_flash_v2_5");
         assert_eq!(provider.volume, 100);
+        assert!(!provider.stream);
         assert!(provider.is_available());
+
+        let provider = provider.with_streaming(true);
+        assert!(provider.stream);
     }
 
     #[test]
@@ -286,6 +385,25 @@ mod tests {
         assert_eq!(too_fast.speed, Some(1.2));
     }
 
+    #[test]
+    fn test_wpm_sets_speed_within_range() {
+        let at_wpm = |wpm| {
+            ElevenLabsProvider::new(
+                "k".to_string(),
+                "21m00Tcm4TlvDq8ikWAM".to_string(),
+                "eleven_flash_v2_5".to_string(),
+                Some(crate::tts::wpm_to_speed(wpm)),
+                None,
+                None,
+                100,
+            )
+            .speed
+        };
+        assert_eq!(at_wpm(140), Some(0.8));
+        // Beyond what ElevenLabs accepts
+        assert_eq!(at_wpm(350), Some(1.2));
+    }
+
     #[test]
     fn test_unavailable_with_empty_or_placeholder_key() {
         let empty = ElevenLabsProvider::new(
@@ -6,7 +6,7 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use super::TtsProvider;
+use super::{MacOsTtsProvider, TtsCapabilities, TtsProvider};
 use crate::error::{Result, VoiceError};
 
 /// Gemini TTS API base URL
@@ -15,12 +15,17 @@ const GEMINI_TTS_API_BASE: &str = "https://generativelanguage.googleapis.com/v1b
 /// Cost per character for Gemini TTS (estimated)
 const COST_PER_CHAR: f64 = 0.000016;
 
+/// `say` rate for text the model sent back instead of audio
+const TEXT_REPLY_RATE: u32 = 200;
+
 /// Gemini TTS provider using Google AI Studio API
 pub struct GoogleTtsProvider {
     api_key: String,
     model: String,
     voice_name: String,
     volume: u32,
+    /// Speaks the model's text when it answers with text instead of audio
+    text_fallback: Box<dyn TtsProvider>,
 }
 
 #[derive(Debug, Serialize)]
@@ -85,6 +90,33 @@ struct ResponseContent {
 struct ResponsePart {
     #[serde(rename = "inlineData", default)]
     inline_data: Option<InlineData>,
+    /// Set when the model answered in words (a refusal or explanation)
+    #[serde(default)]
+    text: Option<String>,
+}
+
+impl GeminiTtsResponse {
+    fn parts(&self) -> impl Iterator<Item = &ResponsePart> {
+        self.candidates
+            .first()
+            .into_iter()
+            .flat_map(|c| c.content.parts.iter())
+    }
+
+    fn audio(&self) -> Option<&InlineData> {
+        self.parts().find_map(|p| p.inline_data.as_ref())
+    }
+
+    /// Text parts joined, or None when there are none with content
+    fn text(&self) -> Option<String> {
+        let text = self
+            .parts()
+            .filter_map(|p| p.text.as_deref())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -111,9 +143,30 @@ impl GoogleTtsProvider {
             model,
             voice_name,
             volume,
+            text_fallback: Box::new(MacOsTtsProvider::new(None, TEXT_REPLY_RATE, volume)),
         }
     }
 
+    /// Handle a response without audio: when the model sent text instead,
+    /// say it with the local voice so the report isn't silently dropped
+    async fn speak_text_reply(&self, response: &GeminiTtsResponse) -> Result<bool> {
+        let Some(reply) = response.text() else {
+            return Err(VoiceError::Voice("No audio data in response".into()));
+        };
+        if !self.text_fallback.is_available() {
+            return Err(VoiceError::Voice(format!(
+                "Gemini TTS returned text instead of audio: {}",
+                reply
+            )));
+        }
+        tracing::warn!(
+            "Gemini TTS returned text instead of audio, speaking it with {}: {}",
+            self.text_fallback.name(),
+            reply
+        );
+        self.text_fallback.speak(&reply).await
+    }
+
     /// Create HTTP client lazily (avoids issues in parallel tests)
     fn create_client() -> Result<Client> {
         Client::builder()
@@ -128,6 +181,7 @@ impl GoogleTtsProvider {
     /// Play audio data using afplay
     fn play_audio(&self, audio_data: &[u8], mime_type: &str) -> Result<()> {
         use crate::audio::afplay::play_with_afplay;
+        use crate::audio::format::AudioFormat;
         use crate::audio::wav_header::create_wav_file;
 
         tracing::debug!(
@@ -138,7 +192,10 @@ impl GoogleTtsProvider {
         );
 
         // Gemini TTS returns LINEAR16 PCM format (16-bit signed little-endian at 24kHz mono)
-        // Convert raw PCM to WAV format
+        // Convert raw PCM to WAV format, unless it already came with a header
+        if AudioFormat::detect(audio_data) != AudioFormat::Pcm {
+            return play_with_afplay(audio_data, self.volume, "sumvox_google");
+        }
         let wav_data = create_wav_file(audio_data, 24000, 1, 16);
 
         // Play using afplay
@@ -146,6 +203,15 @@ impl GoogleTtsProvider {
     }
 }
 
+/// What SumVox can do with this provider
+pub const CAPABILITIES: TtsCapabilities = TtsCapabilities {
+    ssml: false,
+    rate: false,
+    volume: true,
+    voices: true,
+    streaming: false,
+};
+
 #[async_trait]
 impl TtsProvider for GoogleTtsProvider {
     fn name(&self) -> &str {
@@ -231,12 +297,9 @@ impl TtsProvider for GoogleTtsProvider {
         })?;
 
         // Extract audio data from response
-        let inline_data = tts_response
-            .candidates
-            .first()
-            .and_then(|c| c.content.parts.first())
-            .and_then(|p| p.inline_data.as_ref())
-            .ok_or_else(|| VoiceError::Voice("No audio data in response".into()))?;
+        let Some(inline_data) = tts_response.audio() else {
+            return self.speak_text_reply(&tts_response).await;
+        };
 
         // Decode base64 audio
         let audio_data = base64::engine::general_purpose::STANDARD
@@ -256,6 +319,10 @@ impl TtsProvider for GoogleTtsProvider {
         Ok(true)
     }
 
+    fn capabilities(&self) -> TtsCapabilities {
+        CAPABILITIES
+    }
+
     fn estimate_cost(&self, char_count: usize) -> f64 {
         char_count as f64 * COST_PER_CHAR
     }
@@ -264,6 +331,7 @@ impl TtsProvider for GoogleTtsProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tts::mock::{spoken_log, texts, RecordingProvider, SpokenLog};
 
     #[test]
     fn test_google_provider_creation() {
@@ -320,6 +388,69 @@ mod tests {
         assert!((cost_100 - 0.0016).abs() < 0.0001);
     }
 
+    fn provider_with_fallback(available: bool) -> (GoogleTtsProvider, SpokenLog) {
+        let spoken = spoken_log();
+        let local = RecordingProvider::new("macos", &spoken);
+        let provider = GoogleTtsProvider {
+            text_fallback: Box::new(if available {
+                local
+            } else {
+                local.unavailable()
+            }),
+            ..GoogleTtsProvider::new(
+                "test-api-key".to_string(),
+                "gemini-2.5-flash-preview-tts".to_string(),
+                "Aoede".to_string(),
+                100,
+            )
+        };
+        (provider, spoken)
+    }
+
+    #[tokio::test]
+    async fn test_text_only_response_falls_back_to_local_say() {
+        let response: GeminiTtsResponse = serde_json::from_str(
+            r#"{"candidates":[{"content":{"role":"model","parts":[
+                {"text":"I can't read that aloud."},
+                {"text":"Please rephrase it."}
+            ]}}]}"#,
+        )
+        .unwrap();
+        assert!(response.audio().is_none());
+
+        let (provider, spoken) = provider_with_fallback(true);
+        assert!(provider.speak_text_reply(&response).await.unwrap());
+        assert_eq!(
+            texts(&spoken),
+            ["I can't read that aloud. Please rephrase it."]
+        );
+
+        // Without a local voice the text still shows up in the error
+        let (provider, spoken) = provider_with_fallback(false);
+        let err = provider.speak_text_reply(&response).await.unwrap_err();
+        assert!(err.to_string().contains("I can't read that aloud."));
+        assert!(texts(&spoken).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_audio_response_and_empty_response() {
+        let response: GeminiTtsResponse = serde_json::from_str(
+            r#"{"candidates":[{"content":{"parts":[
+                {"text":"Here you go"},
+                {"inlineData":{"mimeType":"audio/L16;rate=24000","data":"AAAA"}}
+            ]}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(response.audio().unwrap().data, "AAAA");
+
+        let empty: GeminiTtsResponse =
+            serde_json::from_str(r#"{"candidates":[{"content":{"parts":[]}}]}"#).unwrap();
+        let (provider, spoken) = provider_with_fallback(true);
+        let err = provider.speak_text_reply(&empty).await.unwrap_err();
+        assert!(err.to_string().contains("No audio data in response"));
+        assert!(texts(&spoken).is_empty());
+    }
+
     #[tokio::test]
     async fn test_speak_empty_message() {
         let provider = GoogleTtsProvider::new(
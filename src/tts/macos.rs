@@ -5,7 +5,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use async_trait::async_trait;
 use tokio::process::Command;
 
-use super::TtsProvider;
+use super::{TtsCapabilities, TtsProvider};
 use crate::error::{Result, VoiceError};
 
 // Per-call counter so the temp path is unique even for concurrent calls that
@@ -33,6 +33,16 @@ impl MacOsTtsProvider {
     }
 }
 
+/// What SumVox can do with this provider
+pub const CAPABILITIES: TtsCapabilities = TtsCapabilities {
+    ssml: false,
+    // `say -r` words per minute
+    rate: true,
+    volume: true,
+    voices: true,
+    streaming: false,
+};
+
 #[async_trait]
 impl TtsProvider for MacOsTtsProvider {
     fn name(&self) -> &str {
@@ -92,7 +102,8 @@ impl TtsProvider for MacOsTtsProvider {
             return Err(VoiceError::Voice(format!("Say command failed: {}", stderr)));
         }
 
-        // Play with afplay -v; clean up on every path (including playback error).
+        // Play with afplay -v, which also takes the playback permit
+        // (tts.max_concurrent_playbacks); clean up on every path.
         let result = crate::audio::afplay::run_afplay(&aiff_path, self.volume);
         let _ = std::fs::remove_file(&aiff_path);
         result?;
@@ -102,6 +113,10 @@ impl TtsProvider for MacOsTtsProvider {
         Ok(true)
     }
 
+    fn capabilities(&self) -> TtsCapabilities {
+        CAPABILITIES
+    }
+
     fn estimate_cost(&self, _char_count: usize) -> f64 {
         // macOS say is free
         0.0
@@ -0,0 +1,97 @@
+// Recording TTS provider shared by the tts tests
+// Stands in for a real voice: it notes which provider was asked to speak
+// what, and can be made unavailable, failing or slow.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::TtsProvider;
+use crate::error::{Result, VoiceError};
+
+/// (provider name, text) for every successful `speak`, in order
+pub type SpokenLog = Arc<Mutex<Vec<(&'static str, String)>>>;
+
+pub fn spoken_log() -> SpokenLog {
+    Arc::new(Mutex::new(Vec::new()))
+}
+
+/// Names of the providers that spoke, in order
+pub fn speakers(log: &SpokenLog) -> Vec<&'static str> {
+    log.lock().unwrap().iter().map(|(name, _)| *name).collect()
+}
+
+/// Texts that were spoken, in order
+pub fn texts(log: &SpokenLog) -> Vec<String> {
+    log.lock()
+        .unwrap()
+        .iter()
+        .map(|(_, text)| text.clone())
+        .collect()
+}
+
+pub struct RecordingProvider {
+    name: &'static str,
+    available: bool,
+    fails: bool,
+    delay: Duration,
+    spoken: SpokenLog,
+}
+
+impl RecordingProvider {
+    /// An available provider that speaks instantly into `spoken`
+    pub fn new(name: &'static str, spoken: &SpokenLog) -> Self {
+        Self {
+            name,
+            available: true,
+            fails: false,
+            delay: Duration::ZERO,
+            spoken: spoken.clone(),
+        }
+    }
+
+    pub fn unavailable(mut self) -> Self {
+        self.available = false;
+        self
+    }
+
+    /// Every `speak` fails with a quota error
+    pub fn failing(mut self) -> Self {
+        self.fails = true;
+        self
+    }
+
+    /// Every `speak` takes `delay` first
+    pub fn slow(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+#[async_trait]
+impl TtsProvider for RecordingProvider {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn is_available(&self) -> bool {
+        self.available
+    }
+
+    async fn speak(&self, text: &str) -> Result<bool> {
+        tokio::time::sleep(self.delay).await;
+        if self.fails {
+            return Err(VoiceError::Voice("quota exceeded".into()));
+        }
+        self.spoken
+            .lock()
+            .unwrap()
+            .push((self.name, text.to_string()));
+        Ok(true)
+    }
+
+    fn estimate_cost(&self, _char_count: usize) -> f64 {
+        0.0
+    }
+}
@@ -6,15 +6,50 @@ pub mod cloud_tts_auth;
 pub mod elevenlabs;
 pub mod google;
 pub mod macos;
+#[cfg(test)]
+mod mock;
 pub mod openai;
+pub mod paths;
+pub mod pronunciation;
+pub mod sentences;
+pub mod spoken_dedup;
+pub mod voice_rotation;
 pub mod xai;
 
 use async_trait::async_trait;
+use std::future::Future;
 use std::str::FromStr;
+use std::time::Duration;
 
-use crate::config::TtsProviderConfig;
+use crate::config::{TtsFallbackPolicy, TtsProviderConfig};
 use crate::error::{Result, VoiceError};
 
+/// What SumVox can do with a TTS provider (`sumvox providers info`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TtsCapabilities {
+    /// Authored SSML through `say --ssml-file`
+    pub ssml: bool,
+    /// Speaking rate control (`rate`, `speed` or `tts.wpm`)
+    pub rate: bool,
+    /// Playback volume
+    pub volume: bool,
+    /// Selectable voices
+    pub voices: bool,
+    /// Playback starts before synthesis has finished
+    pub streaming: bool,
+}
+
+/// Capabilities of each TTS provider, by config name
+pub const PROVIDER_CAPABILITIES: &[(&str, TtsCapabilities)] = &[
+    ("macos", macos::CAPABILITIES),
+    ("google", google::CAPABILITIES),
+    ("cloud_tts", cloud_tts::CAPABILITIES),
+    ("xai", xai::CAPABILITIES),
+    ("elevenlabs", elevenlabs::CAPABILITIES),
+    ("openai", openai::CAPABILITIES),
+    ("audio_file", crate::audio::file::CAPABILITIES),
+];
+
 /// TTS Provider trait - defines interface for text-to-speech engines
 #[async_trait]
 pub trait TtsProvider: Send + Sync {
@@ -32,12 +67,48 @@ pub trait TtsProvider: Send + Sync {
     /// Returns 0.0 for local engines
     fn estimate_cost(&self, char_count: usize) -> f64;
 
+    /// What SumVox can do with this provider
+    fn capabilities(&self) -> TtsCapabilities {
+        TtsCapabilities::default()
+    }
+
     /// Whether this provider interprets `[tag]`-style audio/emotion tags
     /// (e.g. ElevenLabs eleven_v3). Providers that don't must have such
     /// tags stripped before speaking, or they get read aloud literally.
     fn supports_audio_tags(&self) -> bool {
         false
     }
+
+    /// Whether this provider accepts authored SSML through `speak_ssml`
+    fn supports_ssml(&self) -> bool {
+        false
+    }
+
+    /// Speak an SSML document verbatim (no prompt wrapping or chunking)
+    async fn speak_ssml(&self, _ssml: &str) -> Result<bool> {
+        Err(VoiceError::Voice(format!(
+            "TTS provider {} does not support SSML",
+            self.name()
+        )))
+    }
+}
+
+/// Speak with an upper bound on total synthesis + playback time.
+/// A provider that doesn't finish within `timeout` is abandoned with a `Voice` error,
+/// so callers can fall through to the next provider.
+pub async fn speak_with_timeout(
+    provider: &dyn TtsProvider,
+    text: &str,
+    timeout: Duration,
+) -> Result<bool> {
+    match tokio::time::timeout(timeout, provider.speak(text)).await {
+        Ok(result) => result,
+        Err(_) => Err(VoiceError::Voice(format!(
+            "TTS provider {} timed out after {}s",
+            provider.name(),
+            timeout.as_secs_f32()
+        ))),
+    }
 }
 
 /// Strip a single leading `[tag]` (e.g. "[satisfied] ") from text meant for
@@ -55,6 +126,73 @@ pub fn strip_leading_audio_tag(text: &str) -> &str {
     text
 }
 
+/// Said ahead of the text when a backup provider speaks (`tts.announce_fallback`)
+pub const FALLBACK_MARKER: &str = "(using backup voice)";
+
+/// `text` with `FALLBACK_MARKER` in front, after any leading audio tag so
+/// providers that strip the tag still find it
+pub fn with_fallback_marker(text: &str) -> String {
+    let rest = strip_leading_audio_tag(text);
+    let tag = text[..text.len() - rest.len()].trim();
+    if tag.is_empty() {
+        format!("{} {}", FALLBACK_MARKER, text)
+    } else {
+        format!("{} {} {}", tag, FALLBACK_MARKER, rest)
+    }
+}
+
+/// Speak `text` with each provider in order using `speak`. `First` stops at
+/// the first provider that succeeds; `All` plays every one of them.
+/// With `First`, providers after the first speak `fallback_text` instead
+/// when it is set. Returns whether any provider spoke; failures are added
+/// as "name: error".
+pub async fn speak_in_order<'a, F, Fut>(
+    providers: &'a [Box<dyn TtsProvider>],
+    text: &'a str,
+    fallback_text: Option<&'a str>,
+    policy: TtsFallbackPolicy,
+    failures: &mut Vec<String>,
+    mut speak: F,
+) -> bool
+where
+    F: FnMut(&'a dyn TtsProvider, &'a str) -> Fut,
+    Fut: Future<Output = Result<bool>>,
+{
+    let mut spoke = false;
+    for (index, provider) in providers.iter().enumerate() {
+        tracing::info!("Using TTS provider: {}", provider.name());
+        let text = match fallback_text {
+            Some(fallback_text) if index > 0 && policy == TtsFallbackPolicy::First => fallback_text,
+            _ => text,
+        };
+
+        // Estimate and log cost for cloud providers
+        let cost = provider.estimate_cost(text.len());
+        if cost > 0.0 {
+            tracing::info!("TTS cost estimate: ${:.6} for {} chars", cost, text.len());
+        }
+
+        match speak(provider.as_ref(), text).await {
+            Ok(_) => {
+                tracing::debug!("TTS playback completed with {}", provider.name());
+                spoke = true;
+                if policy == TtsFallbackPolicy::First {
+                    break;
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "TTS provider {} failed: {}, trying next provider",
+                    provider.name(),
+                    e
+                );
+                failures.push(format!("{}: {}", provider.name(), e));
+            }
+        }
+    }
+    spoke
+}
+
 /// TTS Engine type for CLI selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TtsEngine {
@@ -107,17 +245,23 @@ pub use elevenlabs::ElevenLabsProvider;
 pub use google::GoogleTtsProvider;
 pub use macos::MacOsTtsProvider;
 pub use openai::OpenAiTtsProvider;
+pub use pronunciation::apply_pronunciations;
+pub use sentences::{speak_with_pauses, split_sentences};
 pub use xai::XaiTtsProvider;
 
 /// Create TTS provider from config array with automatic fallback
 ///
 /// Tries each provider in order until one is available.
 /// Returns an error if no provider can be created.
-pub fn create_tts_from_config(providers: &[TtsProviderConfig]) -> Result<Box<dyn TtsProvider>> {
+pub fn create_tts_from_config(
+    providers: &[TtsProviderConfig],
+    default_voice: Option<&str>,
+    wpm: Option<u32>,
+) -> Result<Box<dyn TtsProvider>> {
     let mut errors = Vec::new();
 
     for config in providers {
-        match create_single_tts(config) {
+        match create_single_tts(config, default_voice, wpm) {
             Ok(provider) => {
                 if provider.is_available() {
                     tracing::info!(
@@ -144,14 +288,43 @@ pub fn create_tts_from_config(providers: &[TtsProviderConfig]) -> Result<Box<dyn
     )))
 }
 
+/// Speaking rate, in words per minute, that speed multipliers are relative to
+pub const BASELINE_WPM: u32 = 175;
+
+/// Speed multiplier (1.0 = normal) matching `wpm` words per minute
+pub fn wpm_to_speed(wpm: u32) -> f32 {
+    wpm as f32 / BASELINE_WPM as f32
+}
+
+/// `say -r` rate: the provider's `rate`, else `wpm` (already words per minute)
+fn say_rate(config: &TtsProviderConfig, wpm: Option<u32>) -> u32 {
+    config.rate.or(wpm).unwrap_or(200)
+}
+
+/// Speed multiplier for OpenAI, ElevenLabs and Cloud TTS: the provider's
+/// `speed`, else `wpm` relative to `BASELINE_WPM`
+fn speed_multiplier(config: &TtsProviderConfig, wpm: Option<u32>) -> Option<f32> {
+    config.speed.or(wpm.map(wpm_to_speed))
+}
+
 /// Create a single TTS provider from config
-pub fn create_single_tts(config: &TtsProviderConfig) -> Result<Box<dyn TtsProvider>> {
+///
+/// `wpm` (`tts.wpm`) sets the speaking rate in each engine's own unit; a
+/// provider's `rate` or `speed` overrides it.
+pub fn create_single_tts(
+    config: &TtsProviderConfig,
+    default_voice: Option<&str>,
+    wpm: Option<u32>,
+) -> Result<Box<dyn TtsProvider>> {
+    // A provider's own voice wins; `tts.default_voice` fills the gap
+    let config = &config.with_default_voice(default_voice);
     let volume = config.volume.unwrap_or(100);
+    let speed = speed_multiplier(config, wpm);
 
-    match config.name.to_lowercase().as_str() {
+    let provider: Result<Box<dyn TtsProvider>> = match config.name.to_lowercase().as_str() {
         "macos" | "say" => {
             let voice = config.voice.clone();
-            let rate = config.rate.unwrap_or(200);
+            let rate = say_rate(config, wpm);
             Ok(Box::new(MacOsTtsProvider::new(voice, rate, volume)))
         }
         "google" | "google_tts" | "gcloud" | "gemini" => {
@@ -179,9 +352,17 @@ pub fn create_single_tts(config: &TtsProviderConfig) -> Result<Box<dyn TtsProvid
             )))
         }
         "cloud_tts" | "gcp_tts" | "google_cloud" | "gemini_tts" => {
-            let sa_json = config.get_service_account_key().ok_or_else(|| {
-                VoiceError::Config("Cloud TTS requires service_account_key".into())
-            })?;
+            // Service account preferred; a Cloud API key is the lighter alternative
+            let sa_json = config.get_service_account_key();
+            let api_key = config
+                .api_key
+                .clone()
+                .filter(|k| !k.is_empty() && !k.starts_with("${"));
+            if sa_json.is_none() && api_key.is_none() {
+                return Err(VoiceError::Config(
+                    "Cloud TTS requires service_account_key or api_key".into(),
+                ));
+            }
 
             // Voice is required — no hardcoded default.
             let voice = config.voice.clone().ok_or_else(|| {
@@ -194,14 +375,25 @@ pub fn create_single_tts(config: &TtsProviderConfig) -> Result<Box<dyn TtsProvid
             // model set => Gemini-TTS (bare voice name + model_name); style_prompt optional.
             let model = config.model.clone();
             let style_prompt = config.style_prompt.clone();
-            Ok(Box::new(CloudTtsProvider::new(
-                sa_json,
-                voice,
-                language_code,
-                model,
-                style_prompt,
-                volume,
-            )))
+            let provider = match sa_json {
+                Some(sa_json) => CloudTtsProvider::new(
+                    sa_json,
+                    voice,
+                    language_code,
+                    model,
+                    style_prompt,
+                    volume,
+                ),
+                None => CloudTtsProvider::with_api_key(
+                    api_key.unwrap_or_default(),
+                    voice,
+                    language_code,
+                    model,
+                    style_prompt,
+                    volume,
+                ),
+            };
+            Ok(Box::new(provider.with_speaking_rate(speed)))
         }
         "xai" | "xai_tts" | "grok" => {
             let api_key = config.get_xai_api_key().ok_or_else(|| {
@@ -241,12 +433,12 @@ pub fn create_single_tts(config: &TtsProviderConfig) -> Result<Box<dyn TtsProvid
                         .into(),
                 )
             })?;
-            let speed = config.speed;
             let stability = config.stability;
             let style = config.style;
-            Ok(Box::new(ElevenLabsProvider::new(
-                api_key, voice, model, speed, stability, style, volume,
-            )))
+            Ok(Box::new(
+                ElevenLabsProvider::new(api_key, voice, model, speed, stability, style, volume)
+                    .with_streaming(config.stream.unwrap_or(false)),
+            ))
         }
         "openai" | "openai_tts" => {
             let api_key = config.get_openai_api_key().ok_or_else(|| {
@@ -268,7 +460,6 @@ pub fn create_single_tts(config: &TtsProviderConfig) -> Result<Box<dyn TtsProvid
                 )
             })?;
             let instructions = config.style_prompt.clone();
-            let speed = config.speed;
             Ok(Box::new(OpenAiTtsProvider::new(
                 api_key,
                 model,
@@ -295,21 +486,42 @@ pub fn create_single_tts(config: &TtsProviderConfig) -> Result<Box<dyn TtsProvid
             "Unknown TTS provider: {}",
             config.name
         ))),
+    };
+
+    let provider = provider?;
+    if wpm.is_some() && !provider.capabilities().rate {
+        tracing::debug!("TTS {} has no rate setting, ignoring tts.wpm", config.name);
     }
+    Ok(provider)
 }
 
 /// Resolve a CLI/hook-selected TTS engine to a provider, sourcing all attributes
 /// from the matching config entry. Only the voice/volume the caller explicitly set
-/// override config; `rate` is taken from the caller (macOS-only). The engine must
+/// override config, and so does `rate` (macOS-only) when given. The engine must
 /// exist in config — config is the single source of truth, so an absent engine is
 /// an error and no provider/model/voice value is ever hardcoded here.
 pub fn resolve_tts_provider(
     providers: &[TtsProviderConfig],
     aliases: &[&str],
     voice: Option<&str>,
-    rate: u32,
+    rate: Option<u32>,
     volume: Option<u32>,
+    default_voice: Option<&str>,
+    wpm: Option<u32>,
 ) -> Result<Box<dyn TtsProvider>> {
+    let resolved = resolve_tts_config(providers, aliases, voice, rate, volume)?;
+    create_single_tts(&resolved, default_voice, wpm)
+}
+
+/// The config entry `resolve_tts_provider` builds its provider from, with
+/// the caller's overrides applied
+fn resolve_tts_config(
+    providers: &[TtsProviderConfig],
+    aliases: &[&str],
+    voice: Option<&str>,
+    rate: Option<u32>,
+    volume: Option<u32>,
+) -> Result<TtsProviderConfig> {
     // Prefer the entry whose name exactly matches what the user asked for
     // (aliases[0]); aliases can map several names to one engine (e.g. cloud_tts
     // and gemini_tts), and config order must not override an explicit choice.
@@ -332,14 +544,152 @@ pub fn resolve_tts_provider(
     if let Some(vol) = volume {
         resolved.volume = Some(vol);
     }
-    resolved.rate = Some(rate);
-    create_single_tts(&resolved)
+    if let Some(rate) = rate {
+        resolved.rate = Some(rate);
+    }
+    Ok(resolved)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::mock::{speakers, spoken_log, RecordingProvider};
     use super::*;
 
+    #[tokio::test]
+    async fn test_speak_with_timeout_fires_for_slow_provider() {
+        let provider = RecordingProvider::new("slow", &spoken_log()).slow(Duration::from_secs(5));
+        let result = speak_with_timeout(&provider, "hello", Duration::from_millis(20)).await;
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("timed out"), "unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    async fn test_speak_with_timeout_passes_through_fast_provider() {
+        let provider = RecordingProvider::new("slow", &spoken_log()).slow(Duration::from_millis(1));
+        let result = speak_with_timeout(&provider, "hello", Duration::from_secs(5)).await;
+        assert!(result.unwrap());
+    }
+
+    /// Providers that each fail or speak into `spoken`
+    fn chain(
+        providers: [(&'static str, bool); 3],
+        spoken: &mock::SpokenLog,
+    ) -> Vec<Box<dyn TtsProvider>> {
+        providers
+            .into_iter()
+            .map(|(name, fails)| {
+                let provider = RecordingProvider::new(name, spoken);
+                Box::new(if fails { provider.failing() } else { provider }) as Box<dyn TtsProvider>
+            })
+            .collect()
+    }
+
+    async fn run_chain(policy: TtsFallbackPolicy) -> (bool, Vec<&'static str>, Vec<String>) {
+        let spoken = spoken_log();
+        let providers = chain(
+            [("google", true), ("xai", false), ("macos", false)],
+            &spoken,
+        );
+        let mut failures = Vec::new();
+        let spoke = speak_in_order(
+            &providers,
+            "Done",
+            None,
+            policy,
+            &mut failures,
+            |p, text| p.speak(text),
+        )
+        .await;
+        (spoke, speakers(&spoken), failures)
+    }
+
+    #[tokio::test]
+    async fn test_first_policy_stops_at_first_success() {
+        let (spoke, spoken, failures) = run_chain(TtsFallbackPolicy::First).await;
+        assert!(spoke);
+        assert_eq!(spoken, ["xai"]);
+        assert_eq!(failures, ["google: Voice engine error: quota exceeded"]);
+    }
+
+    #[tokio::test]
+    async fn test_all_policy_plays_every_provider() {
+        let (spoke, spoken, failures) = run_chain(TtsFallbackPolicy::All).await;
+        assert!(spoke);
+        assert_eq!(spoken, ["xai", "macos"]);
+        assert_eq!(failures.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chain_reports_when_nothing_spoke() {
+        let providers: Vec<Box<dyn TtsProvider>> = Vec::new();
+        let mut failures = Vec::new();
+        let spoke = speak_in_order(
+            &providers,
+            "Done",
+            None,
+            TtsFallbackPolicy::All,
+            &mut failures,
+            |p, text| p.speak(text),
+        )
+        .await;
+        assert!(!spoke);
+        assert!(failures.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fallback_marker_only_for_non_first_providers() {
+        let providers = chain(
+            [("google", true), ("xai", true), ("macos", false)],
+            &spoken_log(),
+        );
+        let announced = with_fallback_marker("Done");
+        assert_eq!(announced, "(using backup voice) Done");
+
+        let mut texts = Vec::new();
+        let mut failures = Vec::new();
+        speak_in_order(
+            &providers,
+            "Done",
+            Some(&announced),
+            TtsFallbackPolicy::First,
+            &mut failures,
+            |p, text| {
+                texts.push((p.name().to_string(), text.to_string()));
+                p.speak(text)
+            },
+        )
+        .await;
+        assert_eq!(
+            texts,
+            [
+                ("google".to_string(), "Done".to_string()),
+                ("xai".to_string(), "(using backup voice) Done".to_string()),
+                ("macos".to_string(), "(using backup voice) Done".to_string()),
+            ]
+        );
+
+        // Whichever provider comes first speaks the plain text
+        texts.clear();
+        speak_in_order(
+            &providers[2..],
+            "Done",
+            Some(&announced),
+            TtsFallbackPolicy::First,
+            &mut failures,
+            |p, text| {
+                texts.push((p.name().to_string(), text.to_string()));
+                p.speak(text)
+            },
+        )
+        .await;
+        assert_eq!(texts, [("macos".to_string(), "Done".to_string())]);
+
+        assert_eq!(
+            with_fallback_marker("[satisfied] Done"),
+            "[satisfied] (using backup voice) Done"
+        );
+    }
+
     #[test]
     fn test_strip_leading_audio_tag() {
         assert_eq!(
@@ -408,8 +758,9 @@ mod tests {
             stability: None,
             style: None,
             style_prompt: Some("Say it warmly.".to_string()),
+            stream: None,
         };
-        let err = match create_single_tts(&config) {
+        let err = match create_single_tts(&config, None, None) {
             Ok(_) => panic!("expected error without service account key"),
             Err(e) => e.to_string(),
         };
@@ -442,6 +793,7 @@ mod tests {
             stability: None,
             style: None,
             style_prompt: None,
+            stream: None,
         };
         let gemini = TtsProviderConfig {
             name: "gemini_tts".to_string(),
@@ -455,7 +807,9 @@ mod tests {
             &providers,
             &["gemini_tts", "cloud_tts", "gcp_tts", "google_cloud"],
             None,
-            200,
+            Some(200),
+            None,
+            None,
             None,
         );
         assert!(
@@ -490,21 +844,91 @@ mod tests {
             stability: None,
             style: None,
             style_prompt: None,
+            stream: None,
         }
     }
 
     #[test]
     fn test_openai_requires_voice() {
-        let err = create_single_tts(&openai_config(Some("gpt-4o-mini-tts"), None))
+        let err = create_single_tts(&openai_config(Some("gpt-4o-mini-tts"), None), None, None)
             .err()
             .expect("expected error without voice")
             .to_string();
         assert!(err.contains("voice is required"), "unexpected error: {err}");
     }
 
+    #[test]
+    fn test_default_voice_fills_missing_voice() {
+        let config = openai_config(Some("gpt-4o-mini-tts"), None);
+        assert!(create_single_tts(&config, Some("nova"), None).is_ok());
+        assert_eq!(
+            config.with_default_voice(Some("nova")).voice.as_deref(),
+            Some("nova")
+        );
+    }
+
+    #[test]
+    fn test_default_voice_does_not_override_provider_voice() {
+        let config = openai_config(Some("gpt-4o-mini-tts"), Some("alloy"));
+        assert_eq!(
+            config.with_default_voice(Some("nova")).voice.as_deref(),
+            Some("alloy")
+        );
+    }
+
+    #[test]
+    fn test_wpm_maps_to_say_rate() {
+        let mut config = openai_config(None, None);
+        config.name = "macos".to_string();
+        assert_eq!(say_rate(&config, Some(240)), 240);
+        assert_eq!(say_rate(&config, None), 200);
+
+        // A provider's own rate overrides tts.wpm
+        config.rate = Some(150);
+        assert_eq!(say_rate(&config, Some(240)), 150);
+    }
+
+    #[test]
+    fn test_explicit_engine_without_cli_rate_uses_wpm() {
+        let mut macos = openai_config(None, None);
+        macos.name = "macos".to_string();
+        let providers = [macos];
+
+        // `--tts macos` or a hook's stop_tts_provider, no --rate given
+        let resolved = resolve_tts_config(&providers, &["macos", "say"], None, None, None).unwrap();
+        assert_eq!(say_rate(&resolved, Some(240)), 240);
+        assert!(resolve_tts_provider(
+            &providers,
+            &["macos", "say"],
+            None,
+            None,
+            None,
+            None,
+            Some(240)
+        )
+        .is_ok());
+
+        // An explicit --rate still wins
+        let resolved =
+            resolve_tts_config(&providers, &["macos", "say"], None, Some(180), None).unwrap();
+        assert_eq!(say_rate(&resolved, Some(240)), 180);
+    }
+
+    #[test]
+    fn test_wpm_maps_to_speed_multiplier() {
+        let mut config = openai_config(Some("gpt-4o-mini-tts"), Some("nova"));
+        assert_eq!(speed_multiplier(&config, Some(BASELINE_WPM)), Some(1.0));
+        assert_eq!(speed_multiplier(&config, Some(210)), Some(1.2));
+        assert_eq!(speed_multiplier(&config, Some(140)), Some(0.8));
+        assert_eq!(speed_multiplier(&config, None), None);
+
+        config.speed = Some(0.9);
+        assert_eq!(speed_multiplier(&config, Some(210)), Some(0.9));
+    }
+
     #[test]
     fn test_openai_requires_model() {
-        let err = create_single_tts(&openai_config(None, Some("nova")))
+        let err = create_single_tts(&openai_config(None, Some("nova")), None, None)
             .err()
             .expect("expected error without model")
             .to_string();
@@ -513,8 +937,12 @@ mod tests {
 
     #[test]
     fn test_openai_fully_specified_config() {
-        let provider = create_single_tts(&openai_config(Some("gpt-4o-mini-tts"), Some("nova")))
-            .expect("fully specified openai entry should build");
+        let provider = create_single_tts(
+            &openai_config(Some("gpt-4o-mini-tts"), Some("nova")),
+            None,
+            None,
+        )
+        .expect("fully specified openai entry should build");
         assert_eq!(provider.name(), "openai");
         assert!(provider.is_available());
     }
@@ -522,10 +950,18 @@ mod tests {
     #[test]
     fn test_resolve_openai_errors_when_absent() {
         let providers: Vec<TtsProviderConfig> = vec![];
-        let err = resolve_tts_provider(&providers, &["openai", "openai_tts"], None, 200, None)
-            .err()
-            .expect("expected error with empty config")
-            .to_string();
+        let err = resolve_tts_provider(
+            &providers,
+            &["openai", "openai_tts"],
+            None,
+            Some(200),
+            None,
+            None,
+            None,
+        )
+        .err()
+        .expect("expected error with empty config")
+        .to_string();
         assert!(
             err.contains("openai provider not found in config"),
             "unexpected error: {err}"
@@ -570,9 +1006,10 @@ mod tests {
             stability: None,
             style: None,
             style_prompt: None,
+            stream: None,
         }];
 
-        let result = create_tts_from_config(&providers);
+        let result = create_tts_from_config(&providers, None, None);
         assert!(result.is_ok());
         assert_eq!(result.unwrap().name(), "macos");
     }
@@ -596,6 +1033,7 @@ mod tests {
                 stability: None,
                 style: None,
                 style_prompt: None,
+                stream: None,
             },
             TtsProviderConfig {
                 name: "macos".to_string(),
@@ -611,6 +1049,7 @@ mod tests {
                 stability: None,
                 style: None,
                 style_prompt: None,
+                stream: None,
             },
         ];
 
@@ -618,7 +1057,7 @@ mod tests {
         std::env::remove_var("GOOGLE_CLOUD_PROJECT");
         std::env::remove_var("GCP_PROJECT");
 
-        let result = create_tts_from_config(&providers);
+        let result = create_tts_from_config(&providers, None, None);
         assert!(result.is_ok());
         assert_eq!(result.unwrap().name(), "macos");
     }
@@ -639,6 +1078,7 @@ mod tests {
             stability: None,
             style: None,
             style_prompt: None,
+            stream: None,
         }];
 
         // CLI voice override wins over config voice; engine sourced from config.
@@ -646,17 +1086,58 @@ mod tests {
             &providers,
             &["macos", "say"],
             Some("Tingting"),
-            250,
+            Some(250),
             Some(80),
+            None,
+            None,
         );
         assert!(result.is_ok());
         assert_eq!(result.unwrap().name(), "macos");
     }
 
+    #[test]
+    fn test_capability_matrix_reflects_providers() {
+        let caps = |name: &str| {
+            PROVIDER_CAPABILITIES
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, c)| *c)
+                .unwrap()
+        };
+        // Only Cloud TTS takes SSML, only ElevenLabs streams
+        let ssml: Vec<_> = PROVIDER_CAPABILITIES
+            .iter()
+            .filter(|(_, c)| c.ssml)
+            .map(|(n, _)| *n)
+            .collect();
+        assert_eq!(ssml, ["cloud_tts"]);
+        assert!(caps("elevenlabs").streaming);
+        assert!(!caps("openai").streaming);
+
+        for name in ["macos", "cloud_tts", "elevenlabs", "openai"] {
+            assert!(caps(name).rate, "{name} should have a rate setting");
+        }
+        assert!(!caps("google").rate);
+        assert!(!caps("xai").rate);
+        assert!(!caps("audio_file").voices);
+        assert!(PROVIDER_CAPABILITIES.iter().all(|(_, c)| c.volume));
+
+        let macos = MacOsTtsProvider::new(None, 200, 100);
+        assert_eq!(macos.capabilities(), caps("macos"));
+    }
+
     #[test]
     fn test_resolve_tts_provider_errors_when_engine_absent() {
         let providers: Vec<TtsProviderConfig> = vec![];
-        let result = resolve_tts_provider(&providers, &["google", "gemini"], None, 200, None);
+        let result = resolve_tts_provider(
+            &providers,
+            &["google", "gemini"],
+            None,
+            Some(200),
+            None,
+            None,
+            None,
+        );
         assert!(result.is_err());
     }
 
@@ -665,7 +1146,15 @@ mod tests {
         // config is the single source of truth: an unconfigured engine errors,
         // even the credential-free macOS one.
         let providers: Vec<TtsProviderConfig> = vec![];
-        let result = resolve_tts_provider(&providers, &["macos", "say"], None, 200, None);
+        let result = resolve_tts_provider(
+            &providers,
+            &["macos", "say"],
+            None,
+            Some(200),
+            None,
+            None,
+            None,
+        );
         assert!(result.is_err());
     }
 
@@ -673,7 +1162,7 @@ mod tests {
     fn test_create_tts_empty_providers() {
         let providers: Vec<TtsProviderConfig> = vec![];
 
-        let result = create_tts_from_config(&providers);
+        let result = create_tts_from_config(&providers, None, None);
         assert!(result.is_err());
         let err = result.err().unwrap();
         assert!(err.to_string().contains("No TTS provider"));
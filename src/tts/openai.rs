@@ -7,7 +7,7 @@ use reqwest::Client;
 use serde::Serialize;
 use std::time::Duration;
 
-use super::TtsProvider;
+use super::{TtsCapabilities, TtsProvider};
 use crate::error::{Result, VoiceError};
 
 /// OpenAI speech synthesis endpoint
@@ -84,6 +84,16 @@ impl OpenAiTtsProvider {
     }
 }
 
+/// What SumVox can do with this provider
+pub const CAPABILITIES: TtsCapabilities = TtsCapabilities {
+    ssml: false,
+    // `speed`
+    rate: true,
+    volume: true,
+    voices: true,
+    streaming: false,
+};
+
 #[async_trait]
 impl TtsProvider for OpenAiTtsProvider {
     fn name(&self) -> &str {
@@ -163,6 +173,10 @@ impl TtsProvider for OpenAiTtsProvider {
         Ok(true)
     }
 
+    fn capabilities(&self) -> TtsCapabilities {
+        CAPABILITIES
+    }
+
     fn estimate_cost(&self, char_count: usize) -> f64 {
         char_count as f64 * COST_PER_CHAR
     }
@@ -224,6 +238,15 @@ mod tests {
         assert_eq!(provider(Some(1.5)).speed, Some(1.5));
     }
 
+    #[test]
+    fn test_wpm_sets_speed() {
+        // 210 wpm is 1.2x the 175 wpm baseline
+        assert_eq!(
+            provider(Some(crate::tts::wpm_to_speed(210))).speed,
+            Some(1.2)
+        );
+    }
+
     #[test]
     fn test_cost_estimation() {
         let p = provider(None);
@@ -0,0 +1,126 @@
+// Shorter file paths in spoken text (`tts.shorten_paths`)
+// "/Users/me/proj/src/lib.rs" is tedious to hear. Absolute paths inside the
+// repository are spoken relative to its root ("src/lib.rs"); paths outside
+// it are reduced to their file name. Only the spoken text changes: printed
+// and logged summaries keep the full paths.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::config::SumvoxConfig;
+
+/// Absolute (`/a/b`) or home-relative (`~/a/b`) paths of at least two
+/// components, starting a word
+fn path_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r#"(^|[\s(\["'`])((?:~|/[\w.@+-]+)(?:/[\w.@+-]+)+/?)"#)
+            .expect("path pattern is valid")
+    })
+}
+
+/// Nearest directory at or above `start` that holds a `.git` entry
+pub fn repo_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// `text` with every absolute path made relative to `repo_root`, or cut
+/// down to its file name when it lies outside the repository (or there is
+/// no repository)
+pub fn shorten_paths(text: &str, repo_root: Option<&Path>, home: Option<&Path>) -> String {
+    path_pattern()
+        .replace_all(text, |caps: &regex::Captures| {
+            let raw = &caps[2];
+            // A sentence ending right after a path leaves its period behind
+            let path = raw.trim_end_matches(['.', '/']);
+            let trailing = &raw[path.len()..];
+            format!("{}{}{}", &caps[1], shorten(path, repo_root, home), trailing)
+        })
+        .into_owned()
+}
+
+fn shorten(path: &str, repo_root: Option<&Path>, home: Option<&Path>) -> String {
+    let full = match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    };
+    if let Some(relative) = repo_root.and_then(|root| full.strip_prefix(root).ok()) {
+        if !relative.as_os_str().is_empty() {
+            return relative.to_string_lossy().into_owned();
+        }
+    }
+    full.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// `text` as it should be spoken under `tts.shorten_paths`, with the
+/// repository found from the working directory
+pub fn speakable_paths(config: &SumvoxConfig, text: &str) -> String {
+    if !config.tts.shorten_paths {
+        return text.to_string();
+    }
+    let root = std::env::current_dir().ok().and_then(|dir| repo_root(&dir));
+    shorten_paths(text, root.as_deref(), dirs::home_dir().as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOT: &str = "/Users/me/proj";
+
+    fn shorten_in_repo(text: &str) -> String {
+        shorten_paths(text, Some(Path::new(ROOT)), Some(Path::new("/Users/me")))
+    }
+
+    #[test]
+    fn test_paths_inside_repo_become_relative() {
+        assert_eq!(
+            shorten_in_repo("Updated /Users/me/proj/src/lib.rs and /Users/me/proj/Cargo.toml."),
+            "Updated src/lib.rs and Cargo.toml."
+        );
+        assert_eq!(
+            shorten_in_repo("Fixed (`~/proj/src/main.rs`)"),
+            "Fixed (`src/main.rs`)"
+        );
+    }
+
+    #[test]
+    fn test_paths_outside_repo_become_file_names() {
+        assert_eq!(
+            shorten_in_repo("Edited /etc/hosts and ~/notes/todo.md"),
+            "Edited hosts and todo.md"
+        );
+        // No repository: every path is shortened to its name
+        assert_eq!(
+            shorten_paths("Wrote /tmp/build/out.log", None, None),
+            "Wrote out.log"
+        );
+        // The repository root itself keeps its name
+        assert_eq!(
+            shorten_in_repo("Cloned into /Users/me/proj/"),
+            "Cloned into proj/"
+        );
+    }
+
+    #[test]
+    fn test_non_paths_are_left_alone() {
+        let text = "Use and/or 3/4 of https://example.com/a/b, or src/lib.rs";
+        assert_eq!(shorten_in_repo(text), text);
+    }
+
+    #[test]
+    fn test_repo_root_found_from_subdirectory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        let nested = dir.path().join("src").join("tts");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(repo_root(&nested).as_deref(), Some(dir.path()));
+    }
+}
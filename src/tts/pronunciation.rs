@@ -0,0 +1,125 @@
+// Pronunciation dictionary applied before synthesis
+// `tts.pronunciations` maps terms engines get wrong ("kubectl") to what they
+// should say ("cube control"). Matching is whole-word and case-insensitive;
+// the text around a match is left as it was.
+
+use std::collections::HashMap;
+
+/// Letters, digits and `_` continue a word; anything else ends it
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Length in chars of `term` matched case-insensitively at `start`, if it
+/// matches there and doesn't run into a following word character
+fn match_at(text: &[char], start: usize, term: &[char]) -> Option<usize> {
+    let end = start + term.len();
+    if end > text.len() {
+        return None;
+    }
+    let same = text[start..end]
+        .iter()
+        .zip(term)
+        .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()));
+    let ends_word = term.last().is_some_and(|&c| is_word_char(c));
+    if !same || (ends_word && text.get(end).is_some_and(|&c| is_word_char(c))) {
+        return None;
+    }
+    Some(term.len())
+}
+
+/// Replace every whole-word occurrence of a dictionary term in `text`.
+/// Longer terms win over shorter ones that start at the same place.
+pub fn apply_pronunciations(text: &str, pronunciations: &HashMap<String, String>) -> String {
+    if pronunciations.is_empty() {
+        return text.to_string();
+    }
+
+    let mut terms: Vec<(Vec<char>, &str)> = pronunciations
+        .iter()
+        .map(|(term, spoken)| (term.trim().chars().collect::<Vec<_>>(), spoken.as_str()))
+        .filter(|(term, _)| !term.is_empty())
+        .collect();
+    terms.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let after_word = i > 0 && is_word_char(chars[i - 1]);
+        let replacement = terms.iter().find_map(|(term, spoken)| {
+            if after_word && is_word_char(term[0]) {
+                return None;
+            }
+            match_at(&chars, i, term).map(|len| (len, *spoken))
+        });
+        match replacement {
+            Some((len, spoken)) => {
+                out.push_str(spoken);
+                i += len;
+            }
+            None => {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_whole_word_replacement() {
+        let dict = dictionary(&[("kubectl", "cube control"), ("SQL", "sequel")]);
+        assert_eq!(
+            apply_pronunciations("Ran kubectl apply, then Kubectl get.", &dict),
+            "Ran cube control apply, then cube control get."
+        );
+        assert_eq!(
+            apply_pronunciations("(sql) and SQL: fixed", &dict),
+            "(sequel) and sequel: fixed"
+        );
+    }
+
+    #[test]
+    fn test_substrings_are_not_replaced() {
+        let dict = dictionary(&[("sql", "sequel"), ("use", "yooz")]);
+        assert_eq!(
+            apply_pronunciations("Moved from mysql to SQLite", &dict),
+            "Moved from mysql to SQLite"
+        );
+        assert_eq!(
+            apply_pronunciations("sql_query and users stay, because", &dict),
+            "sql_query and users stay, because"
+        );
+    }
+
+    #[test]
+    fn test_longest_term_wins() {
+        let dict = dictionary(&[("k8s", "kubernetes"), ("k8s api", "the cluster API")]);
+        assert_eq!(
+            apply_pronunciations("The K8s API is up; k8s is fine", &dict),
+            "The the cluster API is up; kubernetes is fine"
+        );
+    }
+
+    #[test]
+    fn test_empty_dictionary_keeps_text() {
+        let text = "Nothing to change here.";
+        assert_eq!(apply_pronunciations(text, &HashMap::new()), text);
+        assert_eq!(
+            apply_pronunciations(text, &dictionary(&[("  ", "blank")])),
+            text
+        );
+    }
+}
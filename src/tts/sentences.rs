@@ -0,0 +1,190 @@
+// Sentence boundary detection for TTS chunking
+// Handles CJK full-width terminators (。！？) as well as Latin `.`, `!`, `?`,
+// without breaking on abbreviations like "e.g." or on decimals like "3.14".
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::Result;
+
+/// Closing punctuation that stays attached to the sentence it ends
+const CLOSERS: &[char] = &['"', '\'', ')', ']', '”', '’', '」', '』', '）', '】'];
+
+/// Lowercased words (without the final dot) that a period doesn't end
+const ABBREVIATIONS: &[&str] = &[
+    "e.g", "i.e", "etc", "vs", "cf", "approx", "mr", "mrs", "ms", "dr", "st", "no", "fig",
+];
+
+fn is_cjk_terminator(c: char) -> bool {
+    matches!(c, '。' | '！' | '？' | '…')
+}
+
+fn is_latin_terminator(c: char) -> bool {
+    matches!(c, '.' | '!' | '?')
+}
+
+/// True when the `.` at `dot` belongs to an abbreviation rather than ending a sentence
+fn is_abbreviation(text: &str, dot: usize) -> bool {
+    let word_start = text[..dot]
+        .rfind(|c: char| c.is_whitespace() || c == '(')
+        .map_or(0, |i| i + 1);
+    let word = text[word_start..dot].to_lowercase();
+    ABBREVIATIONS.contains(&word.as_str())
+}
+
+/// Split text into trimmed, non-empty sentences.
+///
+/// CJK terminators always end a sentence. Latin terminators only do when
+/// followed by whitespace or the end of text, so decimals ("3.14"), versions
+/// and file names stay intact; a period after a known abbreviation doesn't.
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let cjk = is_cjk_terminator(c);
+        if !cjk && !is_latin_terminator(c) {
+            continue;
+        }
+
+        // Swallow runs like "?!", "..." and closing quotes/brackets
+        let mut end = i + c.len_utf8();
+        while let Some(&(j, next)) = chars.peek() {
+            if is_cjk_terminator(next) || is_latin_terminator(next) || CLOSERS.contains(&next) {
+                end = j + next.len_utf8();
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        if !cjk {
+            let at_boundary = chars.peek().is_none_or(|&(_, next)| next.is_whitespace());
+            if !at_boundary || (c == '.' && end == i + 1 && is_abbreviation(text, i)) {
+                continue;
+            }
+        }
+
+        let sentence = text[start..end].trim();
+        if !sentence.is_empty() {
+            sentences.push(sentence.to_string());
+        }
+        start = end;
+    }
+
+    let rest = text[start..].trim();
+    if !rest.is_empty() {
+        sentences.push(rest.to_string());
+    }
+    sentences
+}
+
+/// Speak `sentences` in order, one call each, sleeping `pause` between them.
+/// Each call must finish playback before returning for the pause to be heard.
+/// Stops at the first error.
+pub async fn speak_with_pauses<F, Fut>(
+    sentences: &[String],
+    pause: Duration,
+    mut speak: F,
+) -> Result<()>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    for (i, sentence) in sentences.iter().enumerate() {
+        if i > 0 && !pause.is_zero() {
+            tokio::time::sleep(pause).await;
+        }
+        speak(sentence.clone()).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cjk_terminators() {
+        assert_eq!(
+            split_sentences("測試全部通過。接下來要更新文件！還有問題嗎？"),
+            vec!["測試全部通過。", "接下來要更新文件！", "還有問題嗎？"]
+        );
+        // No whitespace needed after a CJK terminator; closing brackets stay attached
+        assert_eq!(
+            split_sentences("他說「完成了。」然後離開"),
+            vec!["他說「完成了。」", "然後離開"]
+        );
+    }
+
+    #[test]
+    fn test_abbreviations_do_not_split() {
+        assert_eq!(
+            split_sentences("Use a cache, e.g. Redis. Dr. Smith agreed, i.e. ship it."),
+            vec![
+                "Use a cache, e.g. Redis.",
+                "Dr. Smith agreed, i.e. ship it."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decimals_and_versions_do_not_split() {
+        assert_eq!(
+            split_sentences("Coverage rose to 87.5 percent. Bumped to v1.8.0 in main.rs now!"),
+            vec![
+                "Coverage rose to 87.5 percent.",
+                "Bumped to v1.8.0 in main.rs now!"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_terminator_runs_and_mixed_scripts() {
+        assert_eq!(
+            split_sentences("Really?! Yes... 完成。 Done"),
+            vec!["Really?!", "Yes...", "完成。", "Done"]
+        );
+        assert!(split_sentences("   ").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_speak_with_pauses_sequencing() {
+        let sentences = split_sentences("Build passed. Tests are green! Ship it?");
+        let spoken = std::cell::RefCell::new(Vec::new());
+        let pause = Duration::from_millis(30);
+
+        speak_with_pauses(&sentences, pause, |sentence| {
+            spoken
+                .borrow_mut()
+                .push((sentence, std::time::Instant::now()));
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        let spoken = spoken.into_inner();
+        let texts: Vec<&str> = spoken.iter().map(|(t, _)| t.as_str()).collect();
+        assert_eq!(texts, ["Build passed.", "Tests are green!", "Ship it?"]);
+        // A pause between each pair, none before the first
+        for pair in spoken.windows(2) {
+            assert!(pair[1].1.duration_since(pair[0].1) >= pause);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_speak_with_pauses_stops_on_error() {
+        let sentences = split_sentences("One. Two. Three.");
+        let calls = std::cell::RefCell::new(0);
+
+        let result = speak_with_pauses(&sentences, Duration::ZERO, |_| {
+            *calls.borrow_mut() += 1;
+            async { Err(crate::error::VoiceError::Voice("device busy".into())) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.into_inner(), 1);
+    }
+}
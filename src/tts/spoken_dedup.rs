@@ -0,0 +1,170 @@
+// Dedup of spoken text across commands (`tts.global_dedup`)
+// say, sum and json all check here before speaking: text already spoken
+// within `tts.global_dedup_window_secs` is skipped, whichever command spoke
+// it first. Text hashes and times are kept in ~/.cache/sumvox/spoken, which
+// is locked while it is read and rewritten so concurrent processes agree on
+// which one speaks.
+
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::os::unix::fs::FileExt;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use nix::fcntl::{Flock, FlockArg};
+use sha2::{Digest, Sha256};
+
+use crate::config::SumvoxConfig;
+
+/// Recently spoken texts, shared by every sumvox process
+pub struct SpokenLog {
+    path: PathBuf,
+}
+
+impl SpokenLog {
+    /// Log at ~/.cache/sumvox/spoken
+    pub fn from_home() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::at(home.join(".cache").join("sumvox").join("spoken")))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// True when `text` was spoken within `window` before `now`. Otherwise
+    /// records it as spoken at `now` and returns false. Any I/O failure
+    /// counts as not spoken, so dedup never silences a report by mistake.
+    pub fn check_and_record(&self, text: &str, window: Duration, now: SystemTime) -> bool {
+        match self.try_check_and_record(text, window, now) {
+            Ok(repeat) => repeat,
+            Err(e) => {
+                tracing::debug!("Spoken-text dedup unavailable ({:?}): {}", self.path, e);
+                false
+            }
+        }
+    }
+
+    fn try_check_and_record(
+        &self,
+        text: &str,
+        window: Duration,
+        now: SystemTime,
+    ) -> std::io::Result<bool> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        let mut file = Flock::lock(file, FlockArg::LockExclusive).map_err(|(_, e)| e)?;
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+
+        let now_ms = millis(now);
+        let window_ms = window.as_millis() as u64;
+        let hash = text_hash(text);
+        // Drop entries that have aged out of the window
+        let mut entries: Vec<(String, u64)> = content
+            .lines()
+            .filter_map(|line| {
+                let (hash, at) = line.split_once('\t')?;
+                Some((hash.to_string(), at.parse().ok()?))
+            })
+            .filter(|(_, at)| now_ms.saturating_sub(*at) < window_ms)
+            .collect();
+
+        let repeat = entries.iter().any(|(h, _)| *h == hash);
+        if !repeat {
+            entries.push((hash, now_ms));
+        }
+
+        let out: String = entries
+            .iter()
+            .map(|(hash, at)| format!("{}\t{}\n", hash, at))
+            .collect();
+        file.set_len(0)?;
+        file.write_all_at(out.as_bytes(), 0)?;
+        Ok(repeat)
+    }
+}
+
+fn millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Hash of `text` with whitespace differences ignored
+fn text_hash(text: &str) -> String {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let digest = Sha256::digest(normalized.as_bytes());
+    digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// True when `tts.global_dedup` is on and `text` was already spoken within
+/// the window; the caller should then stay quiet. Records `text` otherwise.
+pub fn is_repeat(config: &SumvoxConfig, text: &str) -> bool {
+    if !config.tts.global_dedup || text.trim().is_empty() {
+        return false;
+    }
+    let window = Duration::from_secs(config.tts.global_dedup_window_secs);
+    let repeat = SpokenLog::from_home()
+        .is_some_and(|log| log.check_and_record(text, window, SystemTime::now()));
+    if repeat {
+        tracing::info!("Same text spoken within the last {:?}, skipping", window);
+    }
+    repeat
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(10);
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_700_000_000 + secs)
+    }
+
+    #[test]
+    fn test_repeat_within_window_across_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        // Each command opens its own handle on the shared state file
+        let say = SpokenLog::at(dir.path().join("spoken"));
+        let hook = SpokenLog::at(dir.path().join("spoken"));
+
+        assert!(!say.check_and_record("Build finished", WINDOW, at(0)));
+        assert!(hook.check_and_record("Build finished", WINDOW, at(3)));
+        // Whitespace differences don't make it new text
+        assert!(hook.check_and_record("Build  finished\n", WINDOW, at(4)));
+        assert!(!hook.check_and_record("Tests passed", WINDOW, at(5)));
+    }
+
+    #[test]
+    fn test_text_speaks_again_after_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = SpokenLog::at(dir.path().join("spoken"));
+
+        assert!(!log.check_and_record("Build finished", WINDOW, at(0)));
+        assert!(log.check_and_record("Build finished", WINDOW, at(9)));
+        // The window runs from the first time it was spoken
+        assert!(!log.check_and_record("Build finished", WINDOW, at(10)));
+
+        // Expired entries are pruned from the file
+        let content = std::fs::read_to_string(dir.path().join("spoken")).unwrap();
+        assert_eq!(content.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_unwritable_log_never_skips() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("file"), "").unwrap();
+        let log = SpokenLog::at(dir.path().join("file").join("spoken"));
+        assert!(!log.check_and_record("Build finished", WINDOW, at(0)));
+        assert!(!log.check_and_record("Build finished", WINDOW, at(1)));
+    }
+}
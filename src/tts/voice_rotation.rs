@@ -0,0 +1,129 @@
+// Voice rotation across invocations (`tts.rotate_voices`)
+// Each hook run or command takes the next voice in the list, so consecutive
+// reports don't all sound the same. Everything one run speaks (boundary cue,
+// sentences, summary) shares its voice. The index is persisted at
+// ~/.cache/sumvox/voice-index so it carries over between hook runs.
+
+use std::path::PathBuf;
+
+use crate::config::SumvoxConfig;
+
+/// Persisted position in `tts.rotate_voices`
+pub struct VoiceRotation {
+    path: PathBuf,
+}
+
+impl VoiceRotation {
+    /// Index file at ~/.cache/sumvox/voice-index
+    pub fn from_home() -> Option<Self> {
+        dirs::home_dir()
+            .map(|home| Self::at(home.join(".cache").join("sumvox").join("voice-index")))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Voice for this utterance, advancing the index for the next one.
+    /// None when `voices` is empty.
+    pub fn next<'a>(&self, voices: &'a [String]) -> Option<&'a str> {
+        if voices.is_empty() {
+            return None;
+        }
+        // The list may have shrunk since the index was saved
+        let index = self.index() % voices.len();
+        self.save_index((index + 1) % voices.len());
+        Some(&voices[index])
+    }
+
+    fn index(&self) -> usize {
+        std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    fn save_index(&self, index: usize) {
+        if let Some(parent) = self.path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = std::fs::write(&self.path, index.to_string()) {
+            tracing::debug!("Failed to persist voice index to {:?}: {}", self.path, e);
+        }
+    }
+}
+
+/// `config` with the next rotated voice as `tts.default_voice`, or None when
+/// `tts.rotate_voices` is empty. Call once per invocation, before anything is
+/// spoken, and speak everything with the result.
+pub fn with_next_voice(config: &SumvoxConfig) -> Option<SumvoxConfig> {
+    with_voice_from(&VoiceRotation::from_home()?, config)
+}
+
+fn with_voice_from(rotation: &VoiceRotation, config: &SumvoxConfig) -> Option<SumvoxConfig> {
+    let voice = rotation.next(&config.tts.rotate_voices)?;
+    tracing::debug!("Rotated voice: {}", voice);
+    let mut rotated = config.clone();
+    rotated.tts.default_voice = Some(voice.to_string());
+    Some(rotated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voices(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rotation_advances_and_wraps() {
+        let dir = tempfile::tempdir().unwrap();
+        let rotation = VoiceRotation::at(dir.path().join("voice-index"));
+        let voices = voices(&["Aoede", "Kore", "Puck"]);
+
+        let spoken: Vec<_> = (0..4).filter_map(|_| rotation.next(&voices)).collect();
+        assert_eq!(spoken, ["Aoede", "Kore", "Puck", "Aoede"]);
+
+        // Persisted: a new handle on the same file continues the cycle
+        let later = VoiceRotation::at(dir.path().join("voice-index"));
+        assert_eq!(later.next(&voices), Some("Kore"));
+    }
+
+    #[test]
+    fn test_rotation_handles_shrunk_and_empty_lists() {
+        let dir = tempfile::tempdir().unwrap();
+        let rotation = VoiceRotation::at(dir.path().join("voice-index"));
+        std::fs::write(dir.path().join("voice-index"), "5").unwrap();
+
+        assert_eq!(rotation.next(&voices(&["Aoede", "Kore"])), Some("Kore"));
+        assert_eq!(rotation.next(&voices(&["Aoede", "Kore"])), Some("Aoede"));
+        assert_eq!(rotation.next(&[]), None);
+    }
+
+    #[test]
+    fn test_cue_and_summary_share_the_invocation_voice() {
+        let dir = tempfile::tempdir().unwrap();
+        let rotation = VoiceRotation::at(dir.path().join("voice-index"));
+        let mut config = SumvoxConfig::default();
+        config.tts.rotate_voices = voices(&["Aoede", "Kore"]);
+
+        // Two hook runs, each a boundary cue followed by its summary
+        let mut spoken = Vec::new();
+        for _ in 0..2 {
+            let run = with_voice_from(&rotation, &config).unwrap();
+            for utterance in ["cue", "summary"] {
+                spoken.push((utterance, run.tts.default_voice.clone().unwrap()));
+            }
+        }
+        assert_eq!(
+            spoken,
+            [
+                ("cue", "Aoede".to_string()),
+                ("summary", "Aoede".to_string()),
+                ("cue", "Kore".to_string()),
+                ("summary", "Kore".to_string()),
+            ]
+        );
+    }
+}
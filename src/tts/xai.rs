@@ -6,7 +6,7 @@ use reqwest::Client;
 use serde::Serialize;
 use std::time::Duration;
 
-use super::TtsProvider;
+use super::{TtsCapabilities, TtsProvider};
 use crate::error::{Result, VoiceError};
 
 /// xAI TTS API endpoint
@@ -72,6 +72,15 @@ impl XaiTtsProvider {
     }
 }
 
+/// What SumVox can do with this provider
+pub const CAPABILITIES: TtsCapabilities = TtsCapabilities {
+    ssml: false,
+    rate: false,
+    volume: true,
+    voices: true,
+    streaming: false,
+};
+
 #[async_trait]
 impl TtsProvider for XaiTtsProvider {
     fn name(&self) -> &str {
@@ -149,6 +158,10 @@ impl TtsProvider for XaiTtsProvider {
         Ok(true)
     }
 
+    fn capabilities(&self) -> TtsCapabilities {
+        CAPABILITIES
+    }
+
     fn estimate_cost(&self, char_count: usize) -> f64 {
         char_count as f64 * COST_PER_CHAR
     }
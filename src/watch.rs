@@ -0,0 +1,216 @@
+// Transcript watcher for `sumvox watch`
+// Polls a transcript for growth and hands over the assistant texts added
+// since the last update, the same delta the incremental Stop hook reads.
+// A turn counts as complete once the file stops growing for one poll.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::transcript::TranscriptReader;
+
+/// Tracks how far into a transcript updates have been delivered
+pub struct TranscriptWatcher {
+    path: PathBuf,
+    /// Non-empty lines already covered by an update
+    marker: usize,
+    /// Line count at the previous poll
+    last_seen: usize,
+}
+
+impl TranscriptWatcher {
+    /// Start watching at the current end of `path`; earlier content is skipped
+    pub async fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let lines = if path.exists() {
+            TranscriptReader::line_count(&path).await?
+        } else {
+            0
+        };
+        Ok(Self {
+            path,
+            marker: lines,
+            last_seen: lines,
+        })
+    }
+
+    /// Check the transcript once. Returns the assistant texts added since the
+    /// last update when the file grew and has stopped growing since the
+    /// previous poll; None while it is still being written or unchanged.
+    pub async fn poll(&mut self) -> Result<Option<Vec<String>>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let lines = TranscriptReader::line_count(&self.path).await?;
+        if lines < self.marker {
+            // Rewritten (e.g. compacted): start over from its new end
+            tracing::debug!("Transcript shrank to {} lines, resetting", lines);
+            self.marker = lines;
+            self.last_seen = lines;
+            return Ok(None);
+        }
+
+        let settled = lines == self.last_seen;
+        self.last_seen = lines;
+        if !settled || lines == self.marker {
+            return Ok(None);
+        }
+
+        let (texts, line_count) =
+            TranscriptReader::read_texts_after(&self.path, self.marker).await?;
+        self.marker = line_count;
+        self.last_seen = line_count;
+        Ok((!texts.is_empty()).then_some(texts))
+    }
+}
+
+/// Poll `watcher` every `poll_interval` and call `on_update` with each
+/// completed delta, at most once per `min_interval`. Texts that arrive
+/// sooner are held back and delivered together with the next update.
+pub async fn watch<F, Fut>(
+    mut watcher: TranscriptWatcher,
+    poll_interval: Duration,
+    min_interval: Duration,
+    mut on_update: F,
+) -> Result<()>
+where
+    F: FnMut(Vec<String>) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut pending: Vec<String> = Vec::new();
+    let mut last_update: Option<Instant> = None;
+
+    loop {
+        if let Some(texts) = watcher.poll().await? {
+            pending.extend(texts);
+        }
+
+        let due = last_update.is_none_or(|at| at.elapsed() >= min_interval);
+        if !pending.is_empty() && due {
+            if let Err(e) = on_update(std::mem::take(&mut pending)).await {
+                tracing::warn!("Watch update failed: {}", e);
+            }
+            last_update = Some(Instant::now());
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn append(path: &Path, lines: &[&str]) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        for line in lines {
+            writeln!(file, "{}", line).unwrap();
+        }
+    }
+
+    fn user(text: &str) -> String {
+        format!(
+            r#"{{"type":"user","message":{{"role":"user","content":"{}"}}}}"#,
+            text
+        )
+    }
+
+    fn assistant(text: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","message":{{"role":"assistant","content":[{{"type":"text","text":"{}"}}]}}}}"#,
+            text
+        )
+    }
+
+    #[tokio::test]
+    async fn test_growth_reported_once_settled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        append(&path, &[&user("Old task"), &assistant("Old reply")]);
+
+        let mut watcher = TranscriptWatcher::new(&path).await.unwrap();
+        // Existing content is not reported
+        assert_eq!(watcher.poll().await.unwrap(), None);
+
+        append(
+            &path,
+            &[&user("Fix the parser"), &assistant("Looking at it")],
+        );
+        // Still growing at this poll
+        assert_eq!(watcher.poll().await.unwrap(), None);
+        append(&path, &[&assistant("Parser fixed")]);
+        assert_eq!(watcher.poll().await.unwrap(), None);
+
+        // Unchanged since the last poll: the turn is complete
+        assert_eq!(
+            watcher.poll().await.unwrap(),
+            Some(vec![
+                "Looking at it".to_string(),
+                "Parser fixed".to_string()
+            ])
+        );
+        assert_eq!(watcher.poll().await.unwrap(), None);
+
+        // The next turn only brings its own texts
+        append(&path, &[&user("Run tests"), &assistant("All green")]);
+        assert_eq!(watcher.poll().await.unwrap(), None);
+        assert_eq!(
+            watcher.poll().await.unwrap(),
+            Some(vec!["All green".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_user_only_growth_and_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+
+        let mut watcher = TranscriptWatcher::new(&path).await.unwrap();
+        assert_eq!(watcher.poll().await.unwrap(), None);
+
+        append(&path, &[&user("Hello")]);
+        assert_eq!(watcher.poll().await.unwrap(), None);
+        // Settled, but nothing the assistant said
+        assert_eq!(watcher.poll().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_watch_respects_min_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        append(&path, &[&user("Start")]);
+        let watcher = TranscriptWatcher::new(&path).await.unwrap();
+
+        let writer_path = path.clone();
+        let writer = tokio::spawn(async move {
+            for i in 0..3 {
+                tokio::time::sleep(Duration::from_millis(40)).await;
+                append(&writer_path, &[&assistant(&format!("Step {}", i))]);
+            }
+        });
+
+        let updates = std::sync::Mutex::new(Vec::new());
+        let run = watch(
+            watcher,
+            Duration::from_millis(10),
+            Duration::from_secs(3600),
+            |texts| {
+                updates.lock().unwrap().push(texts);
+                async { Ok(()) }
+            },
+        );
+        let _ = tokio::time::timeout(Duration::from_millis(300), run).await;
+        writer.await.unwrap();
+
+        // The first completed step is spoken; later ones wait for min_interval
+        let updates = updates.into_inner().unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0][0], "Step 0");
+    }
+}
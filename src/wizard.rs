@@ -0,0 +1,345 @@
+// Interactive setup wizard for `sumvox init --interactive`
+// Questions go through the `Prompter` trait so the config assembly can be
+// driven by scripted answers in tests.
+
+use std::io::{BufRead, Write};
+
+use crate::config::{LlmAuth, LlmProviderConfig, SumvoxConfig, TtsProviderConfig};
+use crate::error::{Result, VoiceError};
+
+/// LLM providers offered by the wizard, with the model suggested for each
+const LLM_CHOICES: &[(&str, &str)] = &[
+    ("google", "gemini-3.1-flash-lite"),
+    ("anthropic", "claude-haiku-4-5-20251001"),
+    ("openai", "gpt-5-nano"),
+    ("xai", "grok-build-0.1"),
+    ("ollama", "llama3.2"),
+];
+
+/// TTS engines offered by the wizard
+const TTS_CHOICES: &[&str] = &["macos", "google", "openai", "xai", "elevenlabs"];
+
+/// Give up after this many invalid answers to one question
+const MAX_ATTEMPTS: usize = 3;
+
+/// Source of answers for the wizard
+pub trait Prompter {
+    /// Ask a free-form question; an empty answer means `default`
+    fn ask(&mut self, question: &str, default: &str) -> Result<String>;
+
+    /// Ask for a secret without echoing it
+    fn ask_secret(&mut self, question: &str) -> Result<String>;
+
+    /// Ask a yes/no question
+    fn confirm(&mut self, question: &str, default: bool) -> Result<bool> {
+        let hint = if default { "Y/n" } else { "y/N" };
+        for _ in 0..MAX_ATTEMPTS {
+            let answer = self.ask(&format!("{} [{}]", question, hint), "")?;
+            match answer.trim().to_lowercase().as_str() {
+                "" => return Ok(default),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => eprintln!("Please answer y or n"),
+            }
+        }
+        Err(VoiceError::Config(format!(
+            "No valid answer for: {}",
+            question
+        )))
+    }
+}
+
+/// Prompter reading answers from the terminal (secrets via rpassword)
+pub struct TerminalPrompter;
+
+impl Prompter for TerminalPrompter {
+    fn ask(&mut self, question: &str, default: &str) -> Result<String> {
+        if default.is_empty() {
+            eprint!("{}: ", question);
+        } else {
+            eprint!("{} [{}]: ", question, default);
+        }
+        std::io::stderr().flush()?;
+
+        let mut line = String::new();
+        std::io::stdin().lock().read_line(&mut line)?;
+        let answer = line.trim();
+        Ok(if answer.is_empty() {
+            default.to_string()
+        } else {
+            answer.to_string()
+        })
+    }
+
+    fn ask_secret(&mut self, question: &str) -> Result<String> {
+        Ok(rpassword::prompt_password(format!("{}: ", question))?
+            .trim()
+            .to_string())
+    }
+}
+
+/// Ask `question` until the answer is one of `choices` (case-insensitive)
+fn ask_choice(
+    prompter: &mut dyn Prompter,
+    question: &str,
+    choices: &[&str],
+    default: &str,
+) -> Result<String> {
+    let question = format!("{} ({})", question, choices.join("/"));
+    for _ in 0..MAX_ATTEMPTS {
+        let answer = prompter.ask(&question, default)?.trim().to_lowercase();
+        if choices.contains(&answer.as_str()) {
+            return Ok(answer);
+        }
+        eprintln!("Unknown choice '{}'", answer);
+    }
+    Err(VoiceError::Config(format!(
+        "No valid answer for: {}",
+        question
+    )))
+}
+
+/// Ask for an optional positive USD amount (empty = none)
+fn ask_cost_cap(prompter: &mut dyn Prompter) -> Result<Option<f64>> {
+    let question = "Maximum estimated cost per summary in USD (empty for no cap)";
+    for _ in 0..MAX_ATTEMPTS {
+        let answer = prompter.ask(question, "")?;
+        let answer = answer.trim().trim_start_matches('$');
+        if answer.is_empty() {
+            return Ok(None);
+        }
+        match answer.parse::<f64>() {
+            Ok(cap) if cap > 0.0 && cap.is_finite() => return Ok(Some(cap)),
+            _ => eprintln!("Enter a positive number such as 0.05"),
+        }
+    }
+    Err(VoiceError::Config(format!(
+        "No valid answer for: {}",
+        question
+    )))
+}
+
+/// Assemble a config from the answers, starting from `base`.
+///
+/// The chosen LLM provider and TTS engine move to the front of their
+/// fallback chains; the rest of `base` is kept.
+pub fn build_config(prompter: &mut dyn Prompter, base: SumvoxConfig) -> Result<SumvoxConfig> {
+    let mut config = base;
+
+    // LLM provider, model and key
+    let names: Vec<&str> = LLM_CHOICES.iter().map(|(name, _)| *name).collect();
+    let llm_name = ask_choice(prompter, "LLM provider", &names, "google")?;
+    let suggested_model = config
+        .llm
+        .providers
+        .iter()
+        .find(|p| p.name == llm_name)
+        .map(|p| p.model.clone())
+        .or_else(|| {
+            LLM_CHOICES
+                .iter()
+                .find(|(name, _)| *name == llm_name)
+                .map(|(_, model)| model.to_string())
+        })
+        .unwrap_or_default();
+    let model = prompter.ask("Model", &suggested_model)?;
+
+    let mut llm = take_llm_provider(&mut config.llm.providers, &llm_name);
+    llm.model = model;
+    if llm_name != "ollama" {
+        let env_var = LlmProviderConfig::env_var_name(&llm_name);
+        let store_key = prompter.confirm(
+            &format!(
+                "Save an API key in the config file? (otherwise {} is used)",
+                env_var
+            ),
+            false,
+        )?;
+        if store_key {
+            let key = prompter.ask_secret("API key")?;
+            llm.api_key = (!key.is_empty()).then(|| key.into());
+        }
+    }
+    config.llm.providers.insert(0, llm);
+
+    // TTS engine and voice
+    let tts_name = ask_choice(prompter, "TTS engine", TTS_CHOICES, "macos")?;
+    let voice = prompter.ask("Voice (empty for the engine default)", "")?;
+    let mut tts = take_tts_provider(&mut config.tts.providers, &tts_name)?;
+    if !voice.trim().is_empty() {
+        tts.voice = Some(voice.trim().to_string());
+    }
+    config.tts.providers.insert(0, tts);
+
+    // Spending limit
+    config.cost.max_per_call_usd = ask_cost_cap(prompter)?;
+
+    let errors = config.validation_errors();
+    if !errors.is_empty() {
+        return Err(VoiceError::InvalidConfig(errors));
+    }
+    Ok(config)
+}
+
+/// Remove and return the `name` entry from `providers`, or a fresh one
+fn take_llm_provider(providers: &mut Vec<LlmProviderConfig>, name: &str) -> LlmProviderConfig {
+    match providers.iter().position(|p| p.name == name) {
+        Some(index) => providers.remove(index),
+        None => LlmProviderConfig {
+            name: name.to_string(),
+            model: String::new(),
+            api_key: None,
+            base_url: None,
+            timeout: 10,
+            disable_thinking: None,
+            auth: LlmAuth::ApiKey,
+            region: None,
+            models: Vec::new(),
+        },
+    }
+}
+
+/// Remove and return the `name` entry from `providers`, or a fresh one
+fn take_tts_provider(
+    providers: &mut Vec<TtsProviderConfig>,
+    name: &str,
+) -> Result<TtsProviderConfig> {
+    match providers.iter().position(|p| p.name == name) {
+        Some(index) => Ok(providers.remove(index)),
+        None => {
+            serde_json::from_value(serde_json::json!({ "name": name })).map_err(VoiceError::Json)
+        }
+    }
+}
+
+/// One-line-per-setting overview shown before asking to write the file
+pub fn describe(config: &SumvoxConfig) -> String {
+    let llm = config.llm.providers.first();
+    let tts = config.tts.providers.first();
+    let mut lines = vec![
+        format!(
+            "LLM: {} ({})",
+            llm.map(|p| p.name.as_str()).unwrap_or("none"),
+            llm.map(|p| p.model.as_str()).unwrap_or("-")
+        ),
+        format!(
+            "API key: {}",
+            match llm.and_then(|p| p.api_key.as_ref()) {
+                Some(_) => "saved in config".to_string(),
+                None => format!(
+                    "from {}",
+                    LlmProviderConfig::env_var_name(llm.map(|p| p.name.as_str()).unwrap_or(""))
+                ),
+            }
+        ),
+        format!(
+            "TTS: {} (voice: {})",
+            tts.map(|p| p.name.as_str()).unwrap_or("none"),
+            tts.and_then(|p| p.voice.as_deref()).unwrap_or("default")
+        ),
+    ];
+    lines.push(match config.cost.max_per_call_usd {
+        Some(cap) => format!("Cost cap: ${} per summary", cap),
+        None => "Cost cap: none".to_string(),
+    });
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Answers questions from a fixed script, in order
+    struct ScriptedPrompter {
+        answers: VecDeque<&'static str>,
+        questions: Vec<String>,
+    }
+
+    impl ScriptedPrompter {
+        fn new(answers: &[&'static str]) -> Self {
+            Self {
+                answers: answers.iter().copied().collect(),
+                questions: Vec::new(),
+            }
+        }
+    }
+
+    impl Prompter for ScriptedPrompter {
+        fn ask(&mut self, question: &str, default: &str) -> Result<String> {
+            self.questions.push(question.to_string());
+            let answer = self.answers.pop_front().expect("ran out of answers");
+            Ok(if answer.is_empty() {
+                default.to_string()
+            } else {
+                answer.to_string()
+            })
+        }
+
+        fn ask_secret(&mut self, question: &str) -> Result<String> {
+            self.ask(question, "")
+        }
+    }
+
+    #[test]
+    fn test_build_config_from_answers() {
+        let mut prompter = ScriptedPrompter::new(&[
+            "anthropic",   // LLM provider
+            "",            // model: keep suggestion
+            "y",           // save key
+            "sk-ant-test", // key
+            "elevenlabs",  // TTS engine
+            "Rachel",      // voice
+            "0.05",        // cost cap
+        ]);
+
+        let config = build_config(&mut prompter, SumvoxConfig::default()).unwrap();
+
+        let llm = &config.llm.providers[0];
+        assert_eq!(llm.name, "anthropic");
+        assert_eq!(llm.model, "claude-haiku-4-5-20251001");
+        assert_eq!(llm.get_api_key().as_deref(), Some("sk-ant-test"));
+        // The rest of the default chain is kept, without a duplicate
+        assert_eq!(
+            config
+                .llm
+                .providers
+                .iter()
+                .filter(|p| p.name == "anthropic")
+                .count(),
+            1
+        );
+
+        assert_eq!(config.tts.providers[0].name, "elevenlabs");
+        assert_eq!(config.tts.providers[0].voice.as_deref(), Some("Rachel"));
+        assert_eq!(config.cost.max_per_call_usd, Some(0.05));
+        assert!(prompter.answers.is_empty());
+    }
+
+    #[test]
+    fn test_build_config_reasks_invalid_answers() {
+        let mut prompter = ScriptedPrompter::new(&[
+            "gpt",    // not a choice
+            "ollama", // LLM provider (no key question)
+            "",       // model
+            "",       // TTS engine: default macos
+            "",       // voice
+            "-1",     // invalid cost
+            "",       // no cap
+        ]);
+
+        let config = build_config(&mut prompter, SumvoxConfig::default()).unwrap();
+
+        assert_eq!(config.llm.providers[0].name, "ollama");
+        assert!(config.llm.providers[0].api_key.is_none());
+        assert_eq!(config.tts.providers[0].name, "macos");
+        assert_eq!(config.cost.max_per_call_usd, None);
+        assert!(!prompter.questions.iter().any(|q| q.contains("API key")));
+    }
+
+    #[test]
+    fn test_build_config_gives_up_after_repeated_invalid_answers() {
+        let mut prompter = ScriptedPrompter::new(&["a", "b", "c"]);
+        assert!(build_config(&mut prompter, SumvoxConfig::default()).is_err());
+    }
+}
@@ -412,6 +412,20 @@ fn test_sum_no_llm_config() {
         .stderr(predicate::str::contains("Empty summary generated"));
 }
 
+#[test]
+fn test_sum_output_json_no_llm_config() {
+    let env = TestEnv::new();
+    env.setup_with_config(&config_without_llm());
+
+    // Scripts always get a JSON object, even when no provider produced a summary
+    env.cmd()
+        .args(["sum", "Hello world", "--no-speak", "--output-json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"summary\": \"\""))
+        .stdout(predicate::str::contains("\"provider\": null"));
+}
+
 // ============================================================================
 // TTS — say Command (4)
 // ============================================================================
@@ -613,6 +627,72 @@ fn test_stop_hook_active() {
         .stdout(predicate::str::contains("preventing infinite loop"));
 }
 
+#[test]
+fn test_notification_printed_without_tts() {
+    let env = TestEnv::new();
+    env.setup_with_config(&config_no_tts());
+
+    let json = notification_json("Build finished", "idle_prompt");
+
+    // No TTS provider at all: the text still reaches stdout
+    env.cmd()
+        .arg("json")
+        .write_stdin(json.clone())
+        .assert()
+        .stdout(predicate::str::is_match("(?m)^Build finished$").unwrap());
+
+    env.cmd()
+        .args(["json", "--print-summary=false"])
+        .write_stdin(json)
+        .assert()
+        .stdout(
+            predicate::str::is_match("(?m)^Build finished$")
+                .unwrap()
+                .not(),
+        );
+}
+
+#[test]
+fn test_stop_fallback_printed_without_tts() {
+    let env = TestEnv::new();
+    env.setup_with_config(&config_no_tts());
+
+    let json = serde_json::json!({
+        "session_id": "e2e-test",
+        "transcript_path": env.home_path().join("moved-away.jsonl"),
+        "hook_event_name": "Stop"
+    })
+    .to_string();
+
+    env.cmd()
+        .arg("json")
+        .write_stdin(json)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("(?m)^Test completed$").unwrap());
+}
+
+#[test]
+fn test_stop_missing_transcript_speaks_fallback() {
+    let env = TestEnv::new();
+    env.setup_with_config(&config_no_tts());
+
+    let json = serde_json::json!({
+        "session_id": "e2e-test",
+        "transcript_path": env.home_path().join("moved-away.jsonl"),
+        "hook_event_name": "Stop"
+    })
+    .to_string();
+
+    // A vanished transcript must not fail the hook
+    env.cmd_debug()
+        .arg("json")
+        .write_stdin(json)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Transcript not found"));
+}
+
 // ============================================================================
 // Notification Queue (3)
 // ============================================================================